@@ -1,8 +1,9 @@
 use anyhow::Result;
 use modelcontextprotocol_client::mcp_protocol::types::completion::{
-    CompleteRequest, CompletionArgument, CompletionReference,
+    CompleteRequest, CompletionArgument, CompletionContext, CompletionReference,
 };
 use modelcontextprotocol_client::{transport::StdioTransport, ClientBuilder};
+use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io;
 use std::sync::Arc;
@@ -87,6 +88,8 @@ async fn main() -> Result<()> {
             name: "project".to_string(),
             value: "b".to_string(), // Should match 'backend'
         },
+        context: None,
+        meta: None,
     };
 
     let project_completion = client.complete(project_completion_request).await?;
@@ -105,6 +108,14 @@ async fn main() -> Result<()> {
             name: "filename".to_string(),
             value: "m".to_string(), // Should match 'main.rs' etc.
         },
+        // The project chosen above narrows the filenames offered
+        context: Some(CompletionContext {
+            arguments: Some(HashMap::from([(
+                "project".to_string(),
+                "backend".to_string(),
+            )])),
+        }),
+        meta: None,
     };
 
     let filename_completion = client.complete(filename_completion_request).await?;
@@ -123,6 +134,8 @@ async fn main() -> Result<()> {
             name: "language".to_string(),
             value: "py".to_string(),
         },
+        context: None,
+        meta: None,
     };
 
     let prompt_completion = client.complete(prompt_completion_request).await?;
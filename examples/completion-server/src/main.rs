@@ -1,10 +1,10 @@
 use anyhow::Result;
 use modelcontextprotocol_server::mcp_protocol::types::{
-    completion::CompletionItem,
-    prompt::{Prompt, PromptArgument, PromptMessage, PromptMessageContent},
-    resource::{Resource, ResourceTemplate},
+    content::ContentBlock,
+    prompt::{Prompt, PromptArgument, PromptMessage},
+    resource::{Resource, ResourceContent, ResourceTemplate},
 };
-use modelcontextprotocol_server::{transport::StdioTransport, ServerBuilder};
+use modelcontextprotocol_server::{transport::StdioTransport, CompletionQuery, ServerBuilder};
 use std::fs::OpenOptions;
 use std::io;
 use std::sync::Arc;
@@ -51,89 +51,49 @@ async fn main() -> Result<()> {
         description: Some("Access files within a project".to_string()),
         mime_type: Some("application/octet-stream".to_string()),
         annotations: None,
+        title: None,
     };
 
-    // Register template with a simple expander
-    resource_manager.register_template(template.clone(), |template_uri, params| {
-        let mut result = template_uri;
+    // Register template with a read handler
+    resource_manager.register_template(template.clone(), |params| {
+        let project = params.get("project").cloned().unwrap_or_default();
+        let filename = params.get("filename").cloned().unwrap_or_default();
 
-        for (name, value) in params {
-            result = result.replace(&format!("{{{}}}", name), &value);
-        }
-
-        Ok(result)
+        Ok(vec![ResourceContent {
+            uri: format!("file:///{}/{}", project, filename),
+            mime_type: "text/plain".to_string(),
+            text: Some(format!("Contents of {} in project {}", filename, project)),
+            blob: None,
+            meta: None,
+        }])
     });
 
     // Register completion provider for the template
     resource_manager.register_completion_provider(
         &template.uri_template,
-        move |_, param_name, value| {
+        |query: CompletionQuery| async move {
             debug!(
-                "Getting completions for parameter: {} with value: {:?}",
-                param_name, value
+                "Getting completions for parameter: {} with value: {:?} and context: {:?}",
+                query.argument, query.value, query.arguments
             );
 
-            match param_name.as_str() {
+            match query.argument.as_str() {
+                // Provide a list of example projects
                 "project" => {
-                    // Provide a list of example projects
-                    let projects = vec!["backend", "frontend", "common", "tools", "docs"];
-
-                    let filtered = if let Some(prefix) = value {
-                        projects
-                            .into_iter()
-                            .filter(|p| p.starts_with(&prefix))
-                            .collect::<Vec<&str>>()
-                    } else {
-                        projects
-                    };
-
-                    let items = filtered
-                        .into_iter()
-                        .map(|p| CompletionItem {
-                            label: p.to_string(),
-                            detail: Some(format!("Project directory: {}", p)),
-                            documentation: None,
-                        })
-                        .collect();
-
-                    Ok(items)
+                    Ok(query.prefix_matches(["backend", "frontend", "common", "tools", "docs"]))
                 }
                 "filename" => {
-                    // Provide some example filenames based on the project
-                    let filenames = match value {
-                        Some(val) if val.starts_with("main") => {
-                            vec!["main.rs", "main.go", "main.py", "main.js"]
-                        }
-                        Some(val) if val.starts_with("config") => {
-                            vec!["config.json", "config.yaml", "config.toml"]
-                        }
-                        Some(val) => {
-                            // Filter by prefix
-                            vec!["api.rs", "utils.rs", "models.rs", "config.rs", "main.rs"]
-                                .into_iter()
-                                .filter(|f| f.starts_with(&val))
-                                .collect()
-                        }
-                        None => {
-                            vec!["api.rs", "utils.rs", "models.rs", "config.rs", "main.rs"]
-                        }
+                    // Narrow the filenames by the project the client already chose
+                    let filenames = match query.arguments.get("project").map(String::as_str) {
+                        Some("frontend") => vec!["index.ts", "main.ts", "app.tsx", "config.ts"],
+                        Some("docs") => vec!["index.md", "guide.md", "config.md"],
+                        _ => vec!["api.rs", "utils.rs", "models.rs", "config.rs", "main.rs"],
                     };
 
-                    let items = filenames
-                        .into_iter()
-                        .map(|f| CompletionItem {
-                            label: f.to_string(),
-                            detail: Some(format!("File: {}", f)),
-                            documentation: None,
-                        })
-                        .collect();
-
-                    Ok(items)
-                }
-                _ => {
-                    // Unknown parameter
-                    Ok(vec![])
+                    Ok(query.fuzzy_matches(filenames))
                 }
+                // Unknown parameter
+                _ => Ok(vec![]),
             }
         },
     );
@@ -146,6 +106,7 @@ async fn main() -> Result<()> {
         mime_type: Some("text/x-rust".to_string()),
         annotations: None,
         size: None,
+        title: None,
     };
 
     resource_manager.register_resource(resource, || {
@@ -154,6 +115,7 @@ async fn main() -> Result<()> {
             mime_type: "text/x-rust".to_string(),
             text: Some("fn main() {\n    println!(\"Hello from the backend!\");\n}".to_string()),
             blob: None,
+            meta: None,
         };
         Ok(vec![content])
     });
@@ -171,14 +133,17 @@ async fn main() -> Result<()> {
                 name: "language".to_string(),
                 description: Some("Programming language".to_string()),
                 required: Some(true),
+                title: None,
             },
             PromptArgument {
                 name: "focus".to_string(),
                 description: Some("Review focus area".to_string()),
                 required: Some(false),
+                title: None,
             },
         ]),
         annotations: None,
+        title: None,
     };
 
     // Register the prompt
@@ -197,42 +162,36 @@ async fn main() -> Result<()> {
 
         let message = PromptMessage {
             role: "system".to_string(),
-            content: PromptMessageContent::Text {
-                text: format!("Reviewing {} code with focus on {}", language, focus),
-            },
+            content: ContentBlock::text(format!(
+                "Reviewing {} code with focus on {}",
+                language, focus
+            )),
         };
 
         Ok(vec![message])
     });
 
     // Register language completion provider for the prompt
-    prompt_manager.register_completion_provider("code_review", "language", |_, prefix| {
-        let languages = vec![
-            "python".to_string(),
-            "javascript".to_string(),
-            "typescript".to_string(),
-            "rust".to_string(),
-            "go".to_string(),
-            "java".to_string(),
-            "c#".to_string(),
-            "c++".to_string(),
-            "php".to_string(),
-            "ruby".to_string(),
-            "kotlin".to_string(),
-            "swift".to_string(),
-        ];
-
-        let filtered_langs = if let Some(prefix) = prefix {
-            languages
-                .into_iter()
-                .filter(|lang| lang.starts_with(&prefix.to_lowercase()))
-                .collect()
-        } else {
-            languages
-        };
-
-        Ok(filtered_langs)
-    });
+    prompt_manager.register_completion_provider(
+        "code_review",
+        "language",
+        |query: CompletionQuery| async move {
+            Ok(query.fuzzy_matches([
+                "python",
+                "javascript",
+                "typescript",
+                "rust",
+                "go",
+                "java",
+                "c#",
+                "c++",
+                "php",
+                "ruby",
+                "kotlin",
+                "swift",
+            ]))
+        },
+    );
 
     // Build the server
     let server = server_builder.build()?;
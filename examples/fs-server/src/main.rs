@@ -0,0 +1,53 @@
+use anyhow::Result;
+use modelcontextprotocol_server::resources::{fs::FileSystemProvider, ResourceManager};
+use modelcontextprotocol_server::{transport::StdioTransport, ServerBuilder};
+use std::fs::OpenOptions;
+use std::io;
+use std::sync::Arc;
+use tracing::{info, Level};
+use tracing_subscriber::fmt;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Initialize logging to a file
+    let subscriber = fmt::Subscriber::builder()
+        .with_max_level(Level::DEBUG)
+        .with_writer(move || -> Box<dyn io::Write> {
+            Box::new(io::BufWriter::new(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open("fs-server.log")
+                    .unwrap(),
+            ))
+        })
+        .with_ansi(true)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("Failed to set default tracing subscriber");
+
+    // Serve the directory given on the command line, or the current directory
+    let root = std::env::args().nth(1).unwrap_or_else(|| ".".to_string());
+    info!("Starting fs-server MCP server for {}", root);
+
+    // Expose the directory tree, skipping build output and version control
+    let resource_manager = Arc::new(ResourceManager::new());
+    let _watcher = FileSystemProvider::new(&root)?
+        .with_exclude("target/**")
+        .with_exclude(".git/**")
+        .watch(resource_manager.clone())?;
+
+    let server = ServerBuilder::new("fs-server", "0.1.0")
+        .with_transport(StdioTransport::new())
+        .with_resource_manager(resource_manager)
+        .build()?;
+
+    info!("Server initialized. Waiting for client connection...");
+
+    // Run server (blocks until shutdown)
+    server.run().await?;
+
+    info!("Server shutting down");
+
+    Ok(())
+}
@@ -1,12 +1,39 @@
 use anyhow::Result;
-use modelcontextprotocol_server::mcp_protocol::types::tool::{ToolCallResult, ToolContent};
+use modelcontextprotocol_server::mcp_protocol::types::{
+    content::ContentBlock, tool::ToolCallResult,
+};
 use modelcontextprotocol_server::{transport::StdioTransport, ServerBuilder};
-use serde_json::json;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
 use std::io;
 use tracing::{debug, info, Level};
 use tracing_subscriber::fmt;
 
+/// Arguments for the hello tool
+#[derive(Debug, Deserialize, JsonSchema)]
+struct HelloArgs {
+    /// Name of the person to greet
+    name: String,
+}
+
+/// Arguments for the word-count tool
+#[derive(Debug, Deserialize, JsonSchema)]
+struct WordCountArgs {
+    /// Text to count words in
+    text: String,
+}
+
+/// Structured output of the word-count tool
+#[derive(Debug, Serialize, JsonSchema)]
+struct WordCount {
+    /// Number of whitespace-separated words
+    words: usize,
+
+    /// Number of characters
+    characters: usize,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging to a file
@@ -37,33 +64,31 @@ async fn main() -> Result<()> {
     // Create server with stdio transport
     let server = ServerBuilder::new("hello-world", "0.1.0")
         .with_transport(StdioTransport::new())
-        .with_tool(
+        .with_typed_tool(
             "hello",
             Some("Say hello to someone"),
-            json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Name of the person to greet"
-                    }
-                },
-                "required": ["name"]
-            }),
-            |args| {
+            |args: HelloArgs| {
                 debug!("Hello tool called with args: {:?}", args);
 
-                let name = args.get("name").and_then(|v| v.as_str()).unwrap_or("world");
-
-                debug!("Greeting {}", name);
+                debug!("Greeting {}", args.name);
 
-                let content = vec![ToolContent::Text {
-                    text: format!("Hello, {}!", name),
-                }];
+                let content = vec![ContentBlock::text(format!("Hello, {}!", args.name))];
 
                 Ok(ToolCallResult {
                     content,
                     is_error: Some(false),
+                    structured_content: None,
+                    meta: None,
+                })
+            },
+        )
+        .with_structured_tool(
+            "word-count",
+            Some("Count the words and characters in a piece of text"),
+            |args: WordCountArgs| {
+                Ok(WordCount {
+                    words: args.text.split_whitespace().count(),
+                    characters: args.text.chars().count(),
                 })
             },
         )
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use modelcontextprotocol_server::mcp_protocol::types::{
+    content::ContentBlock,
+    prompt::PromptMessage,
+    resource::ResourceContent,
+    tool::ToolCallResult,
+};
+use modelcontextprotocol_server::{
+    mcp_prompt, mcp_resource, mcp_tool, transport::StdioTransport, ServerBuilder,
+};
+use std::fs::OpenOptions;
+use std::io;
+use tracing::{debug, info, Level};
+use tracing_subscriber::fmt;
+
+const DOCUMENTS: &[&str] = &[
+    "Getting started with MCP",
+    "Declaring tools with attribute macros",
+    "Serving resources over stdio",
+    "Writing prompt templates",
+];
+
+/// Search the document index
+#[mcp_tool]
+async fn search(
+    #[arg(description = "Text to search for")] query: String,
+    #[arg(description = "Maximum number of results")] limit: Option<u32>,
+) -> Result<ToolCallResult> {
+    debug!("Searching for {:?} (limit {:?})", query, limit);
+
+    let query = query.to_lowercase();
+    let content = DOCUMENTS
+        .iter()
+        .filter(|doc| doc.to_lowercase().contains(&query))
+        .take(limit.unwrap_or(10) as usize)
+        .map(|doc| ContentBlock::text(doc.to_string()))
+        .collect();
+
+    Ok(ToolCallResult {
+        content,
+        is_error: Some(false),
+        structured_content: None,
+        meta: None,
+    })
+}
+
+#[mcp_tool(name = "hello", title = "Say Hello", description = "Say hello to someone")]
+fn greet(#[arg(description = "Name of the person to greet")] name: String) -> Result<ToolCallResult> {
+    Ok(ToolCallResult {
+        content: vec![ContentBlock::text(format!("Hello, {}!", name))],
+        is_error: Some(false),
+        structured_content: None,
+        meta: None,
+    })
+}
+
+/// Asks the AI model to review a piece of code
+#[mcp_prompt]
+fn code_review(
+    #[arg(description = "The code to review")] code: String,
+    #[arg(description = "The programming language of the code")] language: Option<String>,
+) -> Result<Vec<PromptMessage>> {
+    let language = language.unwrap_or_else(|| "the provided".to_string());
+
+    Ok(vec![PromptMessage {
+        role: "user".to_string(),
+        content: ContentBlock::text(format!(
+            "Please review {} code and suggest improvements:\n\n```\n{}\n```",
+            language, code
+        )),
+    }])
+}
+
+/// The list of indexed documents
+#[mcp_resource(uri = "docs:///index", name = "Document Index", mime_type = "text/plain")]
+fn document_index() -> Result<Vec<ResourceContent>> {
+    Ok(vec![ResourceContent {
+        uri: "docs:///index".to_string(),
+        mime_type: "text/plain".to_string(),
+        text: Some(DOCUMENTS.join("\n")),
+        blob: None,
+        meta: None,
+    }])
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Initialize logging to a file
+    let subscriber = fmt::Subscriber::builder()
+        .with_max_level(Level::DEBUG)
+        .with_writer(move || -> Box<dyn io::Write> {
+            Box::new(io::BufWriter::new(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open("macro-server.log")
+                    .unwrap(),
+            ))
+        })
+        .with_ansi(true) // Enable ANSI color codes
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("Failed to set default tracing subscriber");
+
+    info!("Starting macro-server MCP server");
+
+    // Create server with stdio transport, registering the generated definitions
+    let server = ServerBuilder::new("macro-server", "0.1.0")
+        .with_transport(StdioTransport::new())
+        .with_tool_definition(search_tool())
+        .with_tool_definition(greet_tool())
+        .with_prompt_definition(code_review_prompt())
+        .with_resource_definition(document_index_resource())
+        .build()?;
+
+    info!("Server initialized. Waiting for client connection...");
+
+    // Run server (blocks until shutdown)
+    server.run().await?;
+
+    info!("Server shutting down");
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use modelcontextprotocol_client::mcp_protocol::{
     constants::methods,
+    messages::JsonRpcMessage,
     types::{
         prompt::{PromptGetParams, PromptsListParams},
         ClientInfo,
@@ -23,6 +24,7 @@ async fn main() -> Result<()> {
     let _client_info = ClientInfo {
         name: "prompt-client-example".to_string(),
         version: "0.1.0".to_string(),
+        title: None,
     };
 
     // Create transport
@@ -51,22 +53,16 @@ async fn main() -> Result<()> {
     let response = client
         .send_request(
             methods::PROMPTS_LIST,
-            Some(serde_json::to_value(PromptsListParams { cursor: None })?),
-            id.to_string(),
+            Some(serde_json::to_value(PromptsListParams {
+                cursor: None,
+                meta: None,
+            })?),
+            id,
         )
         .await?;
 
     let prompts_list: serde_json::Value = match response {
-        modelcontextprotocol_client::mcp_protocol::messages::JsonRpcMessage::Response {
-            result,
-            ..
-        } => {
-            if let Some(result) = result {
-                serde_json::from_value(result)?
-            } else {
-                serde_json::Value::Null
-            }
-        }
+        JsonRpcMessage::Response(response) => response.result,
         _ => serde_json::Value::Null,
     };
 
@@ -108,6 +104,7 @@ fn factorial(n: u64) -> u64 {
         let params = PromptGetParams {
             name: "code_review".to_string(),
             arguments: Some(args),
+            meta: None,
         };
 
         // Get prompt content
@@ -116,21 +113,12 @@ fn factorial(n: u64) -> u64 {
             .send_request(
                 methods::PROMPTS_GET,
                 Some(serde_json::to_value(params)?),
-                id.to_string(),
+                id,
             )
             .await?;
 
         let prompt_content: serde_json::Value = match response {
-            modelcontextprotocol_client::mcp_protocol::messages::JsonRpcMessage::Response {
-                result,
-                ..
-            } => {
-                if let Some(result) = result {
-                    serde_json::from_value(result)?
-                } else {
-                    serde_json::Value::Null
-                }
-            }
+            JsonRpcMessage::Response(response) => response.result,
             _ => serde_json::Value::Null,
         };
 
@@ -159,6 +147,7 @@ fn factorial(n: u64) -> u64 {
         let params = PromptGetParams {
             name: "translate".to_string(),
             arguments: Some(args),
+            meta: None,
         };
 
         // Get prompt content
@@ -167,21 +156,12 @@ fn factorial(n: u64) -> u64 {
             .send_request(
                 methods::PROMPTS_GET,
                 Some(serde_json::to_value(params)?),
-                id.to_string(),
+                id,
             )
             .await?;
 
         let prompt_content: serde_json::Value = match response {
-            modelcontextprotocol_client::mcp_protocol::messages::JsonRpcMessage::Response {
-                result,
-                ..
-            } => {
-                if let Some(result) = result {
-                    serde_json::from_value(result)?
-                } else {
-                    serde_json::Value::Null
-                }
-            }
+            JsonRpcMessage::Response(response) => response.result,
             _ => serde_json::Value::Null,
         };
 
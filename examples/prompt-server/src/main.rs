@@ -1,7 +1,6 @@
 use anyhow::Result;
-use modelcontextprotocol_server::mcp_protocol::types::prompt::{
-    PromptArgument, PromptMessage, PromptMessageContent,
-};
+use modelcontextprotocol_server::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_server::mcp_protocol::types::prompt::{PromptArgument, PromptMessage};
 use modelcontextprotocol_server::{transport::StdioTransport, ServerBuilder};
 use std::fs::OpenOptions;
 use std::io;
@@ -45,11 +44,13 @@ async fn main() -> Result<()> {
                     name: "code".to_string(),
                     description: Some("The code to review".to_string()),
                     required: Some(true),
+                    title: None,
                 },
                 PromptArgument {
                     name: "language".to_string(),
                     description: Some("The programming language of the code".to_string()),
                     required: Some(false),
+                    title: None,
                 },
             ]),
             |args| {
@@ -76,12 +77,10 @@ async fn main() -> Result<()> {
                 // Create prompt messages
                 let messages = vec![PromptMessage {
                     role: "user".to_string(),
-                    content: PromptMessageContent::Text {
-                        text: format!(
-                            "Please review {} code and suggest improvements:\n\n```\n{}\n```",
-                            language, code
-                        ),
-                    },
+                    content: ContentBlock::text(format!(
+                        "Please review {} code and suggest improvements:\n\n```\n{}\n```",
+                        language, code
+                    )),
                 }];
 
                 Ok(messages)
@@ -96,16 +95,19 @@ async fn main() -> Result<()> {
                     name: "text".to_string(),
                     description: Some("The text to translate".to_string()),
                     required: Some(true),
+                    title: None,
                 },
                 PromptArgument {
                     name: "source_language".to_string(),
                     description: Some("The source language".to_string()),
                     required: Some(false),
+                    title: None,
                 },
                 PromptArgument {
                     name: "target_language".to_string(),
                     description: Some("The target language".to_string()),
                     required: Some(true),
+                    title: None,
                 },
             ]),
             |args| {
@@ -137,12 +139,10 @@ async fn main() -> Result<()> {
                 // Create prompt messages
                 let messages = vec![PromptMessage {
                     role: "user".to_string(),
-                    content: PromptMessageContent::Text {
-                        text: format!(
-                            "Translate the following text from {} to {}:\n\n{}",
-                            source_language, target_language, text
-                        ),
-                    },
+                    content: ContentBlock::text(format!(
+                        "Translate the following text from {} to {}:\n\n{}",
+                        source_language, target_language, text
+                    )),
                 }];
 
                 Ok(messages)
@@ -1,10 +1,14 @@
 use anyhow::Result;
+use futures::stream::{self, StreamExt};
 use modelcontextprotocol_server::mcp_protocol::types::{
+    content::ContentBlock,
     resource::ResourceContent,
-    tool::{ToolCallResult, ToolContent},
+    tool::ToolCallResult,
 };
 use modelcontextprotocol_server::{transport::StdioTransport, ServerBuilder};
-use serde_json::json;
+use std::time::Duration;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use std::fs::OpenOptions;
 use std::io;
 use tracing::{debug, info, Level};
@@ -37,6 +41,16 @@ fn main() {
 }
 "#;
 
+/// Number of pages in the streamed log resource
+const LOG_PAGES: usize = 3;
+
+/// Arguments for the get_file_contents tool
+#[derive(Debug, Deserialize, JsonSchema)]
+struct GetFileContentsArgs {
+    /// Path to the file
+    path: String,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging to a file
@@ -78,6 +92,7 @@ async fn main() -> Result<()> {
                     mime_type: "text/markdown".to_string(),
                     text: Some(FILE_CONTENT.to_string()),
                     blob: None,
+                    meta: None,
                 }])
             },
         )
@@ -94,43 +109,62 @@ async fn main() -> Result<()> {
                     mime_type: "text/x-rust".to_string(),
                     text: Some(CODE_CONTENT.to_string()),
                     blob: None,
+                    meta: None,
                 }])
             },
         )
+        // Add a log resource whose pages are produced one at a time, as a slow backend would
+        .with_streaming_resource(
+            "log:///sample/events",
+            "events.log",
+            Some("Sample event log, streamed page by page"),
+            Some("text/plain"),
+            None,
+            |context| async move {
+                debug!(
+                    "Streaming {} for session {}",
+                    context.uri, context.session.id
+                );
+
+                let pages = stream::iter(0..LOG_PAGES).then(move |page| {
+                    let uri = context.uri.clone();
+                    async move {
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        Ok(ResourceContent {
+                            uri,
+                            mime_type: "text/plain".to_string(),
+                            text: Some(format!("page {}: event data", page + 1)),
+                            blob: None,
+                            meta: None,
+                        })
+                    }
+                });
+
+                Ok(pages.boxed())
+            },
+        )
         // Add a tool that uses resources
-        .with_tool(
+        .with_typed_tool(
             "get_file_contents",
             Some("Retrieve the contents of a file"),
-            json!({
-                "type": "object",
-                "properties": {
-                    "path": {
-                        "type": "string",
-                        "description": "Path to the file"
-                    }
-                },
-                "required": ["path"]
-            }),
-            |args| {
+            |args: GetFileContentsArgs| {
                 debug!("get_file_contents tool called with args: {:?}", args);
 
-                let path = args.get("path").and_then(|v| v.as_str()).unwrap_or("");
-
-                debug!("Fetching file: {}", path);
+                debug!("Fetching file: {}", args.path);
 
-                let content = match path {
+                let content = match args.path.as_str() {
                     "readme.md" => FILE_CONTENT,
                     "main.rs" => CODE_CONTENT,
                     _ => "File not found",
                 };
 
-                let content = vec![ToolContent::Text {
-                    text: format!("File contents:\n\n{}", content),
-                }];
+                let content = vec![ContentBlock::text(format!("File contents:\n\n{}", content))];
 
                 Ok(ToolCallResult {
                     content,
                     is_error: Some(false),
+                    structured_content: None,
+                    meta: None,
                 })
             },
         )
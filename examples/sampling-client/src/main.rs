@@ -1,6 +1,6 @@
 use anyhow::Result;
-use modelcontextprotocol_client::mcp_protocol::types::sampling::{
-    CreateMessageResult, MessageContent,
+use modelcontextprotocol_client::mcp_protocol::types::{
+    content::ContentBlock, sampling::CreateMessageResult,
 };
 use modelcontextprotocol_client::{transport::StdioTransport, ClientBuilder};
 use tracing::{info, Level};
@@ -43,7 +43,7 @@ async fn main() -> Result<()> {
             // Find the last user message content
             for message in &params.messages {
                 if message.role == "user" {
-                    if let MessageContent::Text { text } = &message.content {
+                    if let Some(text) = message.content.as_text() {
                         response_text.push_str(text);
                         break;
                     }
@@ -53,12 +53,11 @@ async fn main() -> Result<()> {
             // Create response
             let result = CreateMessageResult {
                 role: "assistant".to_string(),
-                content: MessageContent::Text {
-                    text: response_text,
-                },
+                content: ContentBlock::text(response_text),
                 model: Some("echo-model-1.0".to_string()),
                 stop_reason: Some("content_length".to_string()),
                 metadata: None,
+                meta: None,
             };
 
             Ok(result)
@@ -1,12 +1,22 @@
 use anyhow::Result;
-use modelcontextprotocol_server::mcp_protocol::types::tool::{ToolCallResult, ToolContent};
+use modelcontextprotocol_server::mcp_protocol::types::{
+    content::ContentBlock, tool::ToolCallResult,
+};
 use modelcontextprotocol_server::{transport::StdioTransport, ServerBuilder};
-use serde_json::json;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use std::fs::OpenOptions;
 use std::io;
 use tracing::{debug, info, Level};
 use tracing_subscriber::fmt;
 
+/// Arguments for the ask-llm tool
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AskLlmArgs {
+    /// The question to ask the LLM
+    question: String,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging to a file
@@ -36,42 +46,26 @@ async fn main() -> Result<()> {
     let server = ServerBuilder::new("sampling-server", "0.1.0")
         .with_transport(StdioTransport::new())
         // Add a tool that uses sampling
-        .with_tool(
+        .with_typed_tool(
             "ask-llm",
             Some("Asks an LLM for information"),
-            json!({
-                "type": "object",
-                "properties": {
-                    "question": {
-                        "type": "string",
-                        "description": "The question to ask the LLM"
-                    }
-                },
-                "required": ["question"]
-            }),
-            |args| {
+            |args: AskLlmArgs| {
                 debug!("Ask LLM tool called with args: {:?}", args);
 
-                let question = args
-                    .get("question")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Tell me about yourself");
-
                 // Since we can't directly access the transport from here,
                 // we'll just return a simple response
                 let content = vec![
-                    ToolContent::Text {
-                        text: format!("Question: {}", question),
-                    },
-                    ToolContent::Text {
-                        text: "This tool would normally use sampling to get an answer from an LLM."
-                            .to_string(),
-                    },
+                    ContentBlock::text(format!("Question: {}", args.question)),
+                    ContentBlock::text(
+                        "This tool would normally use sampling to get an answer from an LLM.",
+                    ),
                 ];
 
                 let result = ToolCallResult {
                     content,
                     is_error: Some(false),
+                    structured_content: None,
+                    meta: None,
                 };
 
                 Ok(result)
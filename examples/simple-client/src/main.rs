@@ -1,5 +1,7 @@
 use anyhow::Result;
-use modelcontextprotocol_client::{transport::StdioTransport, ClientBuilder};
+use futures::TryStreamExt;
+use modelcontextprotocol_client::mcp_protocol::types::tool::Tool;
+use modelcontextprotocol_client::{transport::StdioTransport, ClientBuilder, DisplayName};
 use serde_json::json;
 use std::fs::OpenOptions;
 use std::io;
@@ -39,6 +41,11 @@ async fn main() -> Result<()> {
     let client = Arc::new(
         ClientBuilder::new("simple-client", "0.1.0")
             .with_transport(transport)
+            // Log calls to tools that may modify their environment
+            .with_tool_policy(|tool, arguments| {
+                info!("Confirming call to destructive tool {}: {}", tool.name, arguments);
+                true
+            })
             .build()?,
     );
 
@@ -64,18 +71,19 @@ async fn main() -> Result<()> {
 
     // List available tools
     info!("Requesting available tools");
-    let tools = client.list_tools().await?;
-    info!("Available tools: {}", tools.tools.len());
-    for tool in &tools.tools {
+    // Fetch every page of tools
+    let tools: Vec<Tool> = client.list_all_tools().try_collect().await?;
+    info!("Available tools: {}", tools.len());
+    for tool in &tools {
         info!(
             "Tool: {} - {}",
-            tool.name,
+            tool.display_name(),
             tool.description.as_deref().unwrap_or("")
         );
     }
 
     // Call the hello tool
-    if tools.tools.iter().any(|t| t.name == "hello") {
+    if tools.iter().any(|t| t.name == "hello") {
         info!("Calling 'hello' tool");
         let result = client
             .call_tool(
@@ -90,10 +98,10 @@ async fn main() -> Result<()> {
         info!("Tool result:");
         for content in result.content {
             match content {
-                modelcontextprotocol_client::mcp_protocol::types::tool::ToolContent::Text {
-                    text,
-                } => {
-                    info!("{}", text);
+                modelcontextprotocol_client::mcp_protocol::types::content::ContentBlock::Text(
+                    content,
+                ) => {
+                    info!("{}", content.text);
                 }
                 _ => {
                     info!("Received non-text content");
@@ -1,6 +1,7 @@
 use anyhow::Result;
+use modelcontextprotocol_client::mcp_protocol::messages::JsonRpcMessage;
 use modelcontextprotocol_client::mcp_protocol::types::{
-    resource::ResourceTemplatesListParams, tool::ToolContent,
+    content::ContentBlock, resource::ResourceTemplatesListParams,
 };
 use modelcontextprotocol_client::{transport::StdioTransport, ClientBuilder};
 use serde_json::json;
@@ -68,19 +69,16 @@ async fn main() -> Result<()> {
         .send_request("resources/list", None, "list_resources".to_string())
         .await?;
 
-    if let modelcontextprotocol_client::mcp_protocol::messages::JsonRpcMessage::Response {
-        result,
-        ..
-    } = resources_result
-    {
-        if let Some(result) = result {
-            info!("Resources: {}", result);
-        }
+    if let JsonRpcMessage::Response(response) = resources_result {
+        info!("Resources: {}", response.result);
     }
 
     // List templates
     info!("Listing resource templates");
-    let templates_params = ResourceTemplatesListParams { cursor: None };
+    let templates_params = ResourceTemplatesListParams {
+        cursor: None,
+        meta: None,
+    };
 
     let templates_result = client
         .send_request(
@@ -90,14 +88,8 @@ async fn main() -> Result<()> {
         )
         .await?;
 
-    if let modelcontextprotocol_client::mcp_protocol::messages::JsonRpcMessage::Response {
-        result,
-        ..
-    } = templates_result
-    {
-        if let Some(result) = result {
-            info!("Templates: {}", result);
-        }
+    if let JsonRpcMessage::Response(response) = templates_result {
+        info!("Templates: {}", response.result);
     }
 
     // Get completions for a template parameter
@@ -110,6 +102,8 @@ async fn main() -> Result<()> {
             name: "database".to_string(),
             value: "".to_string(),
         },
+        context: None,
+        meta: None,
     };
 
     let completion_result = client
@@ -120,14 +114,8 @@ async fn main() -> Result<()> {
         )
         .await?;
 
-    if let modelcontextprotocol_client::mcp_protocol::messages::JsonRpcMessage::Response {
-        result,
-        ..
-    } = completion_result
-    {
-        if let Some(result) = result {
-            info!("Completions: {}", result);
-        }
+    if let JsonRpcMessage::Response(response) = completion_result {
+        info!("Completions: {}", response.result);
     }
 
     // Call the expand-template tool
@@ -149,8 +137,8 @@ async fn main() -> Result<()> {
     info!("Tool result:");
     for content in template_expansion.content {
         match content {
-            ToolContent::Text { text } => {
-                info!("{}", text);
+            ContentBlock::Text(content) => {
+                info!("{}", content.text);
             }
             _ => {
                 info!("Received non-text content");
@@ -1,17 +1,27 @@
 use anyhow::Result;
 use modelcontextprotocol_server::mcp_protocol::types::{
-    completion::CompletionItem,
-    resource::ResourceContent,
-    tool::{ToolCallResult, ToolContent},
+    content::ContentBlock, resource::ResourceContent, tool::ToolCallResult,
 };
-use modelcontextprotocol_server::{transport::StdioTransport, ServerBuilder};
-use serde_json::json;
+use modelcontextprotocol_server::mcp_protocol::uri_template::UriTemplate;
+use modelcontextprotocol_server::{transport::StdioTransport, CompletionQuery, ServerBuilder};
+use schemars::JsonSchema;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io;
 use tracing::{debug, info, Level};
 use tracing_subscriber::fmt;
 
+/// Arguments for the expand-template tool
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ExpandTemplateArgs {
+    /// URI template to expand
+    template: String,
+
+    /// Parameters to use for expansion
+    parameters: HashMap<String, String>,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging to a file
@@ -49,6 +59,7 @@ async fn main() -> Result<()> {
                     mime_type: "text/plain".to_string(),
                     text: Some("This is a sample text file.".to_string()),
                     blob: None,
+                    meta: None,
                 };
                 Ok(vec![content])
             },
@@ -59,12 +70,18 @@ async fn main() -> Result<()> {
             "File Access",
             Some("Access a file by path"),
             Some("application/octet-stream"),
-            |_template, params| {
-                if let Some(path) = params.get("path") {
-                    Ok(format!("file:///{}", path))
-                } else {
-                    Err(anyhow::anyhow!("Missing path parameter"))
-                }
+            |params| {
+                let path = params
+                    .get("path")
+                    .ok_or_else(|| anyhow::anyhow!("Missing path parameter"))?;
+
+                Ok(vec![ResourceContent {
+                    uri: format!("file:///{}", path),
+                    mime_type: "text/plain".to_string(),
+                    text: Some(format!("This is the file at {}.", path)),
+                    blob: None,
+                    meta: None,
+                }])
             },
         )
         // Add a database record template
@@ -73,132 +90,69 @@ async fn main() -> Result<()> {
             "Database Record",
             Some("Access a database record by ID"),
             Some("application/json"),
-            |_template, params| {
+            |params| {
                 let database = params.get("database").cloned().unwrap_or_default();
                 let table = params.get("table").cloned().unwrap_or_default();
                 let id = params.get("id").cloned().unwrap_or_default();
 
-                Ok(format!("db:///{}/{}/{}", database, table, id))
+                let record = serde_json::json!({
+                    "database": database,
+                    "table": table,
+                    "id": id,
+                });
+
+                Ok(vec![ResourceContent {
+                    uri: format!("db:///{}/{}/{}", database, table, id),
+                    mime_type: "application/json".to_string(),
+                    text: Some(record.to_string()),
+                    blob: None,
+                    meta: None,
+                }])
             },
         )
         // Add a completion provider for the database template
         .with_template_completion(
             "db:///{database}/{table}/{id}",
-            |_template, param_name, _value| {
-                debug!("Completion requested for parameter: {}", param_name);
-
-                match param_name.as_str() {
-                    "database" => {
-                        // Return a list of available databases
-                        let items = vec![
-                            CompletionItem {
-                                label: "customers".to_string(),
-                                detail: Some("Customer database".to_string()),
-                                documentation: None,
-                            },
-                            CompletionItem {
-                                label: "products".to_string(),
-                                detail: Some("Product database".to_string()),
-                                documentation: None,
-                            },
-                            CompletionItem {
-                                label: "orders".to_string(),
-                                detail: Some("Order database".to_string()),
-                                documentation: None,
-                            },
-                        ];
-                        Ok(items)
-                    }
+            |query: CompletionQuery| async move {
+                debug!("Completion requested for parameter: {}", query.argument);
+
+                match query.argument.as_str() {
+                    // Available databases
+                    "database" => Ok(query.prefix_matches(["customers", "products", "orders"])),
+                    // Tables of the chosen database
                     "table" => {
-                        // Return tables based on database
-                        let items = vec![
-                            CompletionItem {
-                                label: "users".to_string(),
-                                detail: Some("Users table".to_string()),
-                                documentation: None,
-                            },
-                            CompletionItem {
-                                label: "accounts".to_string(),
-                                detail: Some("Accounts table".to_string()),
-                                documentation: None,
-                            },
-                        ];
-                        Ok(items)
-                    }
-                    "id" => {
-                        // Return some example IDs
-                        let items = vec![
-                            CompletionItem {
-                                label: "1001".to_string(),
-                                detail: Some("User ID 1001".to_string()),
-                                documentation: None,
-                            },
-                            CompletionItem {
-                                label: "1002".to_string(),
-                                detail: Some("User ID 1002".to_string()),
-                                documentation: None,
-                            },
-                        ];
-                        Ok(items)
+                        let tables = match query.arguments.get("database").map(String::as_str) {
+                            Some("products") => vec!["items", "categories"],
+                            Some("orders") => vec!["orders", "line_items"],
+                            _ => vec!["users", "accounts"],
+                        };
+                        Ok(query.prefix_matches(tables))
                     }
+                    // Some example IDs
+                    "id" => Ok(query.prefix_matches(["1001", "1002"])),
                     _ => Ok(vec![]),
                 }
             },
         )
         // Add a tool to expand templates
-        .with_tool(
+        .with_typed_tool(
             "expand-template",
             Some("Expand a URI template with parameters"),
-            json!({
-                "type": "object",
-                "properties": {
-                    "template": {
-                        "type": "string",
-                        "description": "URI template to expand"
-                    },
-                    "parameters": {
-                        "type": "object",
-                        "description": "Parameters to use for expansion"
-                    }
-                },
-                "required": ["template", "parameters"]
-            }),
-            |args| {
+            |args: ExpandTemplateArgs| {
                 debug!("Expand template tool called with args: {:?}", args);
 
-                let template = args
-                    .get("template")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| anyhow::anyhow!("Missing template parameter"))?;
-
-                let parameters = args
-                    .get("parameters")
-                    .and_then(|v| v.as_object())
-                    .ok_or_else(|| anyhow::anyhow!("Missing parameters object"))?;
-
-                // Convert parameters to HashMap<String, String>
-                let mut param_map = HashMap::new();
-                for (key, value) in parameters {
-                    if let Some(value_str) = value.as_str() {
-                        param_map.insert(key.clone(), value_str.to_string());
-                    }
-                }
-
-                // Simple expansion logic (a real implementation would use the resource manager)
-                let mut result = template.to_string();
-                for (key, value) in param_map {
-                    result = result.replace(&format!("{{{}}}", key), &value);
-                }
+                // Expand the RFC 6570 template
+                let result = UriTemplate::parse(&args.template)?.expand_strings(&args.parameters);
 
                 debug!("Expanded template: {}", result);
 
-                let content = vec![ToolContent::Text {
-                    text: format!("Expanded URI: {}", result),
-                }];
+                let content = vec![ContentBlock::text(format!("Expanded URI: {}", result))];
 
                 Ok(ToolCallResult {
                     content,
                     is_error: Some(false),
+                    structured_content: None,
+                    meta: None,
                 })
             },
         )
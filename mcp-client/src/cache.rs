@@ -0,0 +1,256 @@
+// mcp-client/src/cache.rs
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use mcp_protocol::types::resource::ResourceContent;
+
+/// Limits for the client's `resources/read` cache
+#[derive(Debug, Clone)]
+pub struct ResourceCacheConfig {
+    /// Maximum number of URIs kept
+    pub max_entries: usize,
+    /// Maximum total size of cached contents, in bytes of text and base64 blob
+    pub max_bytes: usize,
+    /// How long an entry may be served without asking the server again
+    pub ttl: Option<Duration>,
+}
+
+impl ResourceCacheConfig {
+    /// Set the maximum number of URIs kept
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Set the maximum total size of cached contents
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Set how long an entry may be served, or None to keep it until the server signals a change
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+impl Default for ResourceCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 256,
+            max_bytes: 16 * 1024 * 1024,
+            ttl: Some(Duration::from_secs(300)),
+        }
+    }
+}
+
+/// Counters describing how well the resource cache is doing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceCacheStats {
+    /// Reads served from the cache
+    pub hits: u64,
+    /// Reads that went to the server
+    pub misses: u64,
+    /// Entries dropped to stay within the size limits or because they expired
+    pub evictions: u64,
+    /// Entries dropped because the server signalled a change
+    pub invalidations: u64,
+    /// Entries currently cached
+    pub entries: usize,
+    /// Bytes currently cached
+    pub bytes: usize,
+}
+
+impl ResourceCacheStats {
+    /// Fraction of reads served from the cache, or 0 if nothing has been read
+    pub fn hit_rate(&self) -> f64 {
+        let reads = self.hits + self.misses;
+        if reads == 0 {
+            0.0
+        } else {
+            self.hits as f64 / reads as f64
+        }
+    }
+}
+
+struct CacheEntry {
+    contents: Vec<ResourceContent>,
+    size: usize,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+/// Least recently used cache of resource contents, keyed by URI
+pub(crate) struct ResourceCache {
+    config: ResourceCacheConfig,
+    entries: HashMap<String, CacheEntry>,
+    recency: BTreeMap<u64, String>, // Last use tick to URI, oldest first
+    tick: u64,
+    epoch: u64, // Bumped on every invalidation so reads that raced one are not stored
+    subscribed: HashSet<String>,
+    released: BTreeSet<String>, // Subscribed URIs whose entries are gone, to be unsubscribed
+    stats: ResourceCacheStats,
+}
+
+impl ResourceCache {
+    pub(crate) fn new(config: ResourceCacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            epoch: 0,
+            subscribed: HashSet::new(),
+            released: BTreeSet::new(),
+            stats: ResourceCacheStats::default(),
+        }
+    }
+
+    /// Look up a URI, counting the hit or miss
+    pub(crate) fn get(&mut self, uri: &str) -> Option<Vec<ResourceContent>> {
+        let expired = match (self.entries.get(uri), self.config.ttl) {
+            (Some(entry), Some(ttl)) => entry.stored_at.elapsed() >= ttl,
+            _ => false,
+        };
+        if expired {
+            self.remove(uri);
+            self.release(uri);
+            self.stats.evictions += 1;
+        }
+
+        self.tick += 1;
+        let tick = self.tick;
+        match self.entries.get_mut(uri) {
+            Some(entry) => {
+                self.recency.remove(&entry.last_used);
+                self.recency.insert(tick, uri.to_string());
+                entry.last_used = tick;
+                self.stats.hits += 1;
+                Some(entry.contents.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// The current epoch, to be handed back to [`ResourceCache::insert`]
+    pub(crate) fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Store contents read while the cache was at `epoch`, unless an invalidation happened since
+    ///
+    /// Contents that are not stored have their subscription released.
+    pub(crate) fn insert(&mut self, uri: &str, contents: Vec<ResourceContent>, epoch: u64) {
+        if epoch != self.epoch {
+            self.release(uri);
+            return;
+        }
+
+        let size = contents.iter().map(content_size).sum();
+        if size > self.config.max_bytes || self.config.max_entries == 0 {
+            self.release(uri);
+            return;
+        }
+
+        self.remove(uri);
+        while self.entries.len() >= self.config.max_entries
+            || self.stats.bytes + size > self.config.max_bytes
+        {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.remove(&oldest);
+            self.release(&oldest);
+            self.stats.evictions += 1;
+        }
+
+        self.tick += 1;
+        self.recency.insert(self.tick, uri.to_string());
+        self.entries.insert(
+            uri.to_string(),
+            CacheEntry {
+                contents,
+                size,
+                stored_at: Instant::now(),
+                last_used: self.tick,
+            },
+        );
+        self.stats.entries = self.entries.len();
+        self.stats.bytes += size;
+    }
+
+    /// Drop a URI because the server said it changed
+    pub(crate) fn invalidate(&mut self, uri: &str) {
+        self.epoch += 1;
+        if self.remove(uri) {
+            self.release(uri);
+            self.stats.invalidations += 1;
+        }
+    }
+
+    /// Drop everything because the server said the set of resources changed
+    pub(crate) fn invalidate_all(&mut self) {
+        self.epoch += 1;
+        self.stats.invalidations += self.entries.len() as u64;
+        let uris: Vec<String> = self.entries.drain().map(|(uri, _)| uri).collect();
+        for uri in &uris {
+            self.release(uri);
+        }
+        self.recency.clear();
+        self.stats.entries = 0;
+        self.stats.bytes = 0;
+    }
+
+    /// Whether the client is subscribed to a URI, keeping the subscription if it was to be released
+    pub(crate) fn reclaim_subscription(&mut self, uri: &str) -> bool {
+        if self.released.remove(uri) {
+            self.subscribed.insert(uri.to_string());
+        }
+        self.subscribed.contains(uri)
+    }
+
+    /// Record that the client subscribed to a URI
+    pub(crate) fn mark_subscribed(&mut self, uri: &str) {
+        self.subscribed.insert(uri.to_string());
+    }
+
+    /// Queue the subscription to a URI for release, as nothing is cached for it
+    pub(crate) fn release(&mut self, uri: &str) {
+        if self.subscribed.remove(uri) {
+            self.released.insert(uri.to_string());
+        }
+    }
+
+    /// Take the subscriptions the client should now unsubscribe from
+    pub(crate) fn take_released(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.released).into_iter().collect()
+    }
+
+    pub(crate) fn stats(&self) -> ResourceCacheStats {
+        self.stats.clone()
+    }
+
+    fn remove(&mut self, uri: &str) -> bool {
+        match self.entries.remove(uri) {
+            Some(entry) => {
+                self.recency.remove(&entry.last_used);
+                self.stats.entries = self.entries.len();
+                self.stats.bytes -= entry.size;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Approximate memory used by a content item
+fn content_size(content: &ResourceContent) -> usize {
+    content.uri.len()
+        + content.mime_type.len()
+        + content.text.as_ref().map_or(0, String::len)
+        + content.blob.as_ref().map_or(0, String::len)
+}
@@ -1,5 +1,7 @@
 // mcp-client/src/client.rs
 use anyhow::{anyhow, Result};
+use futures::stream::{self, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -7,15 +9,27 @@ use tokio::sync::{mpsc, Mutex, RwLock};
 
 use mcp_protocol::{
     constants::{error_codes, methods, PROTOCOL_VERSION},
-    messages::{ClientCapabilities, InitializeParams, InitializeResult, JsonRpcMessage},
+    messages::{
+        CallTool, ClientCapabilities, Complete, GetPrompt, Initialize, InitializeParams,
+        InitializeResult, JsonRpcError, JsonRpcMessage, JsonRpcResponse, ListPrompts,
+        ListResourceTemplates, ListResources, ListTools, MessageParseError, Meta, ReadResource,
+        Request, RequestId, ServerNotification, ServerRequest, Subscribe, Unsubscribe,
+    },
     types::{
         completion::{CompleteRequest, CompleteResponse},
+        prompt::{PromptGetParams, PromptGetResult, PromptsListParams},
+        resource::{
+            Resource, ResourceReadParams, ResourceReadResult, ResourceSubscribeParams,
+            ResourceTemplate, ResourceTemplatesListParams, ResourceTemplatesListResult,
+            ResourceUnsubscribeParams, ResourcesListParams, ResourcesListResult,
+        },
         sampling::{CreateMessageParams, CreateMessageResult},
-        tool::{ToolCallParams, ToolCallResult, ToolsListResult},
+        tool::{Tool, ToolCallParams, ToolCallResult, ToolsListParams, ToolsListResult},
         ClientInfo,
     },
 };
 
+use crate::cache::{ResourceCache, ResourceCacheConfig, ResourceCacheStats};
 use crate::transport::Transport;
 
 /// MCP client state
@@ -35,9 +49,12 @@ struct PendingRequest {
 /// MCP client builder
 pub struct ClientBuilder {
     name: String,
+    title: Option<String>,
     version: String,
     transport: Option<Box<dyn Transport>>,
     sampling_enabled: bool,
+    tool_policy: Option<ToolPolicyCallback>,
+    resource_cache: Option<ResourceCacheConfig>,
 }
 
 impl ClientBuilder {
@@ -45,9 +62,12 @@ impl ClientBuilder {
     pub fn new(name: &str, version: &str) -> Self {
         Self {
             name: name.to_string(),
+            title: None,
             version: version.to_string(),
             transport: None,
             sampling_enabled: false,
+            tool_policy: None,
+            resource_cache: None,
         }
     }
 
@@ -57,12 +77,42 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the human-readable name sent in `ClientInfo`, for display in place of `name`
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
     /// Set the transport to use
     pub fn with_transport<T: Transport>(mut self, transport: T) -> Self {
         self.transport = Some(Box::new(transport));
         self
     }
 
+    /// Require confirmation before calling destructive tools
+    ///
+    /// The callback is asked before every call to a tool whose annotations mark it as
+    /// destructive, or that the server does not list; returning `false` rejects the call
+    /// without contacting the server.
+    pub fn with_tool_policy(
+        mut self,
+        policy: impl Fn(&Tool, &serde_json::Value) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.tool_policy = Some(Box::new(policy));
+        self
+    }
+
+    /// Cache `resources/read` results by URI
+    ///
+    /// Cached contents are dropped when the server sends `notifications/resources/updated` for
+    /// the URI or `notifications/resources/list_changed`. If the server supports subscriptions
+    /// the client subscribes to each URI it caches, and unsubscribes on a later read once the
+    /// entry is gone; URIs it cannot subscribe to are only refreshed once the TTL runs out.
+    pub fn with_resource_cache(mut self, config: ResourceCacheConfig) -> Self {
+        self.resource_cache = Some(config);
+        self
+    }
+
     /// Build the client
     pub fn build(self) -> Result<Client> {
         let transport = self
@@ -71,15 +121,17 @@ impl ClientBuilder {
 
         // Create capabilities
         let capabilities = if self.sampling_enabled {
-            let mut caps = ClientCapabilities::default();
-            caps.sampling = Some(HashMap::new());
-            caps
+            ClientCapabilities {
+                sampling: Some(HashMap::new()),
+                ..Default::default()
+            }
         } else {
             ClientCapabilities::default()
         };
 
         Ok(Client {
             name: self.name,
+            title: self.title,
             version: self.version,
             transport,
             sampling_enabled: self.sampling_enabled,
@@ -89,6 +141,13 @@ impl ClientBuilder {
             pending_requests: Arc::new(RwLock::new(HashMap::new())),
             initialized_result: Arc::new(RwLock::new(None)),
             sampling_callback: Arc::new(RwLock::new(None)),
+            tool_policy: self.tool_policy,
+            tool_cache: Arc::new(RwLock::new(None)),
+            resource_cache: Arc::new(RwLock::new(None)),
+            template_cache: Arc::new(RwLock::new(None)),
+            content_cache: self
+                .resource_cache
+                .map(|config| Arc::new(Mutex::new(ResourceCache::new(config)))),
         })
     }
 }
@@ -97,18 +156,27 @@ impl ClientBuilder {
 pub type SamplingCallback =
     Box<dyn Fn(CreateMessageParams) -> Result<CreateMessageResult> + Send + Sync>;
 
+/// Type for the callback deciding whether a destructive tool may be called
+pub type ToolPolicyCallback = Box<dyn Fn(&Tool, &serde_json::Value) -> bool + Send + Sync>;
+
 /// MCP client
 pub struct Client {
     name: String,
+    title: Option<String>,
     version: String,
     transport: Box<dyn Transport>,
     sampling_enabled: bool,
     capabilities: ClientCapabilities,
     state: Arc<RwLock<ClientState>>,
     next_id: Arc<Mutex<i64>>,
-    pending_requests: Arc<RwLock<HashMap<String, PendingRequest>>>,
+    pending_requests: Arc<RwLock<HashMap<RequestId, PendingRequest>>>,
     initialized_result: Arc<RwLock<Option<InitializeResult>>>,
     sampling_callback: Arc<RwLock<Option<SamplingCallback>>>,
+    tool_policy: Option<ToolPolicyCallback>,
+    tool_cache: Arc<RwLock<Option<Vec<Tool>>>>,
+    resource_cache: Arc<RwLock<Option<Vec<Resource>>>>,
+    template_cache: Arc<RwLock<Option<Vec<ResourceTemplate>>>>,
+    content_cache: Option<Arc<Mutex<ResourceCache>>>,
 }
 
 impl Client {
@@ -138,56 +206,106 @@ impl Client {
             client_info: ClientInfo {
                 name: self.name.clone(),
                 version: self.version.clone(),
+                title: self.title.clone(),
             },
+            meta: None,
         };
 
         // Send initialize request
-        let id = self.next_request_id().await?;
-        let response = self
-            .send_request(methods::INITIALIZE, Some(json!(params)), id.to_string())
-            .await?;
+        let result = self.request::<Initialize>(params).await?;
 
-        match response {
-            JsonRpcMessage::Response { result, error, .. } => {
-                if let Some(error) = error {
-                    return Err(anyhow!(
-                        "Initialize error: {} (code: {})",
-                        error.message,
-                        error.code
-                    ));
-                }
+        // Store the result
+        {
+            let mut initialized = self.initialized_result.write().await;
+            *initialized = Some(result.clone());
+        }
 
-                if let Some(result) = result {
-                    let result: InitializeResult = serde_json::from_value(result)?;
+        // Send initialized notification
+        self.transport
+            .send(JsonRpcMessage::notification(methods::INITIALIZED, None))
+            .await?;
 
-                    // Store the result
-                    {
-                        let mut initialized = self.initialized_result.write().await;
-                        *initialized = Some(result.clone());
-                    }
+        // Update state to ready
+        {
+            let mut state = self.state.write().await;
+            *state = ClientState::Ready;
+        }
 
-                    // Send initialized notification
-                    self.transport
-                        .send(JsonRpcMessage::notification(methods::INITIALIZED, None))
-                        .await?;
+        Ok(result)
+    }
 
-                    // Update state to ready
-                    {
-                        let mut state = self.state.write().await;
-                        *state = ClientState::Ready;
-                    }
+    /// List available tools (first page only; see [`Client::list_all_tools`])
+    pub async fn list_tools(&self) -> Result<ToolsListResult> {
+        self.list_tools_page(None).await
+    }
 
-                    return Ok(result);
-                }
+    /// List one page of available tools, starting after `cursor`
+    pub async fn list_tools_page(&self, cursor: Option<String>) -> Result<ToolsListResult> {
+        self.list_tools_page_with_meta(cursor, None).await
+    }
 
-                Err(anyhow!("Invalid initialize response"))
+    /// List one page of available tools, sending `meta` as the request's `_meta`
+    pub async fn list_tools_page_with_meta(
+        &self,
+        cursor: Option<String>,
+        meta: Option<Meta>,
+    ) -> Result<ToolsListResult> {
+        // Check if we're initialized
+        {
+            let state = self.state.read().await;
+            if *state != ClientState::Ready {
+                return Err(anyhow!("Client not initialized"));
             }
-            _ => Err(anyhow!("Invalid response type")),
         }
+
+        // Create tool list parameters
+        let first_page = cursor.is_none();
+        let params = ToolsListParams { cursor, meta };
+
+        // Send tools/list request
+        let result = self.request::<ListTools>(params).await?;
+
+        // A single complete page is the whole list, so it can refresh the cache
+        if first_page && result.next_cursor.is_none() {
+            *self.tool_cache.write().await = Some(result.tools.clone());
+        }
+
+        Ok(result)
     }
 
-    /// List available tools
-    pub async fn list_tools(&self) -> Result<ToolsListResult> {
+    /// Stream every available tool, fetching further pages as they are needed
+    pub fn list_all_tools(&self) -> impl Stream<Item = Result<Tool>> + '_ {
+        // `None` once the last page has been fetched
+        stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| async move {
+            let cursor = match cursor {
+                Some(cursor) => cursor,
+                None => return Ok::<_, anyhow::Error>(None),
+            };
+
+            let page = self.list_tools_page(cursor).await?;
+            let next_cursor = page.next_cursor.filter(|cursor| !cursor.is_empty());
+            Ok(Some((page.tools, next_cursor.map(Some))))
+        })
+        .map_ok(|tools| stream::iter(tools.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// List available resources (first page only; see [`Client::list_all_resources`])
+    pub async fn list_resources(&self) -> Result<ResourcesListResult> {
+        self.list_resources_page(None).await
+    }
+
+    /// List one page of available resources, starting after `cursor`
+    pub async fn list_resources_page(&self, cursor: Option<String>) -> Result<ResourcesListResult> {
+        self.list_resources_page_with_meta(cursor, None).await
+    }
+
+    /// List one page of available resources, sending `meta` as the request's `_meta`
+    pub async fn list_resources_page_with_meta(
+        &self,
+        cursor: Option<String>,
+        meta: Option<Meta>,
+    ) -> Result<ResourcesListResult> {
         // Check if we're initialized
         {
             let state = self.state.read().await;
@@ -196,37 +314,113 @@ impl Client {
             }
         }
 
-        // Send tools/list request
-        let id = self.next_request_id().await?;
-        let response = self
-            .send_request(methods::TOOLS_LIST, None, id.to_string())
-            .await?;
+        // Create resource list parameters
+        let first_page = cursor.is_none();
+        let params = ResourcesListParams { cursor, meta };
 
-        match response {
-            JsonRpcMessage::Response { result, error, .. } => {
-                if let Some(error) = error {
-                    return Err(anyhow!(
-                        "List tools error: {} (code: {})",
-                        error.message,
-                        error.code
-                    ));
-                }
+        // Send resources/list request
+        let result = self.request::<ListResources>(params).await?;
 
-                if let Some(result) = result {
-                    let result: ToolsListResult = serde_json::from_value(result)?;
-                    return Ok(result);
-                }
+        // A single complete page is the whole list, so it can refresh the cache
+        if first_page && result.next_cursor.is_none() {
+            *self.resource_cache.write().await = Some(result.resources.clone());
+        }
 
-                Err(anyhow!("Invalid list tools response"))
+        Ok(result)
+    }
+
+    /// Stream every available resource, fetching further pages as they are needed
+    pub fn list_all_resources(&self) -> impl Stream<Item = Result<Resource>> + '_ {
+        // `None` once the last page has been fetched
+        stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| async move {
+            let cursor = match cursor {
+                Some(cursor) => cursor,
+                None => return Ok::<_, anyhow::Error>(None),
+            };
+
+            let page = self.list_resources_page(cursor).await?;
+            let next_cursor = page.next_cursor.filter(|cursor| !cursor.is_empty());
+            Ok(Some((page.resources, next_cursor.map(Some))))
+        })
+        .map_ok(|resources| stream::iter(resources.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// List available resource templates (first page only; see [`Client::list_all_resource_templates`])
+    pub async fn list_resource_templates(&self) -> Result<ResourceTemplatesListResult> {
+        self.list_resource_templates_page(None).await
+    }
+
+    /// List one page of available resource templates, starting after `cursor`
+    pub async fn list_resource_templates_page(
+        &self,
+        cursor: Option<String>,
+    ) -> Result<ResourceTemplatesListResult> {
+        self.list_resource_templates_page_with_meta(cursor, None).await
+    }
+
+    /// List one page of available resource templates, sending `meta` as the request's `_meta`
+    pub async fn list_resource_templates_page_with_meta(
+        &self,
+        cursor: Option<String>,
+        meta: Option<Meta>,
+    ) -> Result<ResourceTemplatesListResult> {
+        // Check if we're initialized
+        {
+            let state = self.state.read().await;
+            if *state != ClientState::Ready {
+                return Err(anyhow!("Client not initialized"));
             }
-            _ => Err(anyhow!("Invalid response type")),
         }
+
+        // Create template list parameters
+        let first_page = cursor.is_none();
+        let params = ResourceTemplatesListParams { cursor, meta };
+
+        // Send resources/templates/list request
+        let result = self.request::<ListResourceTemplates>(params).await?;
+
+        // A single complete page is the whole list, so it can refresh the cache
+        if first_page && result.next_cursor.is_none() {
+            *self.template_cache.write().await = Some(result.resource_templates.clone());
+        }
+
+        Ok(result)
+    }
+
+    /// Stream every available resource template, fetching further pages as they are needed
+    pub fn list_all_resource_templates(
+        &self,
+    ) -> impl Stream<Item = Result<ResourceTemplate>> + '_ {
+        // `None` once the last page has been fetched
+        stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| async move {
+            let cursor = match cursor {
+                Some(cursor) => cursor,
+                None => return Ok::<_, anyhow::Error>(None),
+            };
+
+            let page = self.list_resource_templates_page(cursor).await?;
+            let next_cursor = page.next_cursor.filter(|cursor| !cursor.is_empty());
+            Ok(Some((page.resource_templates, next_cursor.map(Some))))
+        })
+        .map_ok(|templates| stream::iter(templates.into_iter().map(Ok)))
+        .try_flatten()
     }
 
-    /// List available resource templates
-    pub async fn list_resource_templates(
+    /// Read a resource, serving it from the resource cache if one is enabled and holds it
+    pub async fn read_resource(&self, uri: &str) -> Result<ResourceReadResult> {
+        self.read_resource_with_meta(uri, None).await
+    }
+
+    /// Read a resource, sending `meta` as the request's `_meta`
+    ///
+    /// Reads served from the resource cache never reach the server, so `meta` is only sent
+    /// when the contents are fetched.
+    pub async fn read_resource_with_meta(
         &self,
-    ) -> Result<mcp_protocol::types::resource::ResourceTemplatesListResult> {
+        uri: &str,
+        meta: Option<Meta>,
+    ) -> Result<ResourceReadResult> {
         // Check if we're initialized
         {
             let state = self.state.read().await;
@@ -235,36 +429,107 @@ impl Client {
             }
         }
 
-        // Send resources/templates/list request
-        let id = self.next_request_id().await?;
-        let response = self
-            .send_request(methods::RESOURCES_TEMPLATES_LIST, None, id.to_string())
-            .await?;
+        let Some(cache) = &self.content_cache else {
+            return self.fetch_resource(uri, meta).await;
+        };
 
-        match response {
-            JsonRpcMessage::Response { result, error, .. } => {
-                if let Some(error) = error {
-                    return Err(anyhow!(
-                        "List resource templates error: {} (code: {})",
-                        error.message,
-                        error.code
-                    ));
-                }
+        if let Some(contents) = cache.lock().await.get(uri) {
+            return Ok(ResourceReadResult {
+                contents,
+                meta: None,
+            });
+        }
 
-                if let Some(result) = result {
-                    let result: mcp_protocol::types::resource::ResourceTemplatesListResult =
-                        serde_json::from_value(result)?;
-                    return Ok(result);
-                }
+        // Subscribe before reading so no change after the read can go unnoticed
+        let subscribed = cache.lock().await.reclaim_subscription(uri);
+        if !subscribed && self.server_supports_subscriptions().await {
+            match self.subscribe_resource(uri).await {
+                Ok(()) => cache.lock().await.mark_subscribed(uri),
+                Err(err) => tracing::debug!("Caching {} without a subscription: {}", uri, err),
+            }
+        }
+
+        let epoch = cache.lock().await.epoch();
+        let result = self.fetch_resource(uri, meta).await;
+        match &result {
+            Ok(result) => cache.lock().await.insert(uri, result.contents.clone(), epoch),
+            Err(_) => cache.lock().await.release(uri),
+        }
 
-                Err(anyhow!("Invalid resource templates list response"))
+        // Entries evicted or invalidated since the last read no longer need their subscriptions
+        let released = cache.lock().await.take_released();
+        for released_uri in released {
+            if let Err(err) = self.unsubscribe_resource(&released_uri).await {
+                tracing::debug!("Failed to unsubscribe from {}: {}", released_uri, err);
             }
-            _ => Err(anyhow!("Invalid response type")),
         }
+
+        result
+    }
+
+    /// Send a resources/read request
+    async fn fetch_resource(&self, uri: &str, meta: Option<Meta>) -> Result<ResourceReadResult> {
+        let params = ResourceReadParams {
+            uri: uri.to_string(),
+            meta,
+        };
+
+        // Send resources/read request
+        self.request::<ReadResource>(params).await
+    }
+
+    /// Get the resource cache's hit, miss and eviction counters, if the cache is enabled
+    pub async fn resource_cache_stats(&self) -> Option<ResourceCacheStats> {
+        match &self.content_cache {
+            Some(cache) => Some(cache.lock().await.stats()),
+            None => None,
+        }
+    }
+
+    /// Ask the server to send `notifications/resources/updated` when a resource changes
+    pub async fn subscribe_resource(&self, uri: &str) -> Result<()> {
+        let params = ResourceSubscribeParams {
+            uri: uri.to_string(),
+            meta: None,
+        };
+        self.request::<Subscribe>(params).await?;
+        Ok(())
+    }
+
+    /// Stop receiving `notifications/resources/updated` for a resource
+    pub async fn unsubscribe_resource(&self, uri: &str) -> Result<()> {
+        let params = ResourceUnsubscribeParams {
+            uri: uri.to_string(),
+            meta: None,
+        };
+        self.request::<Unsubscribe>(params).await?;
+        Ok(())
+    }
+
+    /// Whether the server advertised the resources `subscribe` capability
+    async fn server_supports_subscriptions(&self) -> bool {
+        self.initialized_result
+            .read()
+            .await
+            .as_ref()
+            .and_then(|result| result.capabilities.resources.as_ref())
+            .and_then(|resources| resources.get("subscribe"))
+            .copied()
+            .unwrap_or(false)
     }
 
     /// Get completion suggestions for a resource or prompt parameter
     pub async fn complete(&self, request: CompleteRequest) -> Result<CompleteResponse> {
+        let meta = request.meta.clone();
+        self.complete_with_meta(request, meta).await
+    }
+
+    /// Get completion suggestions, sending `meta` as the request's `_meta`
+    pub async fn complete_with_meta(
+        &self,
+        mut request: CompleteRequest,
+        meta: Option<Meta>,
+    ) -> Result<CompleteResponse> {
         // Check if we're initialized
         {
             let state = self.state.read().await;
@@ -274,30 +539,8 @@ impl Client {
         }
 
         // Send completion/complete request
-        let id = self.next_request_id().await?;
-        let response = self
-            .send_request("completion/complete", Some(json!(request)), id.to_string())
-            .await?;
-
-        match response {
-            JsonRpcMessage::Response { result, error, .. } => {
-                if let Some(error) = error {
-                    return Err(anyhow!(
-                        "Completion error: {} (code: {})",
-                        error.message,
-                        error.code
-                    ));
-                }
-
-                if let Some(result) = result {
-                    let result: CompleteResponse = serde_json::from_value(result)?;
-                    return Ok(result);
-                }
-
-                Err(anyhow!("Invalid completion response"))
-            }
-            _ => Err(anyhow!("Invalid response type")),
-        }
+        request.meta = meta;
+        self.request::<Complete>(request).await
     }
 
     /// Call a tool on the server
@@ -305,6 +548,16 @@ impl Client {
         &self,
         name: &str,
         arguments: &serde_json::Value,
+    ) -> Result<ToolCallResult> {
+        self.call_tool_with_meta(name, arguments, None).await
+    }
+
+    /// Call a tool, sending `meta` as the request's `_meta`, e.g. to ask for progress updates
+    pub async fn call_tool_with_meta(
+        &self,
+        name: &str,
+        arguments: &serde_json::Value,
+        meta: Option<Meta>,
     ) -> Result<ToolCallResult> {
         // Check if we're initialized
         {
@@ -314,37 +567,123 @@ impl Client {
             }
         }
 
+        self.check_tool_policy(name, arguments).await?;
+
         // Create tool call parameters
         let params = ToolCallParams {
             name: name.to_string(),
             arguments: arguments.clone(),
+            meta,
         };
 
         // Send tools/call request
-        let id = self.next_request_id().await?;
-        let response = self
-            .send_request(methods::TOOLS_CALL, Some(json!(params)), id.to_string())
-            .await?;
+        self.request::<CallTool>(params).await
+    }
 
-        match response {
-            JsonRpcMessage::Response { result, error, .. } => {
-                if let Some(error) = error {
-                    return Err(anyhow!(
-                        "Tool call error: {} (code: {})",
-                        error.message,
-                        error.code
-                    ));
-                }
+    /// Get the server's tools, fetching them only if the cache is empty or stale
+    ///
+    /// The cache is invalidated whenever the server sends `notifications/tools/list_changed`.
+    pub async fn cached_tools(&self) -> Result<Vec<Tool>> {
+        if let Some(tools) = self.tool_cache.read().await.as_ref() {
+            return Ok(tools.clone());
+        }
 
-                if let Some(result) = result {
-                    let result: ToolCallResult = serde_json::from_value(result)?;
-                    return Ok(result);
-                }
+        self.refresh_tool_cache().await
+    }
 
-                Err(anyhow!("Invalid tool call response"))
-            }
-            _ => Err(anyhow!("Invalid response type")),
+    /// Fetch every page of tools and store the result in the cache
+    async fn refresh_tool_cache(&self) -> Result<Vec<Tool>> {
+        let tools: Vec<Tool> = self.list_all_tools().try_collect().await?;
+        *self.tool_cache.write().await = Some(tools.clone());
+        Ok(tools)
+    }
+
+    /// Get the server's resources, fetching them only if the cache is empty or stale
+    ///
+    /// The cache is invalidated whenever the server sends `notifications/resources/list_changed`.
+    pub async fn cached_resources(&self) -> Result<Vec<Resource>> {
+        if let Some(resources) = self.resource_cache.read().await.as_ref() {
+            return Ok(resources.clone());
         }
+
+        let resources: Vec<Resource> = self.list_all_resources().try_collect().await?;
+        *self.resource_cache.write().await = Some(resources.clone());
+        Ok(resources)
+    }
+
+    /// Get the server's resource templates, fetching them only if the cache is empty or stale
+    ///
+    /// The cache is invalidated whenever the server sends `notifications/resources/list_changed`.
+    pub async fn cached_resource_templates(&self) -> Result<Vec<ResourceTemplate>> {
+        if let Some(templates) = self.template_cache.read().await.as_ref() {
+            return Ok(templates.clone());
+        }
+
+        let templates: Vec<ResourceTemplate> =
+            self.list_all_resource_templates().try_collect().await?;
+        *self.template_cache.write().await = Some(templates.clone());
+        Ok(templates)
+    }
+
+    /// Look up a tool in the cache without contacting the server
+    async fn cached_tool(&self, name: &str) -> Option<Tool> {
+        self.tool_cache
+            .read()
+            .await
+            .as_ref()
+            .and_then(|tools| tools.iter().find(|tool| tool.name == name).cloned())
+    }
+
+    /// Ask the tool policy, if any, whether a call to a destructive tool may go ahead
+    async fn check_tool_policy(&self, name: &str, arguments: &serde_json::Value) -> Result<()> {
+        let policy = match &self.tool_policy {
+            Some(policy) => policy,
+            None => return Ok(()),
+        };
+
+        // Refresh the cache if we have not seen this tool yet
+        let mut tool = self.cached_tool(name).await;
+        if tool.is_none() {
+            self.refresh_tool_cache().await?;
+            tool = self.cached_tool(name).await;
+        }
+
+        // A tool the server does not list has no annotations to go by, so it is treated as
+        // destructive like any other unannotated tool
+        let tool = tool.unwrap_or_else(|| Tool {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            input_schema: serde_json::json!({ "type": "object" }),
+            output_schema: None,
+            annotations: None,
+        });
+
+        if tool.is_destructive() && !policy(&tool, arguments) {
+            return Err(anyhow!("Call to destructive tool {} was not confirmed", name));
+        }
+
+        Ok(())
+    }
+
+    /// Call a tool and deserialize its structured output into `T`
+    ///
+    /// Fails if the tool reports an error or does not return structured content.
+    pub async fn call_tool_typed<T: DeserializeOwned>(
+        &self,
+        name: &str,
+        arguments: &serde_json::Value,
+    ) -> Result<T> {
+        let result = self.call_tool(name, arguments).await?;
+
+        if result.is_error == Some(true) {
+            return Err(anyhow!("Tool {} returned an error", name));
+        }
+
+        let content = result
+            .structured_content
+            .ok_or_else(|| anyhow!("Tool {} returned no structured content", name))?;
+        Ok(serde_json::from_value(content)?)
     }
 
     /// Shutdown the client
@@ -369,6 +708,41 @@ impl Client {
         Ok(())
     }
 
+    /// Get a prompt's messages, filled in with `arguments`
+    pub async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: Option<HashMap<String, String>>,
+    ) -> Result<PromptGetResult> {
+        self.get_prompt_with_meta(name, arguments, None).await
+    }
+
+    /// Get a prompt's messages, sending `meta` as the request's `_meta`
+    pub async fn get_prompt_with_meta(
+        &self,
+        name: &str,
+        arguments: Option<HashMap<String, String>>,
+        meta: Option<Meta>,
+    ) -> Result<PromptGetResult> {
+        // Check if we're initialized
+        {
+            let state = self.state.read().await;
+            if *state != ClientState::Ready {
+                return Err(anyhow!("Client not initialized"));
+            }
+        }
+
+        // Create prompt parameters
+        let params = PromptGetParams {
+            name: name.to_string(),
+            arguments,
+            meta,
+        };
+
+        // Send prompts/get request
+        self.request::<GetPrompt>(params).await
+    }
+
     /// Refresh the list of available prompts
     pub async fn refresh_prompts(&self) -> Result<serde_json::Value> {
         // Check if we're initialized
@@ -380,27 +754,30 @@ impl Client {
         }
 
         // Send prompts/list request
+        let result = self
+            .request::<ListPrompts>(PromptsListParams {
+                cursor: None,
+                meta: None,
+            })
+            .await?;
+        Ok(json!(result))
+    }
+
+    /// Send a typed request and wait for its result
+    pub async fn request<R: Request>(&self, params: R::Params) -> Result<R::Result> {
         let id = self.next_request_id().await?;
         let response = self
-            .send_request(methods::PROMPTS_LIST, None, id.to_string())
+            .send_request(R::METHOD, Some(serde_json::to_value(params)?), id)
             .await?;
 
         match response {
-            JsonRpcMessage::Response { result, error, .. } => {
-                if let Some(error) = error {
-                    return Err(anyhow!(
-                        "List prompts error: {} (code: {})",
-                        error.message,
-                        error.code
-                    ));
-                }
-
-                if let Some(result) = result {
-                    return Ok(result);
-                }
-
-                Err(anyhow!("Invalid list prompts response"))
-            }
+            JsonRpcMessage::Response(response) => Ok(serde_json::from_value(response.result)?),
+            JsonRpcMessage::Error(response) => Err(anyhow!(
+                "{} error: {} (code: {})",
+                R::METHOD,
+                response.error.message,
+                response.error.code
+            )),
             _ => Err(anyhow!("Invalid response type")),
         }
     }
@@ -418,10 +795,11 @@ impl Client {
         &self,
         method: &str,
         params: Option<serde_json::Value>,
-        id: String,
+        id: impl Into<RequestId>,
     ) -> Result<JsonRpcMessage> {
         // Create request
-        let request = JsonRpcMessage::request(id.clone().into(), method, params);
+        let id = id.into();
+        let request = JsonRpcMessage::request(id.clone(), method, params);
 
         // Create response channel
         let (tx, mut rx) = mpsc::channel(1);
@@ -461,134 +839,94 @@ impl Client {
     }
 
     /// Handle sampling createMessage request
-    async fn handle_sampling_create_message(&self, message: JsonRpcMessage) -> Result<()> {
-        match message {
-            JsonRpcMessage::Request { id, params, .. } => {
-                // Check if sampling is enabled
-                if !self.sampling_enabled {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::SAMPLING_NOT_ENABLED,
-                            "Sampling is not enabled",
-                            None,
-                        ))
-                        .await?;
-                    return Ok(());
-                }
-
-                // Parse parameters
-                let params: CreateMessageParams = match params {
-                    Some(params) => match serde_json::from_value(params) {
-                        Ok(params) => params,
-                        Err(err) => {
-                            // Send error response
-                            self.transport
-                                .send(JsonRpcMessage::error(
-                                    id,
-                                    error_codes::INVALID_PARAMS,
-                                    &format!("Invalid sampling parameters: {}", err),
-                                    None,
-                                ))
-                                .await?;
-                            return Ok(());
-                        }
-                    },
-                    None => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::INVALID_PARAMS,
-                                "Missing sampling parameters",
-                                None,
-                            ))
-                            .await?;
-                        return Ok(());
-                    }
-                };
-
-                // Get the callback
-                let callback_result = {
-                    let callback = self.sampling_callback.read().await;
-                    if callback.is_some() {
-                        Ok(())
-                    } else {
-                        Err(anyhow!("No sampling callback registered"))
-                    }
-                };
+    async fn handle_sampling_create_message(
+        &self,
+        id: RequestId,
+        params: CreateMessageParams,
+    ) -> Result<()> {
+        // Check if sampling is enabled
+        if !self.sampling_enabled {
+            // Send error response
+            self.transport
+                .send(JsonRpcMessage::error(
+                    id,
+                    error_codes::SAMPLING_NOT_ENABLED,
+                    "Sampling is not enabled",
+                    None,
+                ))
+                .await?;
+            return Ok(());
+        }
 
-                // Check if we have a callback
-                if let Err(_) = callback_result {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::SAMPLING_NO_CALLBACK,
-                            "No sampling callback registered",
-                            None,
-                        ))
-                        .await?;
-                    return Ok(());
-                }
+        // Get the callback
+        let callback_result = {
+            let callback = self.sampling_callback.read().await;
+            if callback.is_some() {
+                Ok(())
+            } else {
+                Err(anyhow!("No sampling callback registered"))
+            }
+        };
 
-                // Call the callback
-                // Get a lock on the callback to invoke it
-                let result = {
-                    let callback_guard = self.sampling_callback.read().await;
-                    // We know this is Some because we checked earlier
-                    if let Some(callback) = &*callback_guard {
-                        callback(params.clone())
-                    } else {
-                        // This shouldn't happen, but just in case
-                        Err(anyhow!("No sampling callback registered"))
-                    }
-                };
+        // Check if we have a callback
+        if callback_result.is_err() {
+            // Send error response
+            self.transport
+                .send(JsonRpcMessage::error(
+                    id,
+                    error_codes::SAMPLING_NO_CALLBACK,
+                    "No sampling callback registered",
+                    None,
+                ))
+                .await?;
+            return Ok(());
+        }
 
-                match result {
-                    Ok(result) => {
-                        // Send response
-                        self.transport
-                            .send(JsonRpcMessage::response(id, json!(result)))
-                            .await?;
-                    }
-                    Err(err) => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::SAMPLING_ERROR,
-                                &format!("Sampling error: {}", err),
-                                None,
-                            ))
-                            .await?;
-                    }
-                }
+        // Call the callback
+        // Get a lock on the callback to invoke it
+        let result = {
+            let callback_guard = self.sampling_callback.read().await;
+            // We know this is Some because we checked earlier
+            if let Some(callback) = &*callback_guard {
+                callback(params.clone())
+            } else {
+                // This shouldn't happen, but just in case
+                Err(anyhow!("No sampling callback registered"))
+            }
+        };
 
-                Ok(())
+        match result {
+            Ok(result) => {
+                // Send response
+                self.transport
+                    .send(JsonRpcMessage::response(id, json!(result)))
+                    .await?;
+            }
+            Err(err) => {
+                // Send error response
+                self.transport
+                    .send(JsonRpcMessage::error(
+                        id,
+                        error_codes::SAMPLING_ERROR,
+                        &format!("Sampling error: {}", err),
+                        None,
+                    ))
+                    .await?;
             }
-            _ => Err(anyhow!(
-                "Expected request message for sampling/createMessage"
-            )),
         }
+
+        Ok(())
     }
 
     /// Handle a received message
     pub async fn handle_message(&self, message: JsonRpcMessage) -> Result<()> {
         match message.clone() {
-            JsonRpcMessage::Response { ref id, .. } => {
-                // Get id as string
-                let id = match id {
-                    serde_json::Value::String(s) => s.clone(),
-                    serde_json::Value::Number(n) => n.to_string(),
-                    _ => return Err(anyhow!("Invalid response ID type")),
-                };
-
+            JsonRpcMessage::Response(JsonRpcResponse { ref id, .. })
+            | JsonRpcMessage::Error(JsonRpcError { ref id, .. }) => {
                 // Find pending request
                 let pending = {
                     let pending = self.pending_requests.read().await;
-                    match pending.get(&id) {
+                    match pending.get(id) {
                         Some(req) => req.response_tx.clone(),
                         None => return Err(anyhow!("No pending request for ID: {}", id)),
                     }
@@ -601,11 +939,11 @@ impl Client {
                     Ok(())
                 }
             }
-            JsonRpcMessage::Notification { method, params, .. } => {
+            JsonRpcMessage::Notification(notification) => {
                 // Handle notification
-                match method.as_str() {
+                match ServerNotification::from_notification(&notification) {
                     // Handle prompt list changed notification
-                    methods::PROMPTS_LIST_CHANGED => {
+                    Ok(ServerNotification::PromptListChanged(_)) => {
                         // Emit a debug message about the change
                         tracing::debug!("Received notification: prompts list changed");
 
@@ -613,33 +951,77 @@ impl Client {
                         // but we'll skip it for now to avoid complexity with clones
                         Ok(())
                     }
+                    // Handle tool list changed notification
+                    Ok(ServerNotification::ToolListChanged(_)) => {
+                        tracing::debug!("Received notification: tools list changed");
+
+                        // Drop the cached tools; the next lookup fetches a fresh list
+                        *self.tool_cache.write().await = None;
+                        Ok(())
+                    }
+                    // Handle resource list changed notification
+                    Ok(ServerNotification::ResourceListChanged(_)) => {
+                        tracing::debug!("Received notification: resources list changed");
+
+                        // Drop the cached listings; the next lookup fetches fresh ones
+                        *self.resource_cache.write().await = None;
+                        *self.template_cache.write().await = None;
+                        if let Some(cache) = &self.content_cache {
+                            cache.lock().await.invalidate_all();
+                        }
+                        Ok(())
+                    }
                     // Handle resource updated notification
-                    methods::RESOURCES_UPDATED => {
-                        // Extract the resource URI if available
-                        if let Some(params) = params {
-                            if let Some(uri) = params.get("uri").and_then(|u| u.as_str()) {
-                                tracing::debug!(
-                                    "Received notification: resource updated - URI: {}",
-                                    uri
-                                );
-                            }
+                    Ok(ServerNotification::ResourceUpdated(params)) => {
+                        tracing::debug!(
+                            "Received notification: resource updated - URI: {}",
+                            params.uri
+                        );
+
+                        if let Some(cache) = &self.content_cache {
+                            cache.lock().await.invalidate(&params.uri);
                         }
                         Ok(())
                     }
                     // Add other handlers for specific notifications here
-                    _ => {
-                        tracing::debug!("Unhandled notification: {}", method);
+                    Ok(notification) => {
+                        tracing::debug!("Unhandled notification: {}", notification.method());
+                        Ok(())
+                    }
+                    Err(err) => {
+                        tracing::debug!("Unhandled notification: {}", err);
                         Ok(())
                     }
                 }
             }
-            JsonRpcMessage::Request { method, .. } => match method.as_str() {
-                methods::SAMPLING_CREATE_MESSAGE => {
-                    self.handle_sampling_create_message(message).await
+            JsonRpcMessage::Request(request) => match ServerRequest::from_request(&request) {
+                Ok(ServerRequest::CreateMessage(params)) => {
+                    self.handle_sampling_create_message(request.id, params)
+                        .await
                 }
-                _ => {
+                Err(MessageParseError::MethodNotFound(method)) => {
                     tracing::debug!("Unhandled server request: {}", method);
-                    Ok(())
+
+                    // Answer so the server isn't left waiting on the request
+                    self.transport
+                        .send(JsonRpcMessage::error(
+                            request.id,
+                            error_codes::METHOD_NOT_FOUND,
+                            &format!("Method not found: {}", method),
+                            None,
+                        ))
+                        .await
+                }
+                Err(err) => {
+                    // Send error response
+                    self.transport
+                        .send(JsonRpcMessage::error(
+                            request.id,
+                            error_codes::INVALID_PARAMS,
+                            &err.to_string(),
+                            None,
+                        ))
+                        .await
                 }
             },
         }
@@ -0,0 +1,49 @@
+// mcp-client/src/display.rs
+//! Names to show users for the tools, prompts and resources a server offers
+use mcp_protocol::types::content::ResourceLink;
+use mcp_protocol::types::prompt::{Prompt, PromptArgument};
+use mcp_protocol::types::resource::{Resource, ResourceTemplate};
+use mcp_protocol::types::tool::Tool;
+use mcp_protocol::types::{ClientInfo, ServerInfo};
+
+/// Something with a programmatic `name` and possibly a human-readable `title`
+pub trait DisplayName {
+    /// The name to show a user: the `title` if there is one, otherwise the `name`
+    fn display_name(&self) -> &str;
+}
+
+impl DisplayName for Tool {
+    /// Tools fall back to the title in their annotations before their name
+    fn display_name(&self) -> &str {
+        self.title
+            .as_deref()
+            .or_else(|| {
+                self.annotations
+                    .as_ref()
+                    .and_then(|annotations| annotations.title.as_deref())
+            })
+            .unwrap_or(&self.name)
+    }
+}
+
+macro_rules! title_or_name {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl DisplayName for $ty {
+                fn display_name(&self) -> &str {
+                    self.title.as_deref().unwrap_or(&self.name)
+                }
+            }
+        )*
+    };
+}
+
+title_or_name!(
+    Prompt,
+    PromptArgument,
+    Resource,
+    ResourceTemplate,
+    ResourceLink,
+    ServerInfo,
+    ClientInfo,
+);
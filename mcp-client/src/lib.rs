@@ -1,8 +1,12 @@
 // mcp-client/src/lib.rs
+pub mod cache;
 pub mod client;
+pub mod display;
 pub mod transport;
 
+pub use cache::{ResourceCacheConfig, ResourceCacheStats};
 pub use client::{Client, ClientBuilder};
+pub use display::DisplayName;
 pub use transport::Transport;
 
 pub use mcp_protocol;
@@ -0,0 +1,167 @@
+// mcp-client/tests/common/mod.rs
+//! Fixtures shared by the client integration tests
+#![allow(dead_code)] // Each test crate uses a different subset
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use modelcontextprotocol_client::mcp_protocol::messages::{JsonRpcMessage, JsonRpcNotification};
+use modelcontextprotocol_client::{Client, ClientBuilder};
+use modelcontextprotocol_server::ServerBuilder;
+
+/// Client end of an in-process connection
+#[derive(Clone)]
+struct ClientEnd {
+    outgoing: mpsc::UnboundedSender<JsonRpcMessage>,
+}
+
+#[async_trait]
+impl modelcontextprotocol_client::transport::Transport for ClientEnd {
+    async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn send(&self, message: JsonRpcMessage) -> Result<()> {
+        self.outgoing.send(message)?;
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn modelcontextprotocol_client::transport::Transport> {
+        Box::new(self.clone())
+    }
+}
+
+/// Server end of an in-process connection
+#[derive(Clone)]
+struct ServerEnd {
+    incoming: Arc<Mutex<Option<mpsc::UnboundedReceiver<JsonRpcMessage>>>>,
+    outgoing: mpsc::UnboundedSender<JsonRpcMessage>,
+}
+
+#[async_trait]
+impl modelcontextprotocol_server::Transport for ServerEnd {
+    async fn start(&self, message_tx: mpsc::Sender<JsonRpcMessage>) -> Result<()> {
+        let mut incoming = self.incoming.lock().unwrap().take().unwrap();
+        tokio::spawn(async move {
+            while let Some(message) = incoming.recv().await {
+                if message_tx.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn send(&self, message: JsonRpcMessage) -> Result<()> {
+        self.outgoing.send(message)?;
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn modelcontextprotocol_server::Transport> {
+        Box::new(self.clone())
+    }
+}
+
+/// A client connected to an in-process server
+pub struct Connection {
+    pub client: Arc<Client>,
+    notifications: Arc<Mutex<Vec<JsonRpcNotification>>>,
+    server: JoinHandle<Result<()>>,
+    dispatch: JoinHandle<()>,
+}
+
+impl Connection {
+    /// Run the server, connect the client to it and initialize
+    pub async fn start(server: ServerBuilder, client: ClientBuilder) -> Self {
+        let (client_tx, server_rx) = mpsc::unbounded_channel();
+        let (server_tx, mut client_rx) = mpsc::unbounded_channel();
+
+        let server = server
+            .with_transport(ServerEnd {
+                incoming: Arc::new(Mutex::new(Some(server_rx))),
+                outgoing: server_tx,
+            })
+            .build()
+            .unwrap();
+        let server = tokio::spawn(async move { server.run().await });
+
+        let client = Arc::new(
+            client
+                .with_transport(ClientEnd {
+                    outgoing: client_tx,
+                })
+                .build()
+                .unwrap(),
+        );
+        let notifications = Arc::new(Mutex::new(Vec::new()));
+        let dispatch = tokio::spawn({
+            let client = client.clone();
+            let notifications = notifications.clone();
+            async move {
+                while let Some(message) = client_rx.recv().await {
+                    let notification = match &message {
+                        JsonRpcMessage::Notification(notification) => Some(notification.clone()),
+                        _ => None,
+                    };
+                    let _ = client.handle_message(message).await;
+                    // Recorded once handled, so tests can rely on its effect
+                    if let Some(notification) = notification {
+                        notifications.lock().unwrap().push(notification);
+                    }
+                }
+            }
+        });
+
+        client.initialize().await.unwrap();
+        Self {
+            client,
+            notifications,
+            server,
+            dispatch,
+        }
+    }
+
+    /// The `uri` of every notification with this method the client has handled so far
+    pub fn notified_uris(&self, method: &str) -> Vec<String> {
+        self.notifications
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|notification| notification.method == method)
+            .map(|notification| match &notification.params {
+                Some(params) => params["uri"].as_str().unwrap_or_default().to_string(),
+                None => String::new(),
+            })
+            .collect()
+    }
+
+    /// Wait until the client has handled `count` notifications with this method
+    pub async fn wait_for_notifications(&self, method: &str, count: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while self.notified_uris(method).len() < count {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    /// Stop the server and the client's message loop
+    pub fn stop(self) {
+        self.server.abort();
+        self.dispatch.abort();
+    }
+}
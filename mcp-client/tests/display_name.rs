@@ -0,0 +1,46 @@
+// mcp-client/tests/display_name.rs
+use serde_json::json;
+
+use modelcontextprotocol_client::mcp_protocol::types::prompt::Prompt;
+use modelcontextprotocol_client::mcp_protocol::types::tool::Tool;
+use modelcontextprotocol_client::DisplayName;
+
+fn tool(value: serde_json::Value) -> Tool {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn tools_prefer_title_then_annotation_title_then_name() {
+    let schema = json!({"type": "object"});
+
+    let titled = tool(json!({
+        "name": "get_weather",
+        "title": "Weather",
+        "inputSchema": schema,
+        "annotations": {"title": "Weather Lookup"}
+    }));
+    assert_eq!(titled.display_name(), "Weather");
+
+    let annotated = tool(json!({
+        "name": "get_weather",
+        "inputSchema": schema,
+        "annotations": {"title": "Weather Lookup"}
+    }));
+    assert_eq!(annotated.display_name(), "Weather Lookup");
+
+    let bare = tool(json!({"name": "get_weather", "inputSchema": schema}));
+    assert_eq!(bare.display_name(), "get_weather");
+}
+
+#[test]
+fn other_items_prefer_title_then_name() {
+    let prompt: Prompt = serde_json::from_value(json!({
+        "name": "code_review",
+        "title": "Request Code Review"
+    }))
+    .unwrap();
+    assert_eq!(prompt.display_name(), "Request Code Review");
+
+    let prompt: Prompt = serde_json::from_value(json!({"name": "code_review"})).unwrap();
+    assert_eq!(prompt.display_name(), "code_review");
+}
@@ -0,0 +1,121 @@
+// mcp-client/tests/listing_cache.rs
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use modelcontextprotocol_client::mcp_protocol::types::resource::{
+    Resource, ResourceContent, ResourceTemplate,
+};
+use modelcontextprotocol_client::ClientBuilder;
+use modelcontextprotocol_server::resources::ResourceManager;
+use modelcontextprotocol_server::{
+    HandlerResult, IncomingRequest, Middleware, Next, ServerBuilder,
+};
+
+mod common;
+use common::Connection;
+
+const LIST_CHANGED: &str = "notifications/resources/list_changed";
+
+/// Records the method of every list request
+struct ListLog(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl Middleware for ListLog {
+    async fn handle(&self, request: IncomingRequest, next: Next<'_>) -> HandlerResult {
+        if request.method.ends_with("/list") {
+            self.0.lock().unwrap().push(request.method.clone());
+        }
+        next.run(request).await
+    }
+}
+
+fn add_resource(manager: &ResourceManager, uri: &str) {
+    let resource = Resource {
+        uri: uri.to_string(),
+        name: uri.to_string(),
+        description: None,
+        mime_type: None,
+        size: None,
+        annotations: None,
+        title: None,
+    };
+    manager.register_resource(resource, || Ok(Vec::<ResourceContent>::new()));
+}
+
+fn add_template(manager: &ResourceManager, uri_template: &str) {
+    let template = ResourceTemplate {
+        uri_template: uri_template.to_string(),
+        name: uri_template.to_string(),
+        description: None,
+        mime_type: None,
+        annotations: None,
+        title: None,
+    };
+    manager.register_template(template, |_| Ok(Vec::new()));
+}
+
+#[tokio::test]
+async fn list_changed_invalidates_the_cached_listings() {
+    let manager = Arc::new(ResourceManager::new());
+    add_resource(&manager, "memo://a");
+    add_template(&manager, "memo://t/{id}");
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let server = ServerBuilder::new("listing-test", "0.1.0")
+        .with_resource_manager(manager.clone())
+        .with_middleware(ListLog(log.clone()));
+    let connection = Connection::start(server, ClientBuilder::new("listing-test", "0.1.0")).await;
+    let client = &connection.client;
+
+    let uris = |resources: Vec<Resource>| -> Vec<String> {
+        resources.into_iter().map(|resource| resource.uri).collect()
+    };
+    let templates = |templates: Vec<ResourceTemplate>| -> Vec<String> {
+        templates
+            .into_iter()
+            .map(|template| template.uri_template)
+            .collect()
+    };
+
+    // Fetched once, then served from the cache
+    for _ in 0..2 {
+        assert_eq!(uris(client.cached_resources().await.unwrap()), ["memo://a"]);
+        assert_eq!(
+            templates(client.cached_resource_templates().await.unwrap()),
+            ["memo://t/{id}"]
+        );
+    }
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["resources/list", "resources/templates/list"]
+    );
+
+    add_resource(&manager, "memo://b");
+    connection.wait_for_notifications(LIST_CHANGED, 1).await;
+    assert_eq!(
+        uris(client.cached_resources().await.unwrap()),
+        ["memo://a", "memo://b"]
+    );
+
+    manager.remove_template("memo://t/{id}").unwrap();
+    connection.wait_for_notifications(LIST_CHANGED, 2).await;
+    assert!(client.cached_resource_templates().await.unwrap().is_empty());
+
+    // Both listings were dropped each time, and fetched again only when asked for
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "resources/list",
+            "resources/templates/list",
+            "resources/list",
+            "resources/templates/list",
+        ]
+    );
+    assert_eq!(
+        uris(client.cached_resources().await.unwrap()),
+        ["memo://a", "memo://b"]
+    );
+    assert_eq!(log.lock().unwrap().len(), 5);
+
+    connection.stop();
+}
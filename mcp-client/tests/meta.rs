@@ -0,0 +1,207 @@
+// mcp-client/tests/meta.rs
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use modelcontextprotocol_client::mcp_protocol::messages::Meta;
+use modelcontextprotocol_client::mcp_protocol::types::completion::{
+    CompleteRequest, CompletionArgument, CompletionReference,
+};
+use modelcontextprotocol_client::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_client::mcp_protocol::types::prompt::{Prompt, PromptMessage};
+use modelcontextprotocol_client::mcp_protocol::types::resource::{Resource, ResourceContent};
+use modelcontextprotocol_client::mcp_protocol::types::tool::{Tool, ToolCallResult};
+use modelcontextprotocol_client::ClientBuilder;
+use modelcontextprotocol_server::prompts::PromptManager;
+use modelcontextprotocol_server::resources::ResourceManager;
+use modelcontextprotocol_server::tools::ToolManager;
+use modelcontextprotocol_server::{
+    HandlerResult, IncomingRequest, Middleware, Next, ServerBuilder,
+};
+
+mod common;
+use common::Connection;
+
+/// The method and `_meta` of each request, in the order they arrived
+type Log = Arc<Mutex<Vec<(String, Option<Meta>)>>>;
+
+/// Records the `_meta` of every request after `initialize`
+struct MetaLog(Log);
+
+#[async_trait]
+impl Middleware for MetaLog {
+    async fn handle(&self, request: IncomingRequest, next: Next<'_>) -> HandlerResult {
+        if request.method != "initialize" {
+            self.0
+                .lock()
+                .unwrap()
+                .push((request.method.clone(), request.meta.clone()));
+        }
+        next.run(request).await
+    }
+}
+
+/// Describe a handler's `_meta` so it can be sent back as text
+fn describe(meta: Option<&Meta>) -> String {
+    serde_json::to_string(&meta).unwrap()
+}
+
+fn text_of(content: &ContentBlock) -> &str {
+    match content {
+        ContentBlock::Text(content) => &content.text,
+        other => panic!("expected text, got {:?}", other),
+    }
+}
+
+async fn connect(log: Log) -> Connection {
+    let tools = Arc::new(ToolManager::new());
+    tools.register_tool_with_meta(
+        Tool {
+            name: "echo-meta".to_string(),
+            title: None,
+            description: None,
+            input_schema: json!({"type": "object"}),
+            output_schema: None,
+            annotations: None,
+        },
+        |_, meta| async move {
+            Ok(ToolCallResult {
+                content: vec![ContentBlock::text(describe(meta.as_ref()))],
+                is_error: None,
+                structured_content: None,
+                meta: None,
+            })
+        },
+    );
+
+    let prompts = Arc::new(PromptManager::new());
+    prompts.register_prompt_with_meta(
+        Prompt {
+            name: "echo-meta".to_string(),
+            title: None,
+            description: None,
+            arguments: None,
+            annotations: None,
+        },
+        |_, meta| {
+            Ok(vec![PromptMessage {
+                role: "user".to_string(),
+                content: ContentBlock::text(describe(meta)),
+            }])
+        },
+    );
+
+    // Hands the request's `_meta` back on the content
+    let resources = Arc::new(ResourceManager::new());
+    resources.register_async_resource(
+        Resource {
+            uri: "memo://meta".to_string(),
+            name: "Meta".to_string(),
+            description: None,
+            mime_type: None,
+            size: None,
+            annotations: None,
+            title: None,
+        },
+        |context| async move {
+            Ok(vec![ResourceContent {
+                uri: context.uri,
+                mime_type: "text/plain".to_string(),
+                text: Some("meta".to_string()),
+                blob: None,
+                meta: context.meta,
+            }])
+        },
+    );
+
+    let server = ServerBuilder::new("meta-test", "0.1.0")
+        .with_tool_manager(tools)
+        .with_prompt_manager(prompts)
+        .with_resource_manager(resources)
+        .with_middleware(MetaLog(log));
+    Connection::start(server, ClientBuilder::new("meta-test", "0.1.0")).await
+}
+
+#[tokio::test]
+async fn request_meta_reaches_handlers_and_content_meta_comes_back() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let connection = connect(log.clone()).await;
+    let client = &connection.client;
+    let meta = Meta::new()
+        .with_progress_token("progress-1")
+        .with("example.com/trace-id", json!("abc"));
+    let described = describe(Some(&meta));
+
+    let result = client
+        .call_tool_with_meta("echo-meta", &json!({}), Some(meta.clone()))
+        .await
+        .unwrap();
+    assert_eq!(text_of(&result.content[0]), described);
+    let result = client.call_tool("echo-meta", &json!({})).await.unwrap();
+    assert_eq!(text_of(&result.content[0]), "null");
+
+    let result = client
+        .get_prompt_with_meta("echo-meta", None, Some(meta.clone()))
+        .await
+        .unwrap();
+    assert_eq!(text_of(&result.messages[0].content), described);
+    let result = client.get_prompt("echo-meta", None).await.unwrap();
+    assert_eq!(text_of(&result.messages[0].content), "null");
+
+    // `_meta` on resource contents survives the trip to the client
+    let result = client
+        .read_resource_with_meta("memo://meta", Some(meta.clone()))
+        .await
+        .unwrap();
+    assert_eq!(result.contents[0].meta.as_ref(), Some(&meta));
+    let result = client.read_resource("memo://meta").await.unwrap();
+    assert_eq!(result.contents[0].meta, None);
+
+    // The remaining requests only need to carry it
+    log.lock().unwrap().clear();
+    client
+        .list_tools_page_with_meta(None, Some(meta.clone()))
+        .await
+        .unwrap();
+    client
+        .list_resources_page_with_meta(None, Some(meta.clone()))
+        .await
+        .unwrap();
+    client
+        .list_resource_templates_page_with_meta(None, Some(meta.clone()))
+        .await
+        .unwrap();
+    let request = CompleteRequest {
+        r#ref: CompletionReference::Prompt {
+            name: "echo-meta".to_string(),
+        },
+        argument: CompletionArgument {
+            name: "topic".to_string(),
+            value: String::new(),
+        },
+        context: None,
+        meta: None,
+    };
+    client
+        .complete_with_meta(request, Some(meta.clone()))
+        .await
+        .unwrap();
+
+    let log = log.lock().unwrap();
+    let methods: Vec<_> = log.iter().map(|(method, _)| method.as_str()).collect();
+    assert_eq!(
+        methods,
+        [
+            "tools/list",
+            "resources/list",
+            "resources/templates/list",
+            "completion/complete"
+        ]
+    );
+    for (method, received) in log.iter() {
+        assert_eq!(received.as_ref(), Some(&meta), "{}", method);
+    }
+
+    connection.stop();
+}
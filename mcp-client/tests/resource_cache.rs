@@ -0,0 +1,377 @@
+// mcp-client/tests/resource_cache.rs
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{Notify, Semaphore};
+
+use modelcontextprotocol_client::mcp_protocol::types::resource::{
+    Resource, ResourceContent, ResourceTemplate,
+};
+use modelcontextprotocol_client::{Client, ClientBuilder, ResourceCacheConfig, ResourceCacheStats};
+use modelcontextprotocol_server::resources::ResourceManager;
+use modelcontextprotocol_server::{
+    HandlerResult, IncomingRequest, Middleware, Next, ServerBuilder,
+};
+
+mod common;
+use common::Connection;
+
+/// Records every subscribe and unsubscribe request as `"<method> <uri>"`
+struct SubscriptionLog(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl Middleware for SubscriptionLog {
+    async fn handle(&self, request: IncomingRequest, next: Next<'_>) -> HandlerResult {
+        if request.method.ends_with("subscribe") {
+            let uri = request.params.as_ref().unwrap()["uri"].as_str().unwrap();
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", request.method, uri));
+        }
+        next.run(request).await
+    }
+}
+
+fn resource(uri: &str) -> Resource {
+    Resource {
+        uri: uri.to_string(),
+        name: uri.to_string(),
+        description: None,
+        mime_type: None,
+        size: None,
+        annotations: None,
+        title: None,
+    }
+}
+
+fn text(uri: &str, text: String) -> ResourceContent {
+    ResourceContent {
+        uri: uri.to_string(),
+        mime_type: "text/plain".to_string(),
+        text: Some(text),
+        blob: None,
+        meta: None,
+    }
+}
+
+/// Serves each URI as `"<uri> read <n>"`, counting the reads of every URI separately
+fn counting_resource(manager: &ResourceManager, uri: &str) {
+    let reads = Arc::new(Mutex::new(0));
+    let uri = uri.to_string();
+    manager.register_resource(resource(&uri), move || {
+        let mut reads = reads.lock().unwrap();
+        *reads += 1;
+        Ok(vec![text(&uri, format!("{} read {}", uri, reads))])
+    });
+}
+
+/// A server with the given resources, and a log of the client's subscription requests
+fn server(uris: &[&str]) -> (ServerBuilder, Arc<ResourceManager>, Arc<Mutex<Vec<String>>>) {
+    let manager = Arc::new(ResourceManager::new());
+    for uri in uris {
+        counting_resource(&manager, uri);
+    }
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let server = ServerBuilder::new("cache-test", "0.1.0")
+        .with_resource_manager(manager.clone())
+        .with_middleware(SubscriptionLog(log.clone()));
+    (server, manager, log)
+}
+
+async fn connect(server: ServerBuilder, config: ResourceCacheConfig) -> Connection {
+    let client = ClientBuilder::new("cache-test", "0.1.0").with_resource_cache(config);
+    Connection::start(server, client).await
+}
+
+async fn read(client: &Client, uri: &str) -> String {
+    let result = client.read_resource(uri).await.unwrap();
+    result.contents[0].text.clone().unwrap()
+}
+
+async fn stats(client: &Client) -> ResourceCacheStats {
+    client.resource_cache_stats().await.unwrap()
+}
+
+/// Wait until the client has dropped `count` entries on the server's word
+async fn wait_for_invalidations(client: &Client, count: u64) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while stats(client).await.invalidations < count {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn least_recently_used_entries_are_evicted() {
+    let (server, _, log) = server(&["memo://a", "memo://b", "memo://c"]);
+    let config = ResourceCacheConfig::default()
+        .with_max_entries(2)
+        .with_ttl(None);
+    let connection = connect(server, config).await;
+    let client = &connection.client;
+
+    assert_eq!(read(client, "memo://a").await, "memo://a read 1");
+    assert_eq!(read(client, "memo://b").await, "memo://b read 1");
+    assert_eq!(read(client, "memo://a").await, "memo://a read 1");
+
+    // `b` is the least recently used, so `c` pushes it out; then `b` pushes out `c`
+    assert_eq!(read(client, "memo://c").await, "memo://c read 1");
+    assert_eq!(read(client, "memo://a").await, "memo://a read 1");
+    assert_eq!(read(client, "memo://b").await, "memo://b read 2");
+
+    let stats = stats(client).await;
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 4, 2));
+    assert_eq!(stats.entries, 2);
+
+    // Evicted entries give up their subscriptions
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "resources/subscribe memo://a",
+            "resources/subscribe memo://b",
+            "resources/subscribe memo://c",
+            "resources/unsubscribe memo://b",
+            "resources/subscribe memo://b",
+            "resources/unsubscribe memo://c",
+        ]
+    );
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn oversized_contents_are_not_cached() {
+    let (server, _, log) = server(&["memo://a", "memo://long-name"]);
+    let config = ResourceCacheConfig::default().with_max_bytes(40);
+    let connection = connect(server, config).await;
+    let client = &connection.client;
+
+    // `a` takes 8 + 10 + 15 bytes; `long-name` takes 16 + 10 + 23 and never fits
+    assert_eq!(read(client, "memo://a").await, "memo://a read 1");
+    assert_eq!(
+        read(client, "memo://long-name").await,
+        "memo://long-name read 1"
+    );
+    assert_eq!(
+        read(client, "memo://long-name").await,
+        "memo://long-name read 2"
+    );
+    assert_eq!(read(client, "memo://a").await, "memo://a read 1");
+
+    let stats = stats(client).await;
+    assert_eq!(stats.entries, 1);
+    assert_eq!(stats.bytes, 33);
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "resources/subscribe memo://a",
+            "resources/subscribe memo://long-name",
+            "resources/unsubscribe memo://long-name",
+            "resources/subscribe memo://long-name",
+            "resources/unsubscribe memo://long-name",
+        ]
+    );
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn entries_expire_after_the_ttl() {
+    let (server, _, log) = server(&["memo://a"]);
+    let config = ResourceCacheConfig::default().with_ttl(Some(Duration::from_millis(100)));
+    let connection = connect(server, config).await;
+    let client = &connection.client;
+
+    assert_eq!(read(client, "memo://a").await, "memo://a read 1");
+    assert_eq!(read(client, "memo://a").await, "memo://a read 1");
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(read(client, "memo://a").await, "memo://a read 2");
+    assert_eq!(read(client, "memo://a").await, "memo://a read 2");
+
+    let stats = stats(client).await;
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 2, 1));
+
+    // The URI is read again straight away, so it keeps its subscription
+    assert_eq!(*log.lock().unwrap(), ["resources/subscribe memo://a"]);
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn updates_drop_only_the_changed_entry() {
+    let (server, manager, log) = server(&["memo://a", "memo://b"]);
+    let connection = connect(server, ResourceCacheConfig::default()).await;
+    let client = &connection.client;
+
+    read(client, "memo://a").await;
+    read(client, "memo://b").await;
+
+    manager
+        .update_resource(resource("memo://a"), || {
+            Ok(vec![text("memo://a", "edited".to_string())])
+        })
+        .await
+        .unwrap();
+    wait_for_invalidations(client, 1).await;
+
+    assert_eq!(read(client, "memo://a").await, "edited");
+    assert_eq!(read(client, "memo://b").await, "memo://b read 1");
+    assert_eq!(stats(client).await.entries, 2);
+
+    // Read again before it was released, so the subscription is kept
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "resources/subscribe memo://a",
+            "resources/subscribe memo://b"
+        ]
+    );
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn reads_that_race_an_invalidation_are_not_stored() {
+    let (server, manager, log) = server(&["memo://a"]);
+    let entered = Arc::new(Notify::new());
+    let gate = Arc::new(Semaphore::new(0));
+    let reads = Arc::new(Mutex::new(0));
+    manager.register_async_resource(resource("memo://slow"), {
+        let entered = entered.clone();
+        let gate = gate.clone();
+        let reads = reads.clone();
+        move |_| {
+            let entered = entered.clone();
+            let gate = gate.clone();
+            let reads = reads.clone();
+            async move {
+                let read = {
+                    let mut reads = reads.lock().unwrap();
+                    *reads += 1;
+                    *reads
+                };
+                entered.notify_one();
+                gate.acquire().await?.forget();
+                Ok(vec![text("memo://slow", format!("slow read {}", read))])
+            }
+        }
+    });
+    let connection = connect(server, ResourceCacheConfig::default()).await;
+    let client = connection.client.clone();
+
+    read(&client, "memo://a").await;
+    let slow = tokio::spawn({
+        let client = client.clone();
+        async move { read(&client, "memo://slow").await }
+    });
+    entered.notified().await;
+
+    // Adding a resource makes the server send `notifications/resources/list_changed`
+    counting_resource(&manager, "memo://new");
+    wait_for_invalidations(&client, 1).await;
+    gate.add_permits(2);
+    assert_eq!(slow.await.unwrap(), "slow read 1");
+
+    // Neither the invalidated entry nor the read that raced the invalidation is cached
+    assert_eq!(stats(&client).await.entries, 0);
+    assert_eq!(read(&client, "memo://slow").await, "slow read 2");
+    assert_eq!(read(&client, "memo://a").await, "memo://a read 2");
+    assert_eq!(stats(&client).await.entries, 2);
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "resources/subscribe memo://a",
+            "resources/subscribe memo://slow",
+            "resources/unsubscribe memo://a",
+            "resources/unsubscribe memo://slow",
+            "resources/subscribe memo://slow",
+            "resources/subscribe memo://a",
+        ]
+    );
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn failed_subscriptions_are_retried() {
+    let (server, manager, log) = server(&["memo://a"]);
+    // The server only accepts subscriptions to concrete resources
+    manager.register_template(
+        ResourceTemplate {
+            uri_template: "memo://t/{id}".to_string(),
+            name: "Memo".to_string(),
+            description: None,
+            mime_type: None,
+            annotations: None,
+            title: None,
+        },
+        |variables: HashMap<String, String>| {
+            let uri = format!("memo://t/{}", variables["id"]);
+            Ok(vec![text(&uri, "templated".to_string())])
+        },
+    );
+    let config = ResourceCacheConfig::default().with_max_entries(1);
+    let connection = connect(server, config).await;
+    let client = &connection.client;
+
+    assert_eq!(read(client, "memo://t/1").await, "templated");
+    read(client, "memo://a").await;
+    assert_eq!(read(client, "memo://t/1").await, "templated");
+
+    // Nothing to unsubscribe from when `t/1` is evicted, and its subscription is tried again
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "resources/subscribe memo://t/1",
+            "resources/subscribe memo://a",
+            "resources/subscribe memo://t/1",
+            "resources/unsubscribe memo://a",
+        ]
+    );
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn evicted_entries_stop_receiving_updates() {
+    let (server, manager, log) = server(&["memo://a", "memo://b"]);
+    let config = ResourceCacheConfig::default().with_max_entries(1);
+    let connection = connect(server, config).await;
+    let client = &connection.client;
+
+    // Reading `b` evicts `a` and gives up its subscription
+    read(client, "memo://a").await;
+    read(client, "memo://b").await;
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "resources/subscribe memo://a",
+            "resources/subscribe memo://b",
+            "resources/unsubscribe memo://a",
+        ]
+    );
+
+    for uri in ["memo://a", "memo://b"] {
+        manager
+            .update_resource(resource(uri), move || {
+                Ok(vec![text(uri, "edited".to_string())])
+            })
+            .await
+            .unwrap();
+    }
+    wait_for_invalidations(client, 1).await;
+
+    // Updates are sent in order, so one for `a` would have arrived first
+    assert_eq!(
+        connection.notified_uris("notifications/resources/updated"),
+        ["memo://b"]
+    );
+
+    connection.stop();
+}
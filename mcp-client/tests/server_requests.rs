@@ -0,0 +1,60 @@
+// mcp-client/tests/server_requests.rs
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use modelcontextprotocol_client::mcp_protocol::constants::error_codes;
+use modelcontextprotocol_client::mcp_protocol::messages::{JsonRpcMessage, RequestId};
+use modelcontextprotocol_client::{ClientBuilder, Transport};
+
+/// Transport that keeps everything the client sends
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<JsonRpcMessage>>>);
+
+#[async_trait]
+impl Transport for Recorder {
+    async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn send(&self, message: JsonRpcMessage) -> Result<()> {
+        self.0.lock().unwrap().push(message);
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Transport> {
+        Box::new(self.clone())
+    }
+}
+
+#[tokio::test]
+async fn unknown_server_requests_get_method_not_found() {
+    let sent = Recorder::default();
+    let client = ClientBuilder::new("requests-test", "0.1.0")
+        .with_transport(sent.clone())
+        .build()
+        .unwrap();
+
+    client
+        .handle_message(JsonRpcMessage::request(7, "experimental/unknown", None))
+        .await
+        .unwrap();
+
+    let sent = sent.0.lock().unwrap();
+    match sent.as_slice() {
+        [JsonRpcMessage::Error(error)] => {
+            assert_eq!(error.id, RequestId::Number(7));
+            assert_eq!(error.error.code, error_codes::METHOD_NOT_FOUND);
+            assert_eq!(
+                error.error.message,
+                "Method not found: experimental/unknown"
+            );
+        }
+        other => panic!("expected one error, got {:?}", other),
+    }
+}
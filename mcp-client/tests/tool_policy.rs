@@ -0,0 +1,102 @@
+// mcp-client/tests/tool_policy.rs
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use serde_json::json;
+
+use modelcontextprotocol_client::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_client::mcp_protocol::types::tool::{ToolAnnotations, ToolCallResult};
+use modelcontextprotocol_client::ClientBuilder;
+use modelcontextprotocol_server::ServerBuilder;
+
+mod common;
+use common::Connection;
+
+fn ok(_: serde_json::Value) -> Result<ToolCallResult> {
+    Ok(ToolCallResult {
+        content: vec![ContentBlock::text("done")],
+        is_error: None,
+        structured_content: None,
+        meta: None,
+    })
+}
+
+/// A server with one read-only and one destructive tool, and a client whose policy
+/// records every tool it is asked about and rejects them all
+async fn connect() -> (Connection, Arc<Mutex<Vec<String>>>) {
+    let schema = json!({"type": "object"});
+    let server = ServerBuilder::new("policy-test", "0.1.0")
+        .with_annotated_tool(
+            "lookup",
+            None,
+            schema.clone(),
+            Some(ToolAnnotations {
+                read_only_hint: Some(true),
+                ..Default::default()
+            }),
+            ok,
+        )
+        .with_tool("delete", None, schema, ok);
+
+    let asked = Arc::new(Mutex::new(Vec::new()));
+    let client = ClientBuilder::new("policy-test", "0.1.0").with_tool_policy({
+        let asked = asked.clone();
+        move |tool, _| {
+            asked.lock().unwrap().push(tool.name.clone());
+            false
+        }
+    });
+
+    (Connection::start(server, client).await, asked)
+}
+
+#[tokio::test]
+async fn read_only_tools_are_called_without_asking() {
+    let (connection, asked) = connect().await;
+
+    let result = connection
+        .client
+        .call_tool("lookup", &json!({}))
+        .await
+        .unwrap();
+    assert_eq!(result.content.len(), 1);
+    assert!(asked.lock().unwrap().is_empty());
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn destructive_tools_need_confirmation() {
+    let (connection, asked) = connect().await;
+
+    let error = connection
+        .client
+        .call_tool("delete", &json!({}))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Call to destructive tool delete was not confirmed"
+    );
+    assert_eq!(*asked.lock().unwrap(), ["delete"]);
+
+    connection.stop();
+}
+
+#[tokio::test]
+async fn unlisted_tools_are_treated_as_destructive() {
+    let (connection, asked) = connect().await;
+
+    let error = connection
+        .client
+        .call_tool("unlisted", &json!({}))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Call to destructive tool unlisted was not confirmed"
+    );
+    assert_eq!(*asked.lock().unwrap(), ["unlisted"]);
+
+    connection.stop();
+}
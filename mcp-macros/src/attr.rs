@@ -0,0 +1,191 @@
+// mcp-macros/src/attr.rs
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ExprLit, FnArg, GenericArgument, Ident, ItemFn, Lit, LitStr, Meta, MetaNameValue, Pat, PathArguments, Token, Type};
+
+/// `key = "value"` pairs given to one of the attribute macros
+pub struct MacroArgs {
+    values: Vec<(Ident, LitStr)>,
+}
+
+impl MacroArgs {
+    /// Parse the attribute arguments, rejecting keys not listed in `allowed`
+    pub fn parse(tokens: TokenStream, allowed: &[&str]) -> syn::Result<Self> {
+        let pairs = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(tokens)?;
+        let mut values = Vec::new();
+
+        for pair in pairs {
+            let key = pair
+                .path
+                .get_ident()
+                .cloned()
+                .ok_or_else(|| syn::Error::new_spanned(&pair.path, "expected a key"))?;
+            if !allowed.iter().any(|allowed| key == allowed) {
+                return Err(syn::Error::new_spanned(
+                    &key,
+                    format!("unknown key `{}`, expected one of: {}", key, allowed.join(", ")),
+                ));
+            }
+            values.push((key, string_literal(&pair.value)?));
+        }
+
+        Ok(Self { values })
+    }
+
+    /// Get the value for a key, if present
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.values
+            .iter()
+            .find(|(ident, _)| ident == key)
+            .map(|(_, value)| value.value())
+    }
+}
+
+fn string_literal(expr: &Expr) -> syn::Result<LitStr> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok(lit.clone()),
+        _ => Err(syn::Error::new_spanned(expr, "expected a string literal")),
+    }
+}
+
+/// Collect the doc comment on an item, if any
+pub fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit), ..
+                    }),
+                ..
+            }) => Some(lit.value().trim().to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let doc = lines.join("\n").trim().to_string();
+    if doc.is_empty() {
+        None
+    } else {
+        Some(doc)
+    }
+}
+
+/// A parameter of an annotated function
+pub struct Param {
+    pub ident: Ident,
+    pub ty: Type,
+    pub description: Option<String>,
+}
+
+/// Extract the parameters of an annotated function, removing any `#[arg(...)]` attributes
+pub fn take_params(item: &mut ItemFn) -> syn::Result<Vec<Param>> {
+    if !item.sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.sig.generics,
+            "generic functions are not supported",
+        ));
+    }
+
+    let mut params = Vec::new();
+    for input in item.sig.inputs.iter_mut() {
+        let input = match input {
+            FnArg::Typed(input) => input,
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "methods are not supported, use a free function",
+                ))
+            }
+        };
+
+        let ident = match input.pat.as_ref() {
+            Pat::Ident(pat) => pat.ident.clone(),
+            pat => {
+                return Err(syn::Error::new_spanned(
+                    pat,
+                    "parameters must be plain identifiers",
+                ))
+            }
+        };
+
+        let mut description = None;
+        let mut error = None;
+        input.attrs.retain(|attr| {
+            if !attr.path().is_ident("arg") {
+                return true;
+            }
+            match attr
+                .meta
+                .require_list()
+                .and_then(|list| MacroArgs::parse(list.tokens.clone(), &["description"]))
+            {
+                Ok(args) => description = args.get("description"),
+                Err(err) => error = Some(err),
+            }
+            false
+        });
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        params.push(Param {
+            ident,
+            ty: (*input.ty).clone(),
+            description,
+        });
+    }
+
+    Ok(params)
+}
+
+/// If `ty` is `Option<T>`, return `T`
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// Tokens for an `Option<&str>` literal
+pub fn optional_str(value: &Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+/// Reject `async fn` for macros whose handlers are synchronous
+pub fn require_sync(item: &ItemFn, macro_name: &str) -> syn::Result<()> {
+    match &item.sig.asyncness {
+        Some(asyncness) => Err(syn::Error::new_spanned(
+            asyncness,
+            format!("`#[{}]` does not support async functions", macro_name),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Error for a missing required key
+pub fn missing_key(key: &str, macro_name: &str) -> syn::Error {
+    syn::Error::new(
+        Span::call_site(),
+        format!("`#[{}]` requires `{} = \"...\"`", macro_name, key),
+    )
+}
@@ -0,0 +1,83 @@
+// mcp-macros/src/lib.rs
+//! Attribute macros for declaring MCP tools, prompts and resources.
+//!
+//! Each macro leaves the annotated function untouched and generates a companion constructor
+//! (`<name>_tool`, `<name>_prompt` or `<name>_resource`) returning a definition that can be
+//! passed to `ServerBuilder::with_*_definition` or the matching manager's `register_definition`.
+//! The generated code refers to `modelcontextprotocol_server`, which must be a dependency of
+//! the calling crate.
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, ItemFn};
+
+mod attr;
+mod prompt;
+mod resource;
+mod tool;
+
+/// Declare a tool
+///
+/// ```ignore
+/// #[mcp_tool(description = "Search the index")]
+/// async fn search(
+///     #[arg(description = "Text to search for")] query: String,
+///     limit: Option<u32>,
+/// ) -> Result<ToolCallResult> {
+///     // ...
+/// }
+///
+/// let server = ServerBuilder::new("search", "0.1.0").with_tool_definition(search_tool());
+/// ```
+///
+/// Parameters become properties of the generated input schema; `Option` parameters are optional.
+/// Supported attribute keys are `name` (defaults to the function name), `title` and
+/// `description` (defaults to the function's doc comment). The function may be sync or async.
+#[proc_macro_attribute]
+pub fn mcp_tool(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemFn);
+    tool::expand(args.into(), item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Declare a prompt
+///
+/// ```ignore
+/// #[mcp_prompt(description = "Review a piece of code")]
+/// fn code_review(code: String, language: Option<String>) -> Result<Vec<PromptMessage>> {
+///     // ...
+/// }
+///
+/// let server = ServerBuilder::new("review", "0.1.0").with_prompt_definition(code_review_prompt());
+/// ```
+///
+/// Parameters become prompt arguments and are parsed from their string values with `FromStr`;
+/// `Option` parameters are optional. Supported attribute keys are `name`, `title` and
+/// `description`.
+#[proc_macro_attribute]
+pub fn mcp_prompt(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemFn);
+    prompt::expand(args.into(), item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Declare a resource
+///
+/// ```ignore
+/// #[mcp_resource(uri = "file:///readme.md", mime_type = "text/markdown")]
+/// fn readme() -> Result<Vec<ResourceContent>> {
+///     // ...
+/// }
+///
+/// let server = ServerBuilder::new("docs", "0.1.0").with_resource_definition(readme_resource());
+/// ```
+///
+/// `uri` is required; `name` (defaults to the function name), `title`, `description` and
+/// `mime_type` are optional.
+#[proc_macro_attribute]
+pub fn mcp_resource(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemFn);
+    resource::expand(args.into(), item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
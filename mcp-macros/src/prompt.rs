@@ -0,0 +1,72 @@
+// mcp-macros/src/prompt.rs
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ItemFn;
+
+use crate::attr::{doc_comment, option_inner, optional_str, require_sync, take_params, MacroArgs};
+
+pub fn expand(args: TokenStream, mut item: ItemFn) -> syn::Result<TokenStream> {
+    let args = MacroArgs::parse(args, &["name", "title", "description"])?;
+    require_sync(&item, "mcp_prompt")?;
+    let params = take_params(&mut item)?;
+
+    let fn_ident = &item.sig.ident;
+    let vis = &item.vis;
+    let constructor = format_ident!("{}_prompt", fn_ident);
+    let name = args.get("name").unwrap_or_else(|| fn_ident.to_string());
+    let description = optional_str(&args.get("description").or_else(|| doc_comment(&item.attrs)));
+    let title = args.get("title").map(|title| quote!(.with_title(#title)));
+
+    let arguments = params.iter().map(|param| {
+        let arg_name = param.ident.to_string();
+        let required = option_inner(&param.ty).is_none();
+        let description = match &param.description {
+            Some(description) => quote!(::core::option::Option::Some(#description.to_string())),
+            None => quote!(::core::option::Option::None),
+        };
+        quote! {
+            ::modelcontextprotocol_server::mcp_protocol::types::prompt::PromptArgument {
+                name: #arg_name.to_string(),
+                title: ::core::option::Option::None,
+                description: #description,
+                required: ::core::option::Option::Some(#required),
+            }
+        }
+    });
+
+    let extractions = params.iter().map(|param| {
+        let ident = &param.ident;
+        let ty = &param.ty;
+        let arg_name = ident.to_string();
+        let parse = if option_inner(ty).is_some() {
+            quote!(::modelcontextprotocol_server::prompts::parse_argument(&arguments, #arg_name)?)
+        } else {
+            quote!(::modelcontextprotocol_server::prompts::require_argument(&arguments, #arg_name)?)
+        };
+        quote!(let #ident: #ty = #parse;)
+    });
+    let idents = params.iter().map(|param| &param.ident);
+
+    let doc = format!("Definition of the `{}` prompt, generated by `#[mcp_prompt]`", name);
+
+    Ok(quote! {
+        #item
+
+        #[doc = #doc]
+        // `Ok(f()?)` converts the function's error type into `anyhow::Error`
+        #[allow(clippy::needless_question_mark)]
+        #vis fn #constructor() -> ::modelcontextprotocol_server::prompts::PromptDefinition {
+            ::modelcontextprotocol_server::prompts::PromptDefinition::new(
+                #name,
+                #description,
+                ::std::vec![#(#arguments),*],
+                |arguments| {
+                    let arguments = arguments.unwrap_or_default();
+                    #(#extractions)*
+                    ::core::result::Result::Ok(#fn_ident(#(#idents),*)?)
+                },
+            )
+            #title
+        }
+    })
+}
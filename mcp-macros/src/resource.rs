@@ -0,0 +1,46 @@
+// mcp-macros/src/resource.rs
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ItemFn;
+
+use crate::attr::{doc_comment, missing_key, optional_str, require_sync, MacroArgs};
+
+pub fn expand(args: TokenStream, item: ItemFn) -> syn::Result<TokenStream> {
+    let args = MacroArgs::parse(args, &["uri", "name", "title", "description", "mime_type"])?;
+    require_sync(&item, "mcp_resource")?;
+    if !item.sig.inputs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.sig.inputs,
+            "`#[mcp_resource]` functions take no parameters",
+        ));
+    }
+
+    let fn_ident = &item.sig.ident;
+    let vis = &item.vis;
+    let constructor = format_ident!("{}_resource", fn_ident);
+    let uri = args.get("uri").ok_or_else(|| missing_key("uri", "mcp_resource"))?;
+    let name = args.get("name").unwrap_or_else(|| fn_ident.to_string());
+    let description = optional_str(&args.get("description").or_else(|| doc_comment(&item.attrs)));
+    let mime_type = optional_str(&args.get("mime_type"));
+    let title = args.get("title").map(|title| quote!(.with_title(#title)));
+
+    let doc = format!("Definition of the `{}` resource, generated by `#[mcp_resource]`", uri);
+
+    Ok(quote! {
+        #item
+
+        #[doc = #doc]
+        // `Ok(f()?)` converts the function's error type into `anyhow::Error`
+        #[allow(clippy::needless_question_mark)]
+        #vis fn #constructor() -> ::modelcontextprotocol_server::resources::ResourceDefinition {
+            ::modelcontextprotocol_server::resources::ResourceDefinition::new(
+                #uri,
+                #name,
+                #description,
+                #mime_type,
+                || ::core::result::Result::Ok(#fn_ident()?),
+            )
+            #title
+        }
+    })
+}
@@ -0,0 +1,64 @@
+// mcp-macros/src/tool.rs
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ItemFn;
+
+use crate::attr::{doc_comment, optional_str, take_params, MacroArgs};
+
+pub fn expand(args: TokenStream, mut item: ItemFn) -> syn::Result<TokenStream> {
+    let args = MacroArgs::parse(args, &["name", "title", "description"])?;
+    let params = take_params(&mut item)?;
+
+    let fn_ident = &item.sig.ident;
+    let vis = &item.vis;
+    let constructor = format_ident!("{}_tool", fn_ident);
+    let name = args.get("name").unwrap_or_else(|| fn_ident.to_string());
+    let description = optional_str(&args.get("description").or_else(|| doc_comment(&item.attrs)));
+    let title = args.get("title").map(|title| quote!(.with_title(#title)));
+
+    let fields = params.iter().map(|param| {
+        let ident = &param.ident;
+        let ty = &param.ty;
+        let description = param
+            .description
+            .as_ref()
+            .map(|description| quote!(#[schemars(description = #description)]));
+        quote! {
+            #description
+            #ident: #ty
+        }
+    });
+    let idents: Vec<_> = params.iter().map(|param| &param.ident).collect();
+
+    let call = if item.sig.asyncness.is_some() {
+        quote!(async move { #fn_ident(#(args.#idents),*).await })
+    } else {
+        quote!(::core::future::ready(#fn_ident(#(args.#idents),*)))
+    };
+
+    let doc = format!("Definition of the `{}` tool, generated by `#[mcp_tool]`", name);
+
+    Ok(quote! {
+        #item
+
+        #[doc = #doc]
+        #vis fn #constructor() -> ::modelcontextprotocol_server::tools::ToolDefinition {
+            #[derive(
+                ::modelcontextprotocol_server::serde::Deserialize,
+                ::modelcontextprotocol_server::schemars::JsonSchema
+            )]
+            #[serde(crate = "::modelcontextprotocol_server::serde")]
+            #[schemars(crate = "::modelcontextprotocol_server::schemars")]
+            struct Args {
+                #(#fields),*
+            }
+
+            ::modelcontextprotocol_server::tools::ToolDefinition::typed(
+                #name,
+                #description,
+                |args: Args| #call,
+            )
+            #title
+        }
+    })
+}
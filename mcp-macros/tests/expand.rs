@@ -0,0 +1,221 @@
+// mcp-macros/tests/expand.rs
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use futures::TryStreamExt;
+use serde_json::json;
+
+use modelcontextprotocol_server::context::RequestContext;
+use modelcontextprotocol_server::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_server::mcp_protocol::types::prompt::PromptMessage;
+use modelcontextprotocol_server::mcp_protocol::types::resource::ResourceContent;
+use modelcontextprotocol_server::mcp_protocol::types::tool::ToolCallResult;
+use modelcontextprotocol_server::{mcp_prompt, mcp_resource, mcp_tool};
+
+fn text(text: impl Into<String>) -> ToolCallResult {
+    ToolCallResult {
+        content: vec![ContentBlock::text(text)],
+        is_error: None,
+        structured_content: None,
+        meta: None,
+    }
+}
+
+/// The text of the first content block
+fn first_text(result: &ToolCallResult) -> &str {
+    match &result.content[0] {
+        ContentBlock::Text(content) => &content.text,
+        other => panic!("expected text, got {:?}", other),
+    }
+}
+
+/// Add two numbers
+///
+/// Overflow wraps around.
+#[mcp_tool]
+fn add(
+    #[arg(description = "The first number")] a: i64,
+    b: i64,
+    #[arg(description = "Wrap instead of failing")] wrapping: Option<bool>,
+) -> Result<ToolCallResult> {
+    match (a.checked_add(b), wrapping.unwrap_or(false)) {
+        (Some(sum), _) => Ok(text(sum.to_string())),
+        (None, true) => Ok(text(a.wrapping_add(b).to_string())),
+        (None, false) => bail!("overflow"),
+    }
+}
+
+#[mcp_tool(name = "echo", title = "Echo", description = "Repeat the input")]
+async fn echo_back(input: String) -> Result<ToolCallResult> {
+    tokio::task::yield_now().await;
+    Ok(text(input))
+}
+
+#[mcp_tool]
+fn undocumented() -> Result<ToolCallResult> {
+    Ok(text("ok"))
+}
+
+/// Greet someone
+#[mcp_prompt(title = "Greeting")]
+fn greet(
+    #[arg(description = "Who to greet")] name: String,
+    times: Option<u32>,
+) -> Result<Vec<PromptMessage>> {
+    Ok(vec![PromptMessage {
+        role: "user".to_string(),
+        content: ContentBlock::text(
+            format!("Hello, {}!", name).repeat(times.unwrap_or(1) as usize),
+        ),
+    }])
+}
+
+/// Release notes
+#[mcp_resource(uri = "docs:///changelog", mime_type = "text/markdown")]
+fn changelog() -> Result<Vec<ResourceContent>> {
+    Ok(vec![ResourceContent {
+        uri: "docs:///changelog".to_string(),
+        mime_type: "text/markdown".to_string(),
+        text: Some("# 0.1.0".to_string()),
+        blob: None,
+        meta: None,
+    }])
+}
+
+#[mcp_resource(
+    uri = "docs:///index",
+    name = "Index",
+    title = "Document Index",
+    description = "Every document"
+)]
+fn index() -> Result<Vec<ResourceContent>> {
+    Ok(Vec::new())
+}
+
+#[test]
+fn tool_definitions_describe_their_parameters() {
+    let definition = add_tool();
+    let tool = &definition.tool;
+    assert_eq!(tool.name, "add");
+    assert_eq!(tool.title, None);
+    assert_eq!(
+        tool.description.as_deref(),
+        Some("Add two numbers\n\nOverflow wraps around.")
+    );
+
+    let schema = &tool.input_schema;
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["a"]["type"], "integer");
+    assert_eq!(schema["properties"]["a"]["description"], "The first number");
+    assert!(schema["properties"]["b"].get("description").is_none());
+    assert_eq!(
+        schema["properties"]["wrapping"]["description"],
+        "Wrap instead of failing"
+    );
+    // `Option` parameters are not required
+    assert_eq!(schema["required"], json!(["a", "b"]));
+}
+
+#[test]
+fn tool_attribute_keys_override_the_defaults() {
+    let tool = echo_back_tool().tool;
+    assert_eq!(tool.name, "echo");
+    assert_eq!(tool.title.as_deref(), Some("Echo"));
+    assert_eq!(tool.description.as_deref(), Some("Repeat the input"));
+
+    let tool = undocumented_tool().tool;
+    assert_eq!(tool.name, "undocumented");
+    assert_eq!(tool.description, None);
+}
+
+#[tokio::test]
+async fn tool_handlers_call_sync_and_async_functions() {
+    let add = add_tool().handler;
+    let result = add(json!({"a": 2, "b": 3}), None).await.unwrap();
+    assert_eq!(first_text(&result), "5");
+
+    let result = add(json!({"a": i64::MAX, "b": 1, "wrapping": true}), None)
+        .await
+        .unwrap();
+    assert_eq!(first_text(&result), i64::MIN.to_string());
+
+    let error = add(json!({"a": i64::MAX, "b": 1}), None).await.unwrap_err();
+    assert_eq!(error.to_string(), "overflow");
+
+    let echo = echo_back_tool().handler;
+    let result = echo(json!({"input": "hi"}), None).await.unwrap();
+    assert_eq!(first_text(&result), "hi");
+}
+
+#[test]
+fn prompt_definitions_describe_their_arguments() {
+    let prompt = greet_prompt().prompt;
+    assert_eq!(prompt.name, "greet");
+    assert_eq!(prompt.title.as_deref(), Some("Greeting"));
+    assert_eq!(prompt.description.as_deref(), Some("Greet someone"));
+
+    let arguments = prompt.arguments.unwrap();
+    assert_eq!(arguments.len(), 2);
+    assert_eq!(arguments[0].name, "name");
+    assert_eq!(arguments[0].description.as_deref(), Some("Who to greet"));
+    assert_eq!(arguments[0].required, Some(true));
+    assert_eq!(arguments[1].name, "times");
+    assert_eq!(arguments[1].description, None);
+    assert_eq!(arguments[1].required, Some(false));
+}
+
+#[test]
+fn prompt_handlers_parse_their_arguments() {
+    let handler = greet_prompt().handler;
+    let arguments = |pairs: &[(&str, &str)]| {
+        Some(
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>(),
+        )
+    };
+
+    let messages = handler(arguments(&[("name", "Ada"), ("times", "2")]), None).unwrap();
+    match &messages[0].content {
+        ContentBlock::Text(content) => assert_eq!(content.text, "Hello, Ada!Hello, Ada!"),
+        other => panic!("expected text, got {:?}", other),
+    }
+
+    let error = handler(arguments(&[("times", "2")]), None).unwrap_err();
+    assert_eq!(error.to_string(), "Missing required argument: name");
+
+    let error = handler(arguments(&[("name", "Ada"), ("times", "many")]), None).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("Invalid value for argument times"));
+}
+
+#[tokio::test]
+async fn resource_definitions_serve_the_function_contents() {
+    let definition = changelog_resource();
+    let resource = &definition.resource;
+    assert_eq!(resource.uri, "docs:///changelog");
+    assert_eq!(resource.name, "changelog");
+    assert_eq!(resource.description.as_deref(), Some("Release notes"));
+    assert_eq!(resource.mime_type.as_deref(), Some("text/markdown"));
+
+    let stream = (definition.content_provider)(RequestContext::new("docs:///changelog"))
+        .await
+        .unwrap();
+    let contents: Vec<ResourceContent> = stream.try_collect().await.unwrap();
+    assert_eq!(contents.len(), 1);
+    assert_eq!(contents[0].text.as_deref(), Some("# 0.1.0"));
+
+    let resource = index_resource().resource;
+    assert_eq!(resource.name, "Index");
+    assert_eq!(resource.title.as_deref(), Some("Document Index"));
+    assert_eq!(resource.description.as_deref(), Some("Every document"));
+    assert_eq!(resource.mime_type, None);
+}
+
+#[test]
+fn invalid_attributes_fail_to_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,10 @@
+use modelcontextprotocol_server::mcp_prompt;
+
+#[mcp_prompt]
+async fn review(
+    code: String,
+) -> anyhow::Result<Vec<modelcontextprotocol_server::mcp_protocol::types::prompt::PromptMessage>> {
+    unimplemented!("{}", code)
+}
+
+fn main() {}
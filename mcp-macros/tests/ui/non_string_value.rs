@@ -0,0 +1,10 @@
+use modelcontextprotocol_server::mcp_prompt;
+
+#[mcp_prompt(title = 42)]
+fn review(
+    code: String,
+) -> anyhow::Result<Vec<modelcontextprotocol_server::mcp_protocol::types::prompt::PromptMessage>> {
+    unimplemented!("{}", code)
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use modelcontextprotocol_server::mcp_tool;
+
+#[mcp_tool]
+fn add(
+    #[arg(help = "The first number")] a: i64,
+    b: i64,
+) -> anyhow::Result<modelcontextprotocol_server::mcp_protocol::types::tool::ToolCallResult> {
+    unimplemented!("{} {}", a, b)
+}
+
+fn main() {}
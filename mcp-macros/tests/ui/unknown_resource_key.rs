@@ -0,0 +1,10 @@
+use modelcontextprotocol_server::mcp_resource;
+
+#[mcp_resource(name = "Changelog", mime = "text/markdown")]
+fn changelog(
+) -> anyhow::Result<Vec<modelcontextprotocol_server::mcp_protocol::types::resource::ResourceContent>>
+{
+    Ok(Vec::new())
+}
+
+fn main() {}
@@ -3,12 +3,18 @@
 /// The current protocol version
 pub const PROTOCOL_VERSION: &str = "2025-06-18";
 
+/// Every protocol version this crate can speak, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[PROTOCOL_VERSION];
+
 /// JSON-RPC method names
 pub mod methods {
     // Lifecycle methods
     pub const INITIALIZE: &str = "initialize";
     pub const INITIALIZED: &str = "notifications/initialized";
 
+    // Cancellation notifications
+    pub const CANCELLED: &str = "notifications/cancelled";
+
     // Tool methods
     pub const TOOLS_LIST: &str = "tools/list";
     pub const TOOLS_CALL: &str = "tools/call";
@@ -2,9 +2,10 @@
 pub mod constants;
 pub mod messages;
 pub mod types;
+pub mod uri_template;
 pub mod version;
 
 // Re-export commonly used items
 pub use constants::PROTOCOL_VERSION;
-pub use messages::JsonRpcMessage;
+pub use messages::{JsonRpcMessage, RequestId};
 pub use types::*;
@@ -1,88 +1,243 @@
 // mcp-protocol/src/messages/base.rs
+use std::fmt;
+
+use serde::de::{self, Deserializer};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The only JSON-RPC version MCP speaks
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Identifier of a JSON-RPC request, either a string or an integer
+///
+/// `1` and `"1"` are different ids.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RequestId {
+    Number(i64),
+    String(String),
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestId::Number(n) => write!(f, "{}", n),
+            RequestId::String(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+impl From<i64> for RequestId {
+    fn from(id: i64) -> Self {
+        RequestId::Number(id)
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(id: String) -> Self {
+        RequestId::String(id)
+    }
+}
 
-/// JSON-RPC 2.0 error structure
+impl From<&str> for RequestId {
+    fn from(id: &str) -> Self {
+        RequestId::String(id.to_string())
+    }
+}
+
+/// Error object carried by a [`JsonRpcError`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct JsonRpcError {
+pub struct JsonRpcErrorObject {
     pub code: i32,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<serde_json::Value>,
+    pub data: Option<Value>,
+}
+
+impl JsonRpcErrorObject {
+    /// Create an error object without data
+    pub fn new(code: i32, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+
+    /// Attach data to the error
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
+/// A request that expects a response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+    #[serde(deserialize_with = "deserialize_version")]
+    pub jsonrpc: String,
+    pub id: RequestId,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// A successful response to a request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    #[serde(deserialize_with = "deserialize_version")]
+    pub jsonrpc: String,
+    pub id: RequestId,
+    pub result: Value,
+}
+
+/// A response reporting that a request failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    #[serde(deserialize_with = "deserialize_version")]
+    pub jsonrpc: String,
+    pub id: RequestId,
+    pub error: JsonRpcErrorObject,
 }
 
-/// Represents a JSON-RPC 2.0 message (request, response, or notification)
+/// A one-way message with no response
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    #[serde(deserialize_with = "deserialize_version")]
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+fn deserialize_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let version = String::deserialize(deserializer)?;
+    if version != JSONRPC_VERSION {
+        return Err(de::Error::custom(format!(
+            "unsupported JSON-RPC version {:?}, expected \"2.0\"",
+            version
+        )));
+    }
+    Ok(version)
+}
+
+/// Represents a JSON-RPC 2.0 message (request, response, error or notification)
+///
+/// Messages are told apart by their members rather than by trying each shape in turn: a
+/// `method` with an `id` is a request, a `method` without one a notification, an `error` an
+/// error response and a `result` (even `null`) a successful response.
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub enum JsonRpcMessage {
     /// A request from client to server or vice versa
-    Request {
-        jsonrpc: String,
-        id: serde_json::Value,
-        method: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        params: Option<serde_json::Value>,
-    },
-    
-    /// A response to a request
-    Response {
-        jsonrpc: String,
-        id: serde_json::Value,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        result: Option<serde_json::Value>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        error: Option<JsonRpcError>,
-    },
-    
+    Request(JsonRpcRequest),
+
+    /// A successful response to a request
+    Response(JsonRpcResponse),
+
+    /// An error response to a request
+    Error(JsonRpcError),
+
     /// A notification (one-way message with no response)
-    Notification {
-        jsonrpc: String,
-        method: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        params: Option<serde_json::Value>,
-    },
+    Notification(JsonRpcNotification),
+}
+
+impl<'de> Deserialize<'de> for JsonRpcMessage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let object = Map::<String, Value>::deserialize(deserializer)?;
+        let is_request = object.contains_key("method");
+        let has_id = object.contains_key("id");
+        let has_result = object.contains_key("result");
+        let has_error = object.contains_key("error");
+
+        let value = Value::Object(object);
+        let message = match (is_request, has_id, has_result, has_error) {
+            (true, true, false, false) => {
+                serde_json::from_value(value).map(JsonRpcMessage::Request)
+            }
+            (true, false, false, false) => {
+                serde_json::from_value(value).map(JsonRpcMessage::Notification)
+            }
+            (false, _, true, false) => serde_json::from_value(value).map(JsonRpcMessage::Response),
+            (false, _, false, true) => serde_json::from_value(value).map(JsonRpcMessage::Error),
+            (false, _, true, true) => {
+                return Err(de::Error::custom(
+                    "JSON-RPC response has both a result and an error",
+                ))
+            }
+            (true, _, _, _) => {
+                return Err(de::Error::custom(
+                    "JSON-RPC request has a result or an error",
+                ))
+            }
+            (false, _, false, false) => {
+                return Err(de::Error::custom(
+                    "not a JSON-RPC message: no method, result or error",
+                ))
+            }
+        };
+        message.map_err(de::Error::custom)
+    }
 }
 
 impl JsonRpcMessage {
     /// Create a new request
-    pub fn request(id: serde_json::Value, method: &str, params: Option<serde_json::Value>) -> Self {
-        JsonRpcMessage::Request {
-            jsonrpc: "2.0".to_string(),
-            id,
+    pub fn request(id: impl Into<RequestId>, method: &str, params: Option<Value>) -> Self {
+        JsonRpcMessage::Request(JsonRpcRequest {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: id.into(),
             method: method.to_string(),
             params,
-        }
+        })
     }
-    
+
     /// Create a new response with a result
-    pub fn response(id: serde_json::Value, result: serde_json::Value) -> Self {
-        JsonRpcMessage::Response {
-            jsonrpc: "2.0".to_string(),
-            id,
-            result: Some(result),
-            error: None,
-        }
+    pub fn response(id: impl Into<RequestId>, result: Value) -> Self {
+        JsonRpcMessage::Response(JsonRpcResponse {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: id.into(),
+            result,
+        })
     }
-    
+
     /// Create a new error response
-    pub fn error(id: serde_json::Value, code: i32, message: &str, data: Option<serde_json::Value>) -> Self {
-        JsonRpcMessage::Response {
-            jsonrpc: "2.0".to_string(),
-            id,
-            result: None,
-            error: Some(JsonRpcError {
+    pub fn error(id: impl Into<RequestId>, code: i32, message: &str, data: Option<Value>) -> Self {
+        JsonRpcMessage::Error(JsonRpcError {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: id.into(),
+            error: JsonRpcErrorObject {
                 code,
                 message: message.to_string(),
                 data,
-            }),
-        }
+            },
+        })
     }
-    
+
     /// Create a new notification
-    pub fn notification(method: &str, params: Option<serde_json::Value>) -> Self {
-        JsonRpcMessage::Notification {
-            jsonrpc: "2.0".to_string(),
+    pub fn notification(method: &str, params: Option<Value>) -> Self {
+        JsonRpcMessage::Notification(JsonRpcNotification {
+            jsonrpc: JSONRPC_VERSION.to_string(),
             method: method.to_string(),
             params,
+        })
+    }
+
+    /// The request id of a request or response; None for notifications
+    pub fn id(&self) -> Option<&RequestId> {
+        match self {
+            JsonRpcMessage::Request(request) => Some(&request.id),
+            JsonRpcMessage::Response(response) => Some(&response.id),
+            JsonRpcMessage::Error(error) => Some(&error.id),
+            JsonRpcMessage::Notification(_) => None,
+        }
+    }
+
+    /// The method of a request or notification; None for responses
+    pub fn method(&self) -> Option<&str> {
+        match self {
+            JsonRpcMessage::Request(request) => Some(&request.method),
+            JsonRpcMessage::Notification(notification) => Some(&notification.method),
+            _ => None,
         }
     }
 }
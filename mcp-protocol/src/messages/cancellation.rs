@@ -0,0 +1,21 @@
+// mcp-protocol/src/messages/cancellation.rs
+use serde::{Deserialize, Serialize};
+
+use super::{Meta, RequestId};
+
+/// Parameters of the `notifications/cancelled` notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelledParams {
+    /// Id of the request being cancelled
+    #[serde(rename = "requestId")]
+    pub request_id: RequestId,
+
+    /// Optional reason for the cancellation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Notification metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
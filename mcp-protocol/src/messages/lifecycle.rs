@@ -2,6 +2,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::Meta;
 use crate::types::{ClientInfo, ServerInfo};
 
 /// Client capabilities negotiated during initialization
@@ -13,6 +14,9 @@ pub struct ClientCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sampling: Option<HashMap<String, serde_json::Value>>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elicitation: Option<HashMap<String, serde_json::Value>>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub experimental: Option<HashMap<String, serde_json::Value>>,
 }
@@ -23,6 +27,9 @@ pub struct ServerCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logging: Option<HashMap<String, serde_json::Value>>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completions: Option<HashMap<String, serde_json::Value>>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompts: Option<HashMap<String, bool>>,
     
@@ -46,6 +53,11 @@ pub struct InitializeParams {
     
     #[serde(rename = "clientInfo")]
     pub client_info: ClientInfo,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of the initialize request
@@ -61,4 +73,9 @@ pub struct InitializeResult {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instructions: Option<String>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
@@ -0,0 +1,55 @@
+// mcp-protocol/src/messages/meta.rs
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::RequestId;
+
+/// Token a request carries so the receiver can report progress on it; a string or an integer
+pub type ProgressToken = RequestId;
+
+/// The `_meta` object of a request, result or notification
+///
+/// Keys other than `progressToken` (trace ids, vendor metadata, ...) are kept in `extra` so they
+/// survive a round-trip unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    /// Token to send progress notifications for this request with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_token: Option<ProgressToken>,
+
+    /// Every other key
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Meta {
+    /// Create an empty `_meta` object
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the progress token
+    pub fn with_progress_token(mut self, token: impl Into<ProgressToken>) -> Self {
+        self.progress_token = Some(token.into());
+        self
+    }
+
+    /// Set any other key, e.g. a vendor-prefixed one like `example.com/trace-id`
+    pub fn with(mut self, key: &str, value: Value) -> Self {
+        self.extra.insert(key.to_string(), value);
+        self
+    }
+
+    /// Look up a key other than `progressToken`
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.extra.get(key)
+    }
+
+    /// Read the `_meta` object out of raw params or a raw result, if it has a valid one
+    pub fn from_value(value: &Value) -> Option<Self> {
+        value
+            .get("_meta")
+            .and_then(|meta| serde_json::from_value(meta.clone()).ok())
+    }
+}
@@ -2,7 +2,16 @@
 pub mod base;
 pub mod lifecycle;
 pub mod completion;
+pub mod cancellation;
+pub mod meta;
+pub mod request;
 
-pub use base::JsonRpcMessage;
+pub use base::{
+    JsonRpcError, JsonRpcErrorObject, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest,
+    JsonRpcResponse, RequestId, JSONRPC_VERSION,
+};
 pub use lifecycle::*;
 pub use completion::*;
+pub use cancellation::*;
+pub use meta::{Meta, ProgressToken};
+pub use request::*;
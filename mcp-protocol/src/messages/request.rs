@@ -0,0 +1,327 @@
+// mcp-protocol/src/messages/request.rs
+//! Typed requests and notifications, keyed by JSON-RPC method name
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    CancelledParams, InitializeParams, InitializeResult, JsonRpcMessage, JsonRpcNotification,
+    JsonRpcRequest, Meta, RequestId,
+};
+use crate::constants::methods;
+use crate::types::completion::{CompleteRequest, CompleteResponse};
+use crate::types::prompt::{
+    PromptGetParams, PromptGetResult, PromptsListParams, PromptsListResult,
+};
+use crate::types::resource::{
+    ResourceReadParams, ResourceReadResult, ResourceSubscribeParams, ResourceTemplatesListParams,
+    ResourceTemplatesListResult, ResourceUnsubscribeParams, ResourceUpdatedParams,
+    ResourcesListParams, ResourcesListResult,
+};
+use crate::types::sampling::{CreateMessageParams, CreateMessageResult};
+use crate::types::tool::{ToolCallParams, ToolCallResult, ToolsListParams, ToolsListResult};
+
+/// A request method together with the types of its params and result
+pub trait Request {
+    const METHOD: &'static str;
+    type Params: Serialize + DeserializeOwned;
+    type Result: Serialize + DeserializeOwned;
+}
+
+/// A notification method together with the type of its params
+pub trait Notification {
+    const METHOD: &'static str;
+    type Params: Serialize + DeserializeOwned;
+}
+
+/// Params of a method that takes none
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmptyParams {
+    /// Request or notification metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
+
+/// Result of a request that only reports success
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmptyResult {
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
+
+/// Why a JSON-RPC message could not be turned into a typed request or notification
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageParseError {
+    /// No request or notification with this method is known
+    MethodNotFound(String),
+    /// The params do not match the method's params type
+    InvalidParams { method: String, message: String },
+}
+
+impl fmt::Display for MessageParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageParseError::MethodNotFound(method) => write!(f, "Method not found: {}", method),
+            MessageParseError::InvalidParams { method, message } => {
+                write!(f, "Invalid {} parameters: {}", method, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MessageParseError {}
+
+/// Deserialize a method's params, treating missing params as an empty object
+fn parse_params<P: DeserializeOwned>(
+    method: &str,
+    params: &Option<Value>,
+) -> Result<P, MessageParseError> {
+    let params = params
+        .clone()
+        .unwrap_or_else(|| Value::Object(Default::default()));
+    serde_json::from_value(params).map_err(|err| MessageParseError::InvalidParams {
+        method: method.to_string(),
+        message: err.to_string(),
+    })
+}
+
+macro_rules! request {
+    ($(#[$doc:meta])* $name:ident, $method:expr, $params:ty, $result:ty) => {
+        $(#[$doc])*
+        #[derive(Debug)]
+        pub enum $name {}
+
+        impl Request for $name {
+            const METHOD: &'static str = $method;
+            type Params = $params;
+            type Result = $result;
+        }
+    };
+}
+
+macro_rules! notification {
+    ($(#[$doc:meta])* $name:ident, $method:expr, $params:ty) => {
+        $(#[$doc])*
+        #[derive(Debug)]
+        pub enum $name {}
+
+        impl Notification for $name {
+            const METHOD: &'static str = $method;
+            type Params = $params;
+        }
+    };
+}
+
+request!(
+    /// `initialize`
+    Initialize, methods::INITIALIZE, InitializeParams, InitializeResult
+);
+request!(
+    /// `tools/list`
+    ListTools, methods::TOOLS_LIST, ToolsListParams, ToolsListResult
+);
+request!(
+    /// `tools/call`
+    CallTool, methods::TOOLS_CALL, ToolCallParams, ToolCallResult
+);
+request!(
+    /// `resources/list`
+    ListResources, methods::RESOURCES_LIST, ResourcesListParams, ResourcesListResult
+);
+request!(
+    /// `resources/templates/list`
+    ListResourceTemplates,
+    methods::RESOURCES_TEMPLATES_LIST,
+    ResourceTemplatesListParams,
+    ResourceTemplatesListResult
+);
+request!(
+    /// `resources/read`
+    ReadResource, methods::RESOURCES_READ, ResourceReadParams, ResourceReadResult
+);
+request!(
+    /// `resources/subscribe`
+    Subscribe, methods::RESOURCES_SUBSCRIBE, ResourceSubscribeParams, EmptyResult
+);
+request!(
+    /// `resources/unsubscribe`
+    Unsubscribe, methods::RESOURCES_UNSUBSCRIBE, ResourceUnsubscribeParams, EmptyResult
+);
+request!(
+    /// `prompts/list`
+    ListPrompts, methods::PROMPTS_LIST, PromptsListParams, PromptsListResult
+);
+request!(
+    /// `prompts/get`
+    GetPrompt, methods::PROMPTS_GET, PromptGetParams, PromptGetResult
+);
+request!(
+    /// `completion/complete`
+    Complete, methods::COMPLETION_COMPLETE, CompleteRequest, CompleteResponse
+);
+request!(
+    /// `sampling/createMessage`
+    CreateMessage, methods::SAMPLING_CREATE_MESSAGE, CreateMessageParams, CreateMessageResult
+);
+
+notification!(
+    /// `notifications/initialized`
+    Initialized, methods::INITIALIZED, EmptyParams
+);
+notification!(
+    /// `notifications/cancelled`
+    Cancelled, methods::CANCELLED, CancelledParams
+);
+notification!(
+    /// `notifications/tools/list_changed`
+    ToolListChanged, methods::TOOLS_LIST_CHANGED, EmptyParams
+);
+notification!(
+    /// `notifications/resources/list_changed`
+    ResourceListChanged, methods::RESOURCES_LIST_CHANGED, EmptyParams
+);
+notification!(
+    /// `notifications/resources/updated`
+    ResourceUpdated, methods::RESOURCES_UPDATED, ResourceUpdatedParams
+);
+notification!(
+    /// `notifications/prompts/list_changed`
+    PromptListChanged, methods::PROMPTS_LIST_CHANGED, EmptyParams
+);
+
+/// Generate an enum with one variant per request method, holding that method's params
+macro_rules! request_enum {
+    ($(#[$doc:meta])* $name:ident { $($variant:ident),* $(,)? }) => {
+        $(#[$doc])*
+        // Parsed once per message and matched on straight away, so boxing `initialize` buys nothing
+        #[allow(clippy::large_enum_variant)]
+        #[derive(Debug, Clone)]
+        pub enum $name {
+            $($variant(<$variant as Request>::Params),)*
+        }
+
+        impl $name {
+            /// The JSON-RPC method of this request
+            pub fn method(&self) -> &'static str {
+                match self {
+                    $($name::$variant(_) => <$variant as Request>::METHOD,)*
+                }
+            }
+
+            /// Parse a JSON-RPC request by its method name
+            pub fn from_request(request: &JsonRpcRequest) -> Result<Self, MessageParseError> {
+                Self::from_method(&request.method, &request.params)
+            }
+
+            /// Parse a request's params by its method name
+            pub fn from_method(
+                method: &str,
+                params: &Option<Value>,
+            ) -> Result<Self, MessageParseError> {
+                $(
+                    if method == <$variant as Request>::METHOD {
+                        return parse_params(method, params).map($name::$variant);
+                    }
+                )*
+                Err(MessageParseError::MethodNotFound(method.to_string()))
+            }
+
+            /// Build the JSON-RPC request message for this request
+            pub fn into_message(self, id: impl Into<RequestId>) -> JsonRpcMessage {
+                let method = self.method();
+                let params = match self {
+                    $($name::$variant(params) => serde_json::to_value(params),)*
+                };
+                JsonRpcMessage::request(id, method, params.ok())
+            }
+        }
+    };
+}
+
+/// Generate an enum with one variant per notification method, holding that method's params
+macro_rules! notification_enum {
+    ($(#[$doc:meta])* $name:ident { $($variant:ident),* $(,)? }) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone)]
+        pub enum $name {
+            $($variant(<$variant as Notification>::Params),)*
+        }
+
+        impl $name {
+            /// The JSON-RPC method of this notification
+            pub fn method(&self) -> &'static str {
+                match self {
+                    $($name::$variant(_) => <$variant as Notification>::METHOD,)*
+                }
+            }
+
+            /// Parse a JSON-RPC notification by its method name
+            pub fn from_notification(
+                notification: &JsonRpcNotification,
+            ) -> Result<Self, MessageParseError> {
+                $(
+                    if notification.method == <$variant as Notification>::METHOD {
+                        return parse_params(&notification.method, &notification.params)
+                            .map($name::$variant);
+                    }
+                )*
+                Err(MessageParseError::MethodNotFound(notification.method.clone()))
+            }
+
+            /// Build the JSON-RPC notification message for this notification
+            pub fn into_message(self) -> JsonRpcMessage {
+                let method = self.method();
+                let params = match self {
+                    $($name::$variant(params) => serde_json::to_value(params),)*
+                };
+                JsonRpcMessage::notification(method, params.ok())
+            }
+        }
+    };
+}
+
+request_enum!(
+    /// Requests a client sends to a server
+    ClientRequest {
+        Initialize,
+        ListTools,
+        CallTool,
+        ListResources,
+        ListResourceTemplates,
+        ReadResource,
+        Subscribe,
+        Unsubscribe,
+        ListPrompts,
+        GetPrompt,
+        Complete,
+    }
+);
+
+request_enum!(
+    /// Requests a server sends to a client
+    ServerRequest { CreateMessage }
+);
+
+notification_enum!(
+    /// Notifications a client sends to a server
+    ClientNotification {
+        Initialized,
+        Cancelled,
+    }
+);
+
+notification_enum!(
+    /// Notifications a server sends to a client
+    ServerNotification {
+        Cancelled,
+        ToolListChanged,
+        ResourceListChanged,
+        ResourceUpdated,
+        PromptListChanged,
+    }
+);
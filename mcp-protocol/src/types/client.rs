@@ -5,5 +5,10 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientInfo {
     pub name: String,
+
+    /// Human-readable name for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
     pub version: String,
 }
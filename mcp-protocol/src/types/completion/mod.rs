@@ -1,6 +1,10 @@
 // mcp-protocol/src/types/completion/mod.rs
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::messages::Meta;
+
 /// Reference to a prompt or resource for completion
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -29,11 +33,35 @@ pub struct CompletionArgument {
     pub value: String,
 }
 
+/// Extra information for a completion request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompletionContext {
+    /// Arguments already resolved, e.g. `owner` while completing `name` in `repo/{owner}/{name}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<HashMap<String, String>>,
+}
+
+impl CompletionContext {
+    /// The resolved arguments, or an empty map if there are none
+    pub fn into_arguments(self) -> HashMap<String, String> {
+        self.arguments.unwrap_or_default()
+    }
+}
+
 /// Request for completion suggestions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompleteRequest {
     pub r#ref: CompletionReference,
     pub argument: CompletionArgument,
+
+    /// Previously-resolved arguments the suggestions may depend on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<CompletionContext>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Completion results
@@ -42,6 +70,7 @@ pub struct CompletionResult {
     pub values: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total: Option<usize>,
+    #[serde(rename = "hasMore", default)]
     pub has_more: bool,
 }
 
@@ -49,6 +78,11 @@ pub struct CompletionResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompleteResponse {
     pub completion: CompletionResult,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for the completion/complete request
@@ -60,6 +94,15 @@ pub struct CompletionCompleteParams {
     
     /// Argument being completed
     pub argument: CompletionArgument,
+
+    /// Previously-resolved arguments the suggestions may depend on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<CompletionContext>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Completion result
@@ -68,6 +111,11 @@ pub struct CompletionCompleteParams {
 pub struct CompletionCompleteResult {
     /// Completion information
     pub completion: CompletionInfo,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Completion information
@@ -82,7 +130,7 @@ pub struct CompletionInfo {
     pub total: Option<u32>,
     
     /// Flag indicating if there are more results available
-    #[serde(rename = "hasMore")]
+    #[serde(rename = "hasMore", default)]
     pub has_more: bool,
 }
 
@@ -0,0 +1,197 @@
+// mcp-protocol/src/types/content.rs
+use serde::{Deserialize, Serialize};
+
+use crate::types::resource::{Resource, ResourceContent};
+
+/// Who a piece of content is meant for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+/// Hints to the client about how content should be used or displayed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotations {
+    /// Who the content is intended for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audience: Option<Vec<Role>>,
+
+    /// Importance from 0.0 (least) to 1.0 (most)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<f64>,
+
+    /// When the content was last modified, as an ISO 8601 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+/// Text content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextContent {
+    pub text: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Annotations>,
+}
+
+/// Base64-encoded image content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageContent {
+    pub data: String,
+
+    pub mime_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Annotations>,
+}
+
+/// Base64-encoded audio content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioContent {
+    pub data: String,
+
+    pub mime_type: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Annotations>,
+}
+
+/// Contents of a resource, embedded in a message or result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedResource {
+    pub resource: ResourceContent,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Annotations>,
+}
+
+/// A link to a resource the client can read, instead of its contents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLink {
+    /// URI of the resource
+    pub uri: String,
+
+    /// Human-readable name of the resource
+    pub name: String,
+
+    /// Human-readable name for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+
+    /// Size in bytes, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Annotations>,
+}
+
+impl From<Resource> for ResourceLink {
+    fn from(resource: Resource) -> Self {
+        Self {
+            uri: resource.uri,
+            name: resource.name,
+            title: resource.title,
+            description: resource.description,
+            mime_type: resource.mime_type,
+            size: resource.size,
+            annotations: resource.annotations,
+        }
+    }
+}
+
+/// A piece of content in a tool result, prompt message or sampling message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text(TextContent),
+    Image(ImageContent),
+    Audio(AudioContent),
+    Resource(EmbeddedResource),
+    ResourceLink(ResourceLink),
+}
+
+impl ContentBlock {
+    /// Create text content
+    pub fn text(text: impl Into<String>) -> Self {
+        ContentBlock::Text(TextContent {
+            text: text.into(),
+            annotations: None,
+        })
+    }
+
+    /// Create image content from base64-encoded data
+    pub fn image(data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        ContentBlock::Image(ImageContent {
+            data: data.into(),
+            mime_type: mime_type.into(),
+            annotations: None,
+        })
+    }
+
+    /// Create audio content from base64-encoded data
+    pub fn audio(data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        ContentBlock::Audio(AudioContent {
+            data: data.into(),
+            mime_type: mime_type.into(),
+            annotations: None,
+        })
+    }
+
+    /// Embed a resource's contents
+    pub fn resource(resource: ResourceContent) -> Self {
+        ContentBlock::Resource(EmbeddedResource {
+            resource,
+            annotations: None,
+        })
+    }
+
+    /// Link to a resource
+    pub fn resource_link(link: impl Into<ResourceLink>) -> Self {
+        ContentBlock::ResourceLink(link.into())
+    }
+
+    /// Set the annotations
+    pub fn with_annotations(mut self, annotations: Annotations) -> Self {
+        let slot = match &mut self {
+            ContentBlock::Text(content) => &mut content.annotations,
+            ContentBlock::Image(content) => &mut content.annotations,
+            ContentBlock::Audio(content) => &mut content.annotations,
+            ContentBlock::Resource(content) => &mut content.annotations,
+            ContentBlock::ResourceLink(content) => &mut content.annotations,
+        };
+        *slot = Some(annotations);
+        self
+    }
+
+    /// The annotations, if any
+    pub fn annotations(&self) -> Option<&Annotations> {
+        match self {
+            ContentBlock::Text(content) => content.annotations.as_ref(),
+            ContentBlock::Image(content) => content.annotations.as_ref(),
+            ContentBlock::Audio(content) => content.annotations.as_ref(),
+            ContentBlock::Resource(content) => content.annotations.as_ref(),
+            ContentBlock::ResourceLink(content) => content.annotations.as_ref(),
+        }
+    }
+
+    /// The text, if this is text content
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            ContentBlock::Text(content) => Some(&content.text),
+            _ => None,
+        }
+    }
+}
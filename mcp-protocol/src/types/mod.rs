@@ -1,6 +1,7 @@
 // mcp-protocol/src/types/mod.rs
 mod client;
 mod server;
+pub mod content;
 pub mod tool;
 pub mod resource;
 // Using completion/mod.rs for the module structure
@@ -10,6 +11,7 @@ pub mod sampling;
 
 pub use client::*;
 pub use server::*;
+pub use content::*;
 pub use completion::*;
 pub use prompt::*;
 pub use sampling::*;
@@ -2,12 +2,19 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::messages::Meta;
+use crate::types::content::ContentBlock;
+
 /// Prompt definition provided by the server
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prompt {
     /// Unique identifier for the prompt
     pub name: String,
     
+    /// Optional human-readable name for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    
     /// Optional human-readable description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -27,6 +34,10 @@ pub struct PromptArgument {
     /// Argument name
     pub name: String,
     
+    /// Optional human-readable name for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    
     /// Optional human-readable description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -42,6 +53,11 @@ pub struct PromptsListParams {
     /// Optional cursor for pagination
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of the prompts/list request
@@ -50,9 +66,15 @@ pub struct PromptsListResult {
     /// List of available prompts
     pub prompts: Vec<Prompt>,
     
-    /// Cursor for the next page (empty if no more pages)
+    /// Optional cursor for the next page of results
     #[serde(rename = "nextCursor")]
-    pub next_cursor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for the prompts/get request
@@ -64,6 +86,11 @@ pub struct PromptGetParams {
     /// Arguments to apply to the prompt
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<HashMap<String, String>>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of the prompts/get request
@@ -75,6 +102,11 @@ pub struct PromptGetResult {
     
     /// Messages in the prompt
     pub messages: Vec<PromptMessage>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Message in a prompt
@@ -84,49 +116,7 @@ pub struct PromptMessage {
     pub role: String,
     
     /// Content of the message
-    pub content: PromptMessageContent,
-}
-
-/// Content of a prompt message
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
-pub enum PromptMessageContent {
-    /// Text content
-    #[serde(rename = "text")]
-    Text { text: String },
-    
-    /// Image content
-    #[serde(rename = "image")]
-    Image { 
-        data: String, 
-        #[serde(rename = "mimeType")]
-        mime_type: String 
-    },
-    
-    /// Resource content
-    #[serde(rename = "resource")]
-    Resource { 
-        resource: EmbeddedResource 
-    },
-}
-
-/// Embedded resource in a prompt message
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EmbeddedResource {
-    /// URI of the resource
-    pub uri: String,
-    
-    /// MIME type of the resource
-    #[serde(rename = "mimeType")]
-    pub mime_type: String,
-    
-    /// Text content (if text resource)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub text: Option<String>,
-    
-    /// Binary data content (if binary resource)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<String>,
+    pub content: ContentBlock,
 }
 
 /// Reference to a prompt for completion
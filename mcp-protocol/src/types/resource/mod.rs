@@ -1,6 +1,8 @@
 // mcp-protocol/src/types/resource/mod.rs
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+
+use crate::messages::Meta;
+use crate::types::content::Annotations;
 
 /// Represents a resource that can be accessed by the client
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +13,10 @@ pub struct Resource {
     /// Human-readable name of the resource
     pub name: String,
     
+    /// Optional human-readable name for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    
     /// Optional description of the resource
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -24,13 +30,13 @@ pub struct Resource {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<u64>,
     
-    /// Optional custom annotations
+    /// Optional hints about the intended audience, priority and freshness
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub annotations: Option<HashMap<String, serde_json::Value>>,
+    pub annotations: Option<Annotations>,
 }
 
 /// Content of a resource, which can be either text or binary data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResourceContent {
     /// URI that uniquely identifies the resource
     pub uri: String,
@@ -46,6 +52,11 @@ pub struct ResourceContent {
     /// Binary content encoded as base64 (used for binary resources)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blob: Option<String>,
+    
+    /// Content metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for listing resources
@@ -54,6 +65,11 @@ pub struct ResourcesListParams {
     /// Optional cursor for pagination
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of listing resources
@@ -66,6 +82,11 @@ pub struct ResourcesListResult {
     #[serde(rename = "nextCursor")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<String>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for reading a resource
@@ -73,6 +94,11 @@ pub struct ResourcesListResult {
 pub struct ResourceReadParams {
     /// URI of the resource to read
     pub uri: String,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of reading a resource
@@ -80,6 +106,11 @@ pub struct ResourceReadParams {
 pub struct ResourceReadResult {
     /// Contents of the resource
     pub contents: Vec<ResourceContent>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for subscribing to a resource
@@ -87,6 +118,11 @@ pub struct ResourceReadResult {
 pub struct ResourceSubscribeParams {
     /// URI of the resource to subscribe to
     pub uri: String,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for a resource update notification
@@ -94,6 +130,11 @@ pub struct ResourceSubscribeParams {
 pub struct ResourceUpdatedParams {
     /// URI of the updated resource
     pub uri: String,
+
+    /// Notification metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Resource template that can be parameterized
@@ -106,6 +147,10 @@ pub struct ResourceTemplate {
     /// Human-readable name of the template
     pub name: String,
     
+    /// Optional human-readable name for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    
     /// Optional description of the template
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -115,9 +160,9 @@ pub struct ResourceTemplate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
     
-    /// Optional custom annotations
+    /// Optional hints about the intended audience, priority and freshness
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub annotations: Option<HashMap<String, serde_json::Value>>,
+    pub annotations: Option<Annotations>,
 }
 
 /// Parameters for listing resource templates
@@ -126,6 +171,11 @@ pub struct ResourceTemplatesListParams {
     /// Optional cursor for pagination
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of listing resource templates
@@ -139,6 +189,11 @@ pub struct ResourceTemplatesListResult {
     #[serde(rename = "nextCursor")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<String>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for template parameter completion - DEPRECATED in favor of the general completion API
@@ -155,6 +210,11 @@ pub struct ResourceTemplateCompletionParams {
     /// Current value of the parameter (for contextual completion)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of template parameter completion - DEPRECATED in favor of the general completion API
@@ -163,6 +223,11 @@ pub struct ResourceTemplateCompletionParams {
 pub struct ResourceTemplateCompletionResult {
     /// List of completion suggestions
     pub items: Vec<super::completion::CompletionItem>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for unsubscribing from a resource
@@ -170,4 +235,9 @@ pub struct ResourceTemplateCompletionResult {
 pub struct ResourceUnsubscribeParams {
     /// URI of the resource to unsubscribe from
     pub uri: String,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
@@ -2,26 +2,14 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// Sampling message content types
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
-pub enum MessageContent {
-    #[serde(rename = "text")]
-    Text {
-        text: String,
-    },
-    #[serde(rename = "image")]
-    Image {
-        data: String,
-        mime_type: String,
-    },
-}
+use crate::messages::Meta;
+use crate::types::content::ContentBlock;
 
 /// Message role in a conversation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub role: String,
-    pub content: MessageContent,
+    pub content: ContentBlock,
 }
 
 /// Model hint for sampling
@@ -32,6 +20,7 @@ pub struct ModelHint {
 
 /// Model preferences for sampling
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ModelPreferences {
     /// Hints for specific models or model families
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -52,6 +41,7 @@ pub struct ModelPreferences {
 
 /// Params for creating a sampling message
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateMessageParams {
     /// The conversation messages to include
     pub messages: Vec<Message>,
@@ -64,6 +54,10 @@ pub struct CreateMessageParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_prompt: Option<String>,
     
+    /// Which servers' context to include: "none", "thisServer" or "allServers"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_context: Option<String>,
+    
     /// Maximum tokens to generate
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
@@ -76,19 +70,33 @@ pub struct CreateMessageParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
     
+    /// Sequences that stop generation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    
+    /// Provider-specific metadata passed through to the LLM provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    
     /// Optional sampling context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<HashMap<String, String>>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Response for a sampling message creation
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateMessageResult {
     /// The role of the response message
     pub role: String,
     
     /// The content of the response
-    pub content: MessageContent,
+    pub content: ContentBlock,
     
     /// The model used for generation
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -101,4 +109,9 @@ pub struct CreateMessageResult {
     /// Optional metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
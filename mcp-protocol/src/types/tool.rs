@@ -1,20 +1,96 @@
 // mcp-protocol/src/types/tool.rs
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+
+use crate::messages::Meta;
+use crate::types::content::ContentBlock;
 
 /// Definition of a tool that can be called by the client
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tool {
     pub name: String,
 
+    /// Human-readable name for display; `name` is what the tool is called by
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
-    #[cfg_attr(feature = "camel_case", serde(rename = "inputSchema"))]
+    #[serde(rename = "inputSchema")]
     pub input_schema: serde_json::Value,
 
+    /// Optional JSON Schema describing the tool's structured output
+    #[serde(rename = "outputSchema")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+impl Tool {
+    /// Whether the tool may perform destructive updates, per its annotations
+    ///
+    /// Tools without annotations are treated as destructive, matching the spec's defaults.
+    pub fn is_destructive(&self) -> bool {
+        self.annotations
+            .as_ref()
+            .map(|annotations| annotations.is_destructive())
+            .unwrap_or(true)
+    }
+}
+
+/// Hints describing a tool's behavior
+///
+/// These are hints only; clients should not rely on them for tools from untrusted servers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolAnnotations {
+    /// Human-readable title for the tool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// The tool does not modify its environment (default: false)
+    #[serde(rename = "readOnlyHint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+
+    /// The tool may perform destructive updates; only meaningful when not read-only (default: true)
+    #[serde(rename = "destructiveHint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+
+    /// Repeated calls with the same arguments have no additional effect; only meaningful when
+    /// not read-only (default: false)
+    #[serde(rename = "idempotentHint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotent_hint: Option<bool>,
+
+    /// The tool interacts with an open world of external entities (default: true)
+    #[serde(rename = "openWorldHint")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub annotations: Option<HashMap<String, serde_json::Value>>,
+    pub open_world_hint: Option<bool>,
+}
+
+impl ToolAnnotations {
+    /// Whether the tool is read-only, applying the spec default
+    pub fn is_read_only(&self) -> bool {
+        self.read_only_hint.unwrap_or(false)
+    }
+
+    /// Whether the tool may perform destructive updates, applying the spec defaults
+    pub fn is_destructive(&self) -> bool {
+        !self.is_read_only() && self.destructive_hint.unwrap_or(true)
+    }
+
+    /// Whether repeated calls are idempotent, applying the spec defaults
+    pub fn is_idempotent(&self) -> bool {
+        self.is_read_only() || self.idempotent_hint.unwrap_or(false)
+    }
+
+    /// Whether the tool interacts with external entities, applying the spec default
+    pub fn is_open_world(&self) -> bool {
+        self.open_world_hint.unwrap_or(true)
+    }
 }
 
 /// Parameters for a tool call
@@ -23,41 +99,31 @@ pub struct ToolCallParams {
     pub name: String,
 
     pub arguments: serde_json::Value,
-}
 
-/// A single content item in a tool result
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
-pub enum ToolContent {
-    #[serde(rename = "text")]
-    Text { text: String },
-
-    #[serde(rename = "image")]
-    Image {
-        data: String,
-        #[serde(rename = "mimeType")]
-        mime_type: String,
-    },
-
-    #[serde(rename = "audio")]
-    Audio {
-        data: String,
-        #[serde(rename = "mimeType")]
-        mime_type: String,
-    },
-
-    #[serde(rename = "resource")]
-    Resource { resource: serde_json::Value },
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of a tool call
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCallResult {
-    pub content: Vec<ToolContent>,
+    pub content: Vec<ContentBlock>,
 
     #[serde(rename = "isError")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_error: Option<bool>,
+
+    /// Optional structured result, conforming to the tool's output schema if it has one
+    #[serde(rename = "structuredContent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<serde_json::Value>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Parameters for listing tools
@@ -65,6 +131,11 @@ pub struct ToolCallResult {
 pub struct ToolsListParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+
+    /// Request metadata (`_meta`), e.g. a progress token
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
 
 /// Result of listing tools
@@ -75,4 +146,9 @@ pub struct ToolsListResult {
     #[serde(rename = "nextCursor")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<String>,
+
+    /// Result metadata (`_meta`)
+    #[serde(rename = "_meta")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
 }
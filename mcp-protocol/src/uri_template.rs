@@ -0,0 +1,883 @@
+// mcp-protocol/src/uri_template.rs
+//! RFC 6570 URI templates (levels 1-4), used for resource template expansion and for
+//! matching concrete URIs back to the template that produced them.
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Error returned when a URI template is malformed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplateError {
+    /// Byte offset in the template where the problem was found
+    pub position: usize,
+
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for UriTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid URI template at {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for UriTemplateError {}
+
+/// A value bound to a template variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateValue {
+    String(String),
+    List(Vec<String>),
+    /// Key/value pairs, expanded in the given order
+    Map(Vec<(String, String)>),
+}
+
+impl From<&str> for TemplateValue {
+    fn from(value: &str) -> Self {
+        TemplateValue::String(value.to_string())
+    }
+}
+
+impl From<String> for TemplateValue {
+    fn from(value: String) -> Self {
+        TemplateValue::String(value)
+    }
+}
+
+impl From<Vec<String>> for TemplateValue {
+    fn from(values: Vec<String>) -> Self {
+        TemplateValue::List(values)
+    }
+}
+
+impl From<Vec<(String, String)>> for TemplateValue {
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        TemplateValue::Map(pairs)
+    }
+}
+
+/// Expression operators from RFC 6570 section 3.2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Simple,
+    Reserved,
+    Fragment,
+    Label,
+    Path,
+    PathParameter,
+    Query,
+    QueryContinuation,
+}
+
+impl Operator {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '+' => Some(Operator::Reserved),
+            '#' => Some(Operator::Fragment),
+            '.' => Some(Operator::Label),
+            '/' => Some(Operator::Path),
+            ';' => Some(Operator::PathParameter),
+            '?' => Some(Operator::Query),
+            '&' => Some(Operator::QueryContinuation),
+            _ => None,
+        }
+    }
+
+    /// String emitted before the first defined variable
+    fn first(self) -> &'static str {
+        match self {
+            Operator::Simple | Operator::Reserved => "",
+            Operator::Fragment => "#",
+            Operator::Label => ".",
+            Operator::Path => "/",
+            Operator::PathParameter => ";",
+            Operator::Query => "?",
+            Operator::QueryContinuation => "&",
+        }
+    }
+
+    /// String emitted between variables and exploded items
+    fn separator(self) -> char {
+        match self {
+            Operator::Simple | Operator::Reserved | Operator::Fragment => ',',
+            Operator::Label => '.',
+            Operator::Path => '/',
+            Operator::PathParameter => ';',
+            Operator::Query | Operator::QueryContinuation => '&',
+        }
+    }
+
+    /// Whether variables are expanded as `name=value` pairs
+    fn named(self) -> bool {
+        matches!(
+            self,
+            Operator::PathParameter | Operator::Query | Operator::QueryContinuation
+        )
+    }
+
+    /// What follows the name of a named variable whose value is empty
+    fn if_empty(self) -> &'static str {
+        match self {
+            Operator::Query | Operator::QueryContinuation => "=",
+            _ => "",
+        }
+    }
+
+    /// Whether reserved characters are passed through unencoded
+    fn allows_reserved(self) -> bool {
+        matches!(self, Operator::Reserved | Operator::Fragment)
+    }
+}
+
+/// Value modifiers from RFC 6570 section 2.4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Modifier {
+    None,
+    Prefix(usize),
+    Explode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VarSpec {
+    name: String,
+    modifier: Modifier,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Expression {
+        operator: Operator,
+        variables: Vec<VarSpec>,
+    },
+}
+
+/// A parsed RFC 6570 URI template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplate {
+    template: String,
+    parts: Vec<Part>,
+}
+
+impl UriTemplate {
+    /// Parse a URI template
+    pub fn parse(template: &str) -> Result<Self, UriTemplateError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.char_indices();
+
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '{' => {
+                    let body_start = position + 1;
+                    let body_end = template[body_start..]
+                        .find('}')
+                        .map(|end| body_start + end)
+                        .ok_or_else(|| error(position, "unclosed expression"))?;
+
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(parse_expression(&template[body_start..body_end], body_start)?);
+
+                    // Skip past the closing brace
+                    for (index, _) in chars.by_ref() {
+                        if index == body_end {
+                            break;
+                        }
+                    }
+                }
+                '}' => return Err(error(position, "unmatched '}'")),
+                c if c.is_control() || c == ' ' => {
+                    return Err(error(position, "literals may not contain spaces or control characters"))
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Self {
+            template: template.to_string(),
+            parts,
+        })
+    }
+
+    /// The template source text
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Names of the variables in the template, in order of appearance
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for part in &self.parts {
+            if let Part::Expression { variables, .. } = part {
+                for variable in variables {
+                    if !names.contains(&variable.name.as_str()) {
+                        names.push(&variable.name);
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    /// Expand the template; variables missing from `values` are treated as undefined
+    pub fn expand(&self, values: &HashMap<String, TemplateValue>) -> String {
+        let mut uri = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => uri.push_str(literal),
+                Part::Expression {
+                    operator,
+                    variables,
+                } => expand_expression(&mut uri, *operator, variables, values),
+            }
+        }
+
+        uri
+    }
+
+    /// Expand the template with plain string values
+    pub fn expand_strings(&self, values: &HashMap<String, String>) -> String {
+        let values = values
+            .iter()
+            .map(|(name, value)| (name.clone(), TemplateValue::String(value.clone())))
+            .collect();
+        self.expand(&values)
+    }
+
+    /// Match a concrete URI against the template, returning the extracted variables
+    ///
+    /// Values are percent-decoded. Variables whose expansion was empty are omitted, and
+    /// list or map values are returned comma-separated as they appear in a non-exploded
+    /// expansion.
+    pub fn match_uri(&self, uri: &str) -> Option<HashMap<String, String>> {
+        let mut matcher = Matcher::new(&self.parts);
+        if matcher.match_parts(0, uri) {
+            Some(
+                matcher
+                    .bindings
+                    .values
+                    .into_iter()
+                    .map(|(name, binding)| (name, binding.value))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Whether a concrete URI could have been produced by this template
+    pub fn matches(&self, uri: &str) -> bool {
+        self.match_uri(uri).is_some()
+    }
+}
+
+impl fmt::Display for UriTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+impl std::str::FromStr for UriTemplate {
+    type Err = UriTemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::parse(template)
+    }
+}
+
+fn error(position: usize, message: &str) -> UriTemplateError {
+    UriTemplateError {
+        position,
+        message: message.to_string(),
+    }
+}
+
+fn parse_expression(body: &str, offset: usize) -> Result<Part, UriTemplateError> {
+    let (operator, list, list_offset) = match body.chars().next() {
+        Some(c) => match Operator::from_char(c) {
+            Some(operator) => (operator, &body[1..], offset + 1),
+            None if "=,!@|".contains(c) => {
+                return Err(error(offset, "reserved operator"));
+            }
+            None => (Operator::Simple, body, offset),
+        },
+        None => return Err(error(offset, "empty expression")),
+    };
+
+    let mut variables = Vec::new();
+    let mut position = list_offset;
+    for spec in list.split(',') {
+        variables.push(parse_varspec(spec, position)?);
+        position += spec.len() + 1;
+    }
+
+    Ok(Part::Expression {
+        operator,
+        variables,
+    })
+}
+
+fn parse_varspec(spec: &str, position: usize) -> Result<VarSpec, UriTemplateError> {
+    let (name, modifier) = if let Some(name) = spec.strip_suffix('*') {
+        (name, Modifier::Explode)
+    } else if let Some((name, length)) = spec.split_once(':') {
+        let length: usize = length
+            .parse()
+            .ok()
+            .filter(|length| (1..10000).contains(length) && !length_has_leading_zero(spec))
+            .ok_or_else(|| error(position, "prefix length must be between 1 and 9999"))?;
+        (name, Modifier::Prefix(length))
+    } else {
+        (spec, Modifier::None)
+    };
+
+    if !is_valid_varname(name) {
+        return Err(error(position, "invalid variable name"));
+    }
+
+    Ok(VarSpec {
+        name: name.to_string(),
+        modifier,
+    })
+}
+
+fn length_has_leading_zero(spec: &str) -> bool {
+    spec.split_once(':')
+        .map(|(_, length)| length.starts_with('0'))
+        .unwrap_or(false)
+}
+
+fn is_valid_varname(name: &str) -> bool {
+    if name.is_empty() || name.starts_with('.') || name.ends_with('.') || name.contains("..") {
+        return false;
+    }
+
+    let bytes = name.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                if index + 2 >= bytes.len()
+                    || !bytes[index + 1].is_ascii_hexdigit()
+                    || !bytes[index + 2].is_ascii_hexdigit()
+                {
+                    return false;
+                }
+                index += 3;
+            }
+            b if b.is_ascii_alphanumeric() || b == b'_' || b == b'.' => index += 1,
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+fn expand_expression(
+    uri: &mut String,
+    operator: Operator,
+    variables: &[VarSpec],
+    values: &HashMap<String, TemplateValue>,
+) {
+    let mut first = true;
+
+    for variable in variables {
+        let value = match values.get(&variable.name) {
+            Some(value) if is_defined(value) => value,
+            _ => continue,
+        };
+
+        if first {
+            uri.push_str(operator.first());
+            first = false;
+        } else {
+            uri.push(operator.separator());
+        }
+
+        match value {
+            TemplateValue::String(value) => {
+                let value = match variable.modifier {
+                    Modifier::Prefix(length) => truncate(value, length),
+                    _ => value.as_str(),
+                };
+                if operator.named() {
+                    uri.push_str(&variable.name);
+                    if value.is_empty() {
+                        uri.push_str(operator.if_empty());
+                        continue;
+                    }
+                    uri.push('=');
+                }
+                uri.push_str(&encode(value, operator.allows_reserved()));
+            }
+            TemplateValue::List(items) if variable.modifier == Modifier::Explode => {
+                let separator = operator.separator().to_string();
+                let expanded: Vec<String> = items
+                    .iter()
+                    .map(|item| {
+                        if operator.named() {
+                            named_pair(&variable.name, item, operator)
+                        } else {
+                            encode(item, operator.allows_reserved())
+                        }
+                    })
+                    .collect();
+                uri.push_str(&expanded.join(&separator));
+            }
+            TemplateValue::Map(pairs) if variable.modifier == Modifier::Explode => {
+                let separator = operator.separator().to_string();
+                let expanded: Vec<String> = pairs
+                    .iter()
+                    .map(|(key, value)| {
+                        let key = encode(key, operator.allows_reserved());
+                        if operator.named() {
+                            named_pair(&key, value, operator)
+                        } else {
+                            format!("{}={}", key, encode(value, operator.allows_reserved()))
+                        }
+                    })
+                    .collect();
+                uri.push_str(&expanded.join(&separator));
+            }
+            TemplateValue::List(items) => {
+                if operator.named() {
+                    uri.push_str(&variable.name);
+                    uri.push('=');
+                }
+                let expanded: Vec<String> = items
+                    .iter()
+                    .map(|item| encode(item, operator.allows_reserved()))
+                    .collect();
+                uri.push_str(&expanded.join(","));
+            }
+            TemplateValue::Map(pairs) => {
+                if operator.named() {
+                    uri.push_str(&variable.name);
+                    uri.push('=');
+                }
+                let expanded: Vec<String> = pairs
+                    .iter()
+                    .flat_map(|(key, value)| [key, value])
+                    .map(|item| encode(item, operator.allows_reserved()))
+                    .collect();
+                uri.push_str(&expanded.join(","));
+            }
+        }
+    }
+}
+
+/// Empty lists and maps are undefined, as are missing values
+fn is_defined(value: &TemplateValue) -> bool {
+    match value {
+        TemplateValue::String(_) => true,
+        TemplateValue::List(items) => !items.is_empty(),
+        TemplateValue::Map(pairs) => !pairs.is_empty(),
+    }
+}
+
+fn named_pair(name: &str, value: &str, operator: Operator) -> String {
+    if value.is_empty() {
+        format!("{}{}", name, operator.if_empty())
+    } else {
+        format!("{}={}", name, encode(value, operator.allows_reserved()))
+    }
+}
+
+/// Truncate to at most `length` characters (not bytes)
+fn truncate(value: &str, length: usize) -> &str {
+    match value.char_indices().nth(length) {
+        Some((index, _)) => &value[..index],
+        None => value,
+    }
+}
+
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+fn is_reserved(c: char) -> bool {
+    matches!(
+        c,
+        ':' | '/' | '?' | '#' | '[' | ']' | '@' | '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+'
+            | ',' | ';' | '='
+    )
+}
+
+/// Percent-encode everything outside the allowed set, keeping existing escapes when
+/// reserved characters are allowed
+fn encode(value: &str, allow_reserved: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    let bytes = value.as_bytes();
+
+    for (index, c) in value.char_indices() {
+        let keep = is_unreserved(c)
+            || (allow_reserved && is_reserved(c))
+            || (allow_reserved
+                && c == '%'
+                && index + 2 < bytes.len()
+                && bytes[index + 1].is_ascii_hexdigit()
+                && bytes[index + 2].is_ascii_hexdigit());
+
+        if keep {
+            encoded.push(c);
+        } else {
+            let mut buffer = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Percent-decode a matched value, failing on malformed escapes or invalid UTF-8
+fn decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = value.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// A matched value, and whether it may be cut short by a `:N` prefix modifier
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Binding {
+    value: String,
+    partial: bool,
+}
+
+/// Variables bound while matching, with an undo log so a failed attempt can be rolled back
+#[derive(Default)]
+struct Bindings {
+    values: HashMap<String, Binding>,
+    /// Previous value of each variable set, oldest first
+    trail: Vec<(String, Option<Binding>)>,
+}
+
+impl Bindings {
+    fn get(&self, name: &str) -> Option<&Binding> {
+        self.values.get(name)
+    }
+
+    fn set(&mut self, name: &str, value: String, partial: bool) {
+        let previous = self
+            .values
+            .insert(name.to_string(), Binding { value, partial });
+        self.trail.push((name.to_string(), previous));
+    }
+
+    fn checkpoint(&self) -> usize {
+        self.trail.len()
+    }
+
+    /// Undo every change made since `checkpoint`
+    fn rollback(&mut self, checkpoint: usize) {
+        while self.trail.len() > checkpoint {
+            let (name, previous) = self.trail.pop().unwrap();
+            match previous {
+                Some(value) => self.values.insert(name, value),
+                None => self.values.remove(&name),
+            };
+        }
+    }
+}
+
+/// Backtracking matcher for [`UriTemplate::match_uri`]
+///
+/// Whether the parts from some index on match the rest of the URI depends only on how much of
+/// the URI is left and on the values already bound to variables that occur again later. Failed
+/// states are remembered by exactly that, so each is explored once and matching stays
+/// polynomial instead of trying every way of splitting the URI between expressions.
+struct Matcher<'a> {
+    parts: &'a [Part],
+    /// For each part index, the variables that occur both before it and from it on
+    shared: Vec<Vec<&'a str>>,
+    bindings: Bindings,
+    failed: HashSet<(usize, usize, Vec<Option<Binding>>)>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(parts: &'a [Part]) -> Self {
+        let names = |parts: &'a [Part]| -> Vec<&'a str> {
+            parts
+                .iter()
+                .flat_map(|part| match part {
+                    Part::Literal(_) => &[][..],
+                    Part::Expression { variables, .. } => variables.as_slice(),
+                })
+                .map(|variable| variable.name.as_str())
+                .collect()
+        };
+        let shared = (0..=parts.len())
+            .map(|index| {
+                let later = names(&parts[index..]);
+                let mut shared = names(&parts[..index]);
+                shared.retain(|name| later.contains(name));
+                shared.sort_unstable();
+                shared.dedup();
+                shared
+            })
+            .collect();
+
+        Self {
+            parts,
+            shared,
+            bindings: Bindings::default(),
+            failed: HashSet::new(),
+        }
+    }
+
+    /// Try to match the parts from `index` on against the whole of `uri`, a suffix of the
+    /// URI being matched, backtracking over expression lengths
+    fn match_parts(&mut self, index: usize, uri: &str) -> bool {
+        let part = match self.parts.get(index) {
+            Some(part) => part,
+            None => return uri.is_empty(),
+        };
+
+        // A suffix is identified by its length
+        let state = (
+            index,
+            uri.len(),
+            self.shared[index]
+                .iter()
+                .map(|name| self.bindings.get(name).cloned())
+                .collect(),
+        );
+        if self.failed.contains(&state) {
+            return false;
+        }
+
+        let matched = match part {
+            Part::Literal(literal) => match uri.strip_prefix(literal.as_str()) {
+                Some(remaining) => self.match_parts(index + 1, remaining),
+                None => false,
+            },
+            Part::Expression {
+                operator,
+                variables,
+            } => self.match_expression_lengths(index, *operator, variables, uri),
+        };
+
+        if !matched {
+            self.failed.insert(state);
+        }
+        matched
+    }
+
+    /// Try each length the expression at `index` could have expanded to, longest first
+    fn match_expression_lengths(
+        &mut self,
+        index: usize,
+        operator: Operator,
+        variables: &[VarSpec],
+        uri: &str,
+    ) -> bool {
+        // The longest run of characters this expression could have produced
+        let limit = uri
+            .char_indices()
+            .find(|&(_, c)| !can_produce(operator, c))
+            .map(|(index, _)| index)
+            .unwrap_or(uri.len());
+
+        // Prefer the longest match, falling back to shorter ones
+        let ends: Vec<usize> = uri[..limit]
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain(std::iter::once(limit))
+            .collect();
+
+        for &end in ends.iter().rev() {
+            let checkpoint = self.bindings.checkpoint();
+            if match_expression(operator, variables, &uri[..end], &mut self.bindings)
+                && self.match_parts(index + 1, &uri[end..])
+            {
+                return true;
+            }
+            self.bindings.rollback(checkpoint);
+        }
+
+        false
+    }
+}
+
+/// Whether an expansion with this operator can contain the character
+fn can_produce(operator: Operator, c: char) -> bool {
+    // `=` appears in named values and in exploded maps under any operator
+    if is_unreserved(c) || matches!(c, '%' | ',' | '=') {
+        return true;
+    }
+    if operator.allows_reserved() {
+        return is_reserved(c);
+    }
+
+    operator.first().starts_with(c) || operator.separator() == c
+}
+
+/// Decode the text produced by one expression into its variables
+fn match_expression(
+    operator: Operator,
+    variables: &[VarSpec],
+    text: &str,
+    values: &mut Bindings,
+) -> bool {
+    // Nothing was defined
+    if text.is_empty() {
+        return true;
+    }
+
+    let body = match text.strip_prefix(operator.first()) {
+        Some(body) => body,
+        None => return false,
+    };
+
+    if operator.named() {
+        match_named(operator, variables, body, values)
+    } else {
+        match_unnamed(operator, variables, body, values)
+    }
+}
+
+fn match_unnamed(
+    operator: Operator,
+    variables: &[VarSpec],
+    body: &str,
+    values: &mut Bindings,
+) -> bool {
+    let separator = operator.separator();
+    let mut items: Vec<&str> = body.split(separator).collect();
+
+    for (index, variable) in variables.iter().enumerate() {
+        if items.is_empty() {
+            break;
+        }
+
+        let is_last = index + 1 == variables.len();
+        let taken: Vec<&str> = if is_last || variable.modifier == Modifier::Explode {
+            // The last or exploded variable takes everything that is left
+            std::mem::take(&mut items)
+        } else {
+            vec![items.remove(0)]
+        };
+
+        let value = match taken
+            .iter()
+            .map(|item| decode(item))
+            .collect::<Option<Vec<String>>>()
+        {
+            Some(decoded) => decoded.join(","),
+            None => return false,
+        };
+
+        if !insert_consistent(values, variable, value) {
+            return false;
+        }
+    }
+
+    items.is_empty()
+}
+
+fn match_named(
+    operator: Operator,
+    variables: &[VarSpec],
+    body: &str,
+    values: &mut Bindings,
+) -> bool {
+    let mut matched: HashMap<&str, Vec<String>> = HashMap::new();
+    let explode_target = variables
+        .iter()
+        .find(|variable| variable.modifier == Modifier::Explode);
+
+    for item in body.split(operator.separator()) {
+        let (name, value) = match item.split_once('=') {
+            Some((name, value)) => (name, value),
+            None => (item, ""),
+        };
+        let value = match decode(value) {
+            Some(value) => value,
+            None => return false,
+        };
+
+        match variables.iter().find(|variable| variable.name == name) {
+            Some(variable) => matched.entry(&variable.name).or_default().push(value),
+            // Keys of an exploded map are not variable names
+            None => match explode_target {
+                Some(target) => {
+                    let key = match decode(name) {
+                        Some(key) => key,
+                        None => return false,
+                    };
+                    let entry = matched.entry(&target.name).or_default();
+                    entry.push(key);
+                    entry.push(value);
+                }
+                None => return false,
+            },
+        }
+    }
+
+    for variable in variables {
+        if let Some(parts) = matched.remove(variable.name.as_str()) {
+            if !insert_consistent(values, variable, parts.join(",")) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Record a matched value, checking it against any earlier match of the same variable
+///
+/// A `:N` prefix match that uses all N characters only constrains the start of the value, so
+/// it agrees with any value it is a prefix of and the longer of the two is kept. Every other
+/// match must equal the earlier ones.
+fn insert_consistent(values: &mut Bindings, variable: &VarSpec, value: String) -> bool {
+    let partial = match variable.modifier {
+        Modifier::Prefix(length) => {
+            let count = value.chars().count();
+            if count > length {
+                return false;
+            }
+            count == length
+        }
+        _ => false,
+    };
+
+    match values.get(&variable.name).cloned() {
+        None => {
+            values.set(&variable.name, value, partial);
+            true
+        }
+        Some(existing) if existing.value == value => {
+            if existing.partial && !partial {
+                values.set(&variable.name, value, false);
+            }
+            true
+        }
+        Some(existing) if partial && existing.value.starts_with(&value) => true,
+        Some(existing) if existing.partial && value.starts_with(&existing.value) => {
+            values.set(&variable.name, value, partial);
+            true
+        }
+        Some(_) => false,
+    }
+}
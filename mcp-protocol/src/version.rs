@@ -10,14 +10,16 @@ pub struct VersionMismatchError {
 
 /// Check if a protocol version is supported
 pub fn is_supported_version(version: &str) -> bool {
-    // For now, only support the current version
-    version == crate::constants::PROTOCOL_VERSION
+    crate::constants::SUPPORTED_PROTOCOL_VERSIONS.contains(&version)
 }
 
 /// Get information for a version mismatch error
 pub fn version_mismatch_error(requested: &str) -> VersionMismatchError {
     VersionMismatchError {
-        supported: vec![crate::constants::PROTOCOL_VERSION.to_string()],
+        supported: crate::constants::SUPPORTED_PROTOCOL_VERSIONS
+            .iter()
+            .map(|version| version.to_string())
+            .collect(),
         requested: requested.to_string(),
     }
 }
@@ -0,0 +1,149 @@
+// mcp-protocol/tests/golden.rs
+//! Round-trip every protocol type through JSON examples taken from the MCP specification
+use std::path::PathBuf;
+
+use mcp_protocol::messages::{
+    CancelledParams, InitializeParams, InitializeResult, ServerCapabilities,
+};
+use mcp_protocol::types::completion::{CompleteRequest, CompleteResponse};
+use mcp_protocol::types::content::ContentBlock;
+use mcp_protocol::types::prompt::{
+    Prompt, PromptArgument, PromptGetParams, PromptGetResult, PromptMessage, PromptReference,
+    PromptsListParams, PromptsListResult,
+};
+use mcp_protocol::types::resource::{
+    Resource, ResourceContent, ResourceReadParams, ResourceReadResult, ResourceSubscribeParams,
+    ResourceTemplate, ResourceTemplatesListParams, ResourceTemplatesListResult,
+    ResourceUnsubscribeParams, ResourceUpdatedParams, ResourcesListParams, ResourcesListResult,
+};
+use mcp_protocol::types::sampling::{CreateMessageParams, CreateMessageResult, ModelPreferences};
+use mcp_protocol::types::tool::{
+    Tool, ToolAnnotations, ToolCallParams, ToolCallResult, ToolsListParams, ToolsListResult,
+};
+use mcp_protocol::types::{ClientInfo, ServerInfo};
+use mcp_protocol::JsonRpcMessage;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Deserialize `tests/golden/<name>.json` as `T`, serialize it again and compare the JSON
+fn round_trip<T: Serialize + DeserializeOwned>(name: &str) {
+    let path = golden_dir().join(format!("{}.json", name));
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Cannot read {}: {}", path.display(), err));
+    let expected: Value = serde_json::from_str(&text).unwrap();
+
+    let parsed: T = serde_json::from_value(expected.clone())
+        .unwrap_or_else(|err| panic!("{} does not deserialize: {}", name, err));
+    // Going through a string keeps f32 fields from picking up f64 rounding noise
+    let actual: Value = serde_json::from_str(&serde_json::to_string(&parsed).unwrap()).unwrap();
+
+    assert_eq!(actual, expected, "{} did not round-trip", name);
+}
+
+#[test]
+fn jsonrpc_messages() {
+    round_trip::<JsonRpcMessage>("jsonrpc_request");
+    round_trip::<JsonRpcMessage>("jsonrpc_response");
+    round_trip::<JsonRpcMessage>("jsonrpc_error");
+    round_trip::<JsonRpcMessage>("jsonrpc_notification");
+}
+
+#[test]
+fn lifecycle() {
+    round_trip::<InitializeParams>("initialize_params");
+    round_trip::<InitializeResult>("initialize_result");
+    round_trip::<ServerCapabilities>("server_capabilities");
+    round_trip::<ClientInfo>("client_info");
+    round_trip::<ServerInfo>("server_info");
+    round_trip::<CancelledParams>("cancelled_params");
+    round_trip::<CancelledParams>("cancelled_params_meta");
+}
+
+#[test]
+fn tools() {
+    round_trip::<ToolsListParams>("tools_list_params");
+    round_trip::<ToolsListResult>("tools_list_result");
+    round_trip::<Tool>("tool");
+    round_trip::<ToolAnnotations>("tool_annotations");
+    round_trip::<ToolCallParams>("tool_call_params");
+    round_trip::<ToolCallParams>("tool_call_params_meta");
+    round_trip::<ToolCallResult>("tool_call_result");
+    round_trip::<ToolCallResult>("tool_call_result_meta");
+    round_trip::<ToolCallResult>("tool_call_result_structured");
+}
+
+#[test]
+fn content() {
+    round_trip::<ContentBlock>("content_image");
+    round_trip::<ContentBlock>("content_audio");
+    round_trip::<ContentBlock>("content_resource");
+    round_trip::<ContentBlock>("content_resource_blob");
+    round_trip::<ContentBlock>("content_resource_link");
+    round_trip::<ContentBlock>("content_text_annotated");
+}
+
+#[test]
+fn resources() {
+    round_trip::<ResourcesListParams>("resources_list_params");
+    round_trip::<ResourcesListResult>("resources_list_result");
+    round_trip::<Resource>("resource");
+    round_trip::<ResourceContent>("resource_content_text");
+    round_trip::<ResourceContent>("resource_content_blob");
+    round_trip::<ResourceReadParams>("resource_read_params");
+    round_trip::<ResourceReadResult>("resource_read_result");
+    round_trip::<ResourceSubscribeParams>("resource_subscribe_params");
+    round_trip::<ResourceUnsubscribeParams>("resource_unsubscribe_params");
+    round_trip::<ResourceUpdatedParams>("resource_updated_params");
+    round_trip::<ResourceTemplate>("resource_template");
+    round_trip::<ResourceTemplatesListParams>("resource_templates_list_params");
+    round_trip::<ResourceTemplatesListResult>("resource_templates_list_result");
+}
+
+#[test]
+fn prompts() {
+    round_trip::<PromptsListParams>("prompts_list_params");
+    round_trip::<PromptsListResult>("prompts_list_result");
+    round_trip::<Prompt>("prompt");
+    round_trip::<PromptArgument>("prompt_argument");
+    round_trip::<PromptGetParams>("prompt_get_params");
+    round_trip::<PromptGetResult>("prompt_get_result");
+    round_trip::<PromptMessage>("prompt_message_image");
+    round_trip::<PromptMessage>("prompt_message_resource");
+    round_trip::<PromptMessage>("prompt_message_audio");
+    round_trip::<PromptReference>("prompt_reference");
+}
+
+#[test]
+fn completion() {
+    round_trip::<CompleteRequest>("complete_request_prompt");
+    round_trip::<CompleteRequest>("complete_request_resource");
+    round_trip::<CompleteRequest>("complete_request_context");
+    round_trip::<CompleteResponse>("complete_response");
+}
+
+#[test]
+fn sampling() {
+    round_trip::<CreateMessageParams>("create_message_params");
+    round_trip::<CreateMessageParams>("create_message_params_full");
+    round_trip::<CreateMessageResult>("create_message_result");
+    round_trip::<ModelPreferences>("model_preferences");
+}
+
+#[test]
+fn every_golden_file_is_checked() {
+    let source = include_str!("golden.rs");
+    for entry in std::fs::read_dir(golden_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_string_lossy();
+        assert!(
+            source.contains(&format!("(\"{}\")", name)),
+            "{} has no round-trip test",
+            path.display()
+        );
+    }
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
@@ -0,0 +1,74 @@
+// mcp-protocol/tests/jsonrpc.rs
+//! Classification and validation of incoming JSON-RPC messages
+use mcp_protocol::messages::{JsonRpcMessage, RequestId};
+
+fn parse(text: &str) -> Result<JsonRpcMessage, serde_json::Error> {
+    serde_json::from_str(text)
+}
+
+#[test]
+fn classifies_each_message_kind() {
+    let request = parse(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#).unwrap();
+    assert!(matches!(request, JsonRpcMessage::Request(_)));
+    assert_eq!(request.method(), Some("ping"));
+
+    let notification = parse(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#).unwrap();
+    assert!(matches!(notification, JsonRpcMessage::Notification(_)));
+    assert_eq!(notification.id(), None);
+
+    let response = parse(r#"{"jsonrpc":"2.0","id":"a","result":{}}"#).unwrap();
+    assert!(matches!(response, JsonRpcMessage::Response(_)));
+
+    let error =
+        parse(r#"{"jsonrpc":"2.0","id":2,"error":{"code":-32601,"message":"Method not found"}}"#)
+            .unwrap();
+    let JsonRpcMessage::Error(error) = error else {
+        panic!("expected an error response");
+    };
+    assert_eq!(error.error.code, -32601);
+}
+
+#[test]
+fn null_result_is_still_a_response() {
+    let message = parse(r#"{"jsonrpc":"2.0","id":1,"result":null}"#).unwrap();
+    let JsonRpcMessage::Response(response) = message else {
+        panic!("expected a response");
+    };
+    assert!(response.result.is_null());
+}
+
+#[test]
+fn string_and_integer_ids_are_distinct() {
+    let number = parse(r#"{"jsonrpc":"2.0","id":1,"result":{}}"#).unwrap();
+    let string = parse(r#"{"jsonrpc":"2.0","id":"1","result":{}}"#).unwrap();
+
+    assert_eq!(number.id(), Some(&RequestId::Number(1)));
+    assert_eq!(string.id(), Some(&RequestId::String("1".to_string())));
+    assert_ne!(number.id(), string.id());
+
+    // Ids serialize back to the type they arrived as
+    assert_eq!(
+        serde_json::to_string(&RequestId::from("1")).unwrap(),
+        r#""1""#
+    );
+    assert_eq!(serde_json::to_string(&RequestId::from(1)).unwrap(), "1");
+}
+
+#[test]
+fn rejects_malformed_messages() {
+    // Wrong or missing version
+    assert!(parse(r#"{"jsonrpc":"1.0","id":1,"method":"ping"}"#).is_err());
+    assert!(parse(r#"{"id":1,"method":"ping"}"#).is_err());
+
+    // Ids must be strings or integers
+    assert!(parse(r#"{"jsonrpc":"2.0","id":null,"method":"ping"}"#).is_err());
+    assert!(parse(r#"{"jsonrpc":"2.0","id":1.5,"result":{}}"#).is_err());
+
+    // Ambiguous or empty shapes
+    assert!(
+        parse(r#"{"jsonrpc":"2.0","id":1,"result":{},"error":{"code":1,"message":"x"}}"#).is_err()
+    );
+    assert!(parse(r#"{"jsonrpc":"2.0","id":1,"method":"ping","result":{}}"#).is_err());
+    assert!(parse(r#"{"jsonrpc":"2.0","id":1}"#).is_err());
+    assert!(parse(r#"[1, 2]"#).is_err());
+}
@@ -0,0 +1,731 @@
+// mcp-protocol/tests/schema_conformance.rs
+//! Validate the serialized form of every request, result and notification against the official
+//! MCP JSON Schema of each supported protocol version, vendored under `tests/schema/<version>/`
+//!
+//! Supporting a new version means copying `schema/<version>/schema.json` from the specification
+//! repository into `tests/schema/<version>/` and adding it to `SUPPORTED_PROTOCOL_VERSIONS`.
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use mcp_protocol::constants::{methods, SUPPORTED_PROTOCOL_VERSIONS};
+use mcp_protocol::messages::{
+    CancelledParams, ClientCapabilities, InitializeParams, InitializeResult, Meta,
+    ServerCapabilities,
+};
+use mcp_protocol::types::completion::{
+    CompleteRequest, CompleteResponse, CompletionArgument, CompletionCompleteParams,
+    CompletionCompleteResult, CompletionContext, CompletionInfo, CompletionReference,
+    CompletionResult,
+};
+use mcp_protocol::types::content::{Annotations, ContentBlock, ResourceLink, Role};
+use mcp_protocol::types::prompt::{
+    Prompt, PromptArgument, PromptGetParams, PromptGetResult, PromptMessage, PromptsListParams,
+    PromptsListResult,
+};
+use mcp_protocol::types::resource::{
+    Resource, ResourceContent, ResourceReadParams, ResourceReadResult, ResourceSubscribeParams,
+    ResourceTemplate, ResourceTemplatesListParams, ResourceTemplatesListResult,
+    ResourceUnsubscribeParams, ResourceUpdatedParams, ResourcesListParams, ResourcesListResult,
+};
+use mcp_protocol::types::sampling::{
+    CreateMessageParams, CreateMessageResult, Message, ModelHint, ModelPreferences,
+};
+use mcp_protocol::types::tool::{
+    Tool, ToolAnnotations, ToolCallParams, ToolCallResult, ToolsListParams, ToolsListResult,
+};
+use mcp_protocol::types::{ClientInfo, ServerInfo};
+use mcp_protocol::JsonRpcMessage;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// A serialized message and the schema definition it must satisfy
+struct Case {
+    name: String,
+    definition: &'static str,
+    instance: Value,
+}
+
+/// A request: its params are checked by the method's request definition and the whole
+/// message by `JSONRPCRequest`
+fn request(definition: &'static str, method: &str, params: impl Serialize) -> Vec<Case> {
+    let params = serde_json::to_value(params).unwrap();
+    vec![
+        Case {
+            name: format!("{} params", method),
+            definition,
+            instance: json!({ "method": method, "params": params }),
+        },
+        Case {
+            name: format!("{} message", method),
+            definition: "JSONRPCRequest",
+            instance: serde_json::to_value(JsonRpcMessage::request(1, method, Some(params)))
+                .unwrap(),
+        },
+    ]
+}
+
+/// A result: checked by its result definition and, wrapped in a response, by `JSONRPCResponse`
+fn result(definition: &'static str, result: impl Serialize) -> Vec<Case> {
+    let result = serde_json::to_value(result).unwrap();
+    vec![
+        Case {
+            name: definition.to_string(),
+            definition,
+            instance: result.clone(),
+        },
+        Case {
+            name: format!("{} response", definition),
+            definition: "JSONRPCResponse",
+            instance: serde_json::to_value(JsonRpcMessage::response("abc", result)).unwrap(),
+        },
+    ]
+}
+
+/// A notification: checked by its notification definition and by `JSONRPCNotification`
+fn notification(definition: &'static str, method: &str, params: Option<Value>) -> Vec<Case> {
+    let mut body = json!({ "method": method });
+    if let Some(params) = &params {
+        body["params"] = params.clone();
+    }
+    vec![
+        Case {
+            name: method.to_string(),
+            definition,
+            instance: body,
+        },
+        Case {
+            name: format!("{} message", method),
+            definition: "JSONRPCNotification",
+            instance: serde_json::to_value(JsonRpcMessage::notification(method, params)).unwrap(),
+        },
+    ]
+}
+
+fn text_resource() -> ResourceContent {
+    ResourceContent {
+        uri: "file:///project/src/main.rs".to_string(),
+        mime_type: "text/x-rust".to_string(),
+        text: Some("fn main() {}".to_string()),
+        blob: None,
+        meta: None,
+    }
+}
+
+fn blob_resource() -> ResourceContent {
+    ResourceContent {
+        uri: "file:///project/logo.png".to_string(),
+        mime_type: "image/png".to_string(),
+        text: None,
+        blob: Some("aGVsbG8=".to_string()),
+        meta: None,
+    }
+}
+
+fn lifecycle_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    cases.extend(request(
+        "InitializeRequest",
+        methods::INITIALIZE,
+        InitializeParams {
+            protocol_version: "2025-06-18".to_string(),
+            capabilities: ClientCapabilities {
+                roots: Some(HashMap::from([("listChanged".to_string(), true)])),
+                sampling: Some(HashMap::new()),
+                elicitation: Some(HashMap::new()),
+                experimental: None,
+            },
+            client_info: ClientInfo {
+                name: "client".to_string(),
+                version: "1.0.0".to_string(),
+                title: Some("Example Client".to_string()),
+            },
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "InitializeResult",
+        InitializeResult {
+            protocol_version: "2025-06-18".to_string(),
+            capabilities: ServerCapabilities {
+                logging: Some(HashMap::new()),
+                completions: Some(HashMap::new()),
+                prompts: Some(HashMap::from([("listChanged".to_string(), true)])),
+                resources: Some(HashMap::from([
+                    ("subscribe".to_string(), true),
+                    ("listChanged".to_string(), true),
+                ])),
+                tools: Some(HashMap::from([("listChanged".to_string(), true)])),
+                experimental: None,
+            },
+            server_info: ServerInfo {
+                name: "server".to_string(),
+                version: "1.0.0".to_string(),
+                title: Some("Example Server".to_string()),
+            },
+            instructions: Some("Use the tools".to_string()),
+            meta: None,
+        },
+    ));
+    cases.extend(notification(
+        "InitializedNotification",
+        methods::INITIALIZED,
+        None,
+    ));
+    cases.extend(notification(
+        "CancelledNotification",
+        methods::CANCELLED,
+        Some(
+            serde_json::to_value(CancelledParams {
+                request_id: 7.into(),
+                reason: Some("Took too long".to_string()),
+                meta: None,
+            })
+            .unwrap(),
+        ),
+    ));
+
+    cases
+}
+
+fn tool_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    cases.extend(request(
+        "ListToolsRequest",
+        methods::TOOLS_LIST,
+        ToolsListParams {
+            cursor: Some("page-2".to_string()),
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "ListToolsResult",
+        ToolsListResult {
+            tools: vec![
+                Tool {
+                    name: "get_weather".to_string(),
+                    description: Some("Get the weather".to_string()),
+                    input_schema: json!({
+                        "type": "object",
+                        "properties": { "location": { "type": "string" } },
+                        "required": ["location"]
+                    }),
+                    output_schema: Some(json!({
+                        "type": "object",
+                        "properties": { "temperature": { "type": "number" } }
+                    })),
+                    annotations: Some(ToolAnnotations {
+                        title: Some("Weather".to_string()),
+                        read_only_hint: Some(true),
+                        destructive_hint: Some(false),
+                        idempotent_hint: Some(true),
+                        open_world_hint: Some(true),
+                    }),
+                    title: Some("Weather Lookup".to_string()),
+                },
+                Tool {
+                    name: "noop".to_string(),
+                    description: None,
+                    input_schema: json!({ "type": "object" }),
+                    output_schema: None,
+                    annotations: None,
+                    title: None,
+                },
+            ],
+            next_cursor: Some("page-3".to_string()),
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "CallToolRequest",
+        methods::TOOLS_CALL,
+        ToolCallParams {
+            name: "get_weather".to_string(),
+            arguments: json!({ "location": "Paris" }),
+            meta: Some(
+                Meta::new()
+                    .with_progress_token("progress-1")
+                    .with("example.com/trace-id", json!("4bf92f3577b34da6")),
+            ),
+        },
+    ));
+    cases.extend(result(
+        "CallToolResult",
+        ToolCallResult {
+            content: vec![
+                ContentBlock::text("Sunny").with_annotations(Annotations {
+                    audience: Some(vec![Role::User, Role::Assistant]),
+                    priority: Some(0.9),
+                    last_modified: Some("2025-01-12T15:00:58Z".to_string()),
+                }),
+                ContentBlock::image("aGVsbG8=", "image/png"),
+                ContentBlock::audio("aGVsbG8=", "audio/wav"),
+                ContentBlock::resource(text_resource()),
+                ContentBlock::resource_link(ResourceLink {
+                    uri: "file:///project/README.md".to_string(),
+                    name: "README.md".to_string(),
+                    description: None,
+                    mime_type: Some("text/markdown".to_string()),
+                    size: Some(1024),
+                    annotations: None,
+                    title: Some("Readme".to_string()),
+                }),
+            ],
+            is_error: Some(false),
+            structured_content: Some(json!({ "temperature": 22.5 })),
+            meta: Some(Meta::new().with("example.com/cache", json!("hit"))),
+        },
+    ));
+    cases.extend(result(
+        "CallToolResult",
+        ToolCallResult {
+            content: vec![ContentBlock::text("Unknown location")],
+            is_error: Some(true),
+            structured_content: None,
+            meta: None,
+        },
+    ));
+    cases.extend(notification(
+        "ToolListChangedNotification",
+        methods::TOOLS_LIST_CHANGED,
+        None,
+    ));
+
+    cases
+}
+
+fn resource_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    cases.extend(request(
+        "ListResourcesRequest",
+        methods::RESOURCES_LIST,
+        ResourcesListParams {
+            cursor: None,
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "ListResourcesResult",
+        ResourcesListResult {
+            resources: vec![Resource {
+                uri: "file:///project/README.md".to_string(),
+                name: "README.md".to_string(),
+                description: Some("Project readme".to_string()),
+                mime_type: Some("text/markdown".to_string()),
+                size: Some(1024),
+                annotations: Some(Annotations {
+                    audience: Some(vec![Role::User]),
+                    priority: Some(0.8),
+                    last_modified: None,
+                }),
+                title: Some("Project Readme".to_string()),
+            }],
+            next_cursor: None,
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "ListResourceTemplatesRequest",
+        methods::RESOURCES_TEMPLATES_LIST,
+        ResourceTemplatesListParams {
+            cursor: Some("page-2".to_string()),
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "ListResourceTemplatesResult",
+        ResourceTemplatesListResult {
+            resource_templates: vec![ResourceTemplate {
+                uri_template: "file:///{path}".to_string(),
+                name: "Project files".to_string(),
+                description: None,
+                mime_type: Some("application/octet-stream".to_string()),
+                annotations: None,
+                title: Some("📁 Project Files".to_string()),
+            }],
+            next_cursor: None,
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "ReadResourceRequest",
+        methods::RESOURCES_READ,
+        ResourceReadParams {
+            uri: "file:///project/src/main.rs".to_string(),
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "ReadResourceResult",
+        ResourceReadResult {
+            contents: vec![text_resource(), blob_resource()],
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "SubscribeRequest",
+        methods::RESOURCES_SUBSCRIBE,
+        ResourceSubscribeParams {
+            uri: "file:///project/src/main.rs".to_string(),
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "UnsubscribeRequest",
+        methods::RESOURCES_UNSUBSCRIBE,
+        ResourceUnsubscribeParams {
+            uri: "file:///project/src/main.rs".to_string(),
+            meta: None,
+        },
+    ));
+    cases.extend(notification(
+        "ResourceUpdatedNotification",
+        methods::RESOURCES_UPDATED,
+        Some(
+            serde_json::to_value(ResourceUpdatedParams {
+                uri: "file:///project/src/main.rs".to_string(),
+                meta: None,
+            })
+            .unwrap(),
+        ),
+    ));
+    cases.extend(notification(
+        "ResourceListChangedNotification",
+        methods::RESOURCES_LIST_CHANGED,
+        None,
+    ));
+
+    cases
+}
+
+fn prompt_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    cases.extend(request(
+        "ListPromptsRequest",
+        methods::PROMPTS_LIST,
+        PromptsListParams {
+            cursor: None,
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "ListPromptsResult",
+        PromptsListResult {
+            prompts: vec![Prompt {
+                name: "code_review".to_string(),
+                description: Some("Review code".to_string()),
+                arguments: Some(vec![PromptArgument {
+                    name: "code".to_string(),
+                    description: Some("The code to review".to_string()),
+                    required: Some(true),
+                    title: Some("Code".to_string()),
+                }]),
+                annotations: None,
+                title: Some("Request Code Review".to_string()),
+            }],
+            next_cursor: Some("page-2".to_string()),
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "GetPromptRequest",
+        methods::PROMPTS_GET,
+        PromptGetParams {
+            name: "code_review".to_string(),
+            arguments: Some(HashMap::from([(
+                "code".to_string(),
+                "fn main() {}".to_string(),
+            )])),
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "GetPromptResult",
+        PromptGetResult {
+            description: Some("Code review".to_string()),
+            messages: vec![
+                PromptMessage {
+                    role: "user".to_string(),
+                    content: ContentBlock::text("Review this"),
+                },
+                PromptMessage {
+                    role: "user".to_string(),
+                    content: ContentBlock::image("aGVsbG8=", "image/png"),
+                },
+                PromptMessage {
+                    role: "user".to_string(),
+                    content: ContentBlock::audio("aGVsbG8=", "audio/wav"),
+                },
+                PromptMessage {
+                    role: "assistant".to_string(),
+                    content: ContentBlock::resource(blob_resource()).with_annotations(
+                        Annotations {
+                            audience: Some(vec![Role::Assistant]),
+                            ..Default::default()
+                        },
+                    ),
+                },
+                PromptMessage {
+                    role: "assistant".to_string(),
+                    content: ContentBlock::resource_link(ResourceLink {
+                        uri: "file:///project/logo.png".to_string(),
+                        name: "logo.png".to_string(),
+                        description: Some("Project logo".to_string()),
+                        mime_type: Some("image/png".to_string()),
+                        size: None,
+                        annotations: None,
+                        title: None,
+                    }),
+                },
+            ],
+            meta: None,
+        },
+    ));
+    cases.extend(notification(
+        "PromptListChangedNotification",
+        methods::PROMPTS_LIST_CHANGED,
+        None,
+    ));
+
+    cases
+}
+
+fn completion_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    cases.extend(request(
+        "CompleteRequest",
+        methods::COMPLETION_COMPLETE,
+        CompleteRequest {
+            r#ref: CompletionReference::Prompt {
+                name: "code_review".to_string(),
+            },
+            argument: CompletionArgument {
+                name: "language".to_string(),
+                value: "py".to_string(),
+            },
+            context: None,
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "CompleteRequest",
+        methods::COMPLETION_COMPLETE,
+        CompletionCompleteParams {
+            r#ref: CompletionReference::Resource {
+                uri: "file:///{path}".to_string(),
+            },
+            argument: CompletionArgument {
+                name: "path".to_string(),
+                value: "src/".to_string(),
+            },
+            context: None,
+            meta: None,
+        },
+    ));
+    cases.extend(request(
+        "CompleteRequest",
+        methods::COMPLETION_COMPLETE,
+        CompleteRequest {
+            r#ref: CompletionReference::Resource {
+                uri: "github://repos/{owner}/{repo}".to_string(),
+            },
+            argument: CompletionArgument {
+                name: "repo".to_string(),
+                value: "mcp".to_string(),
+            },
+            context: Some(CompletionContext {
+                arguments: Some(HashMap::from([(
+                    "owner".to_string(),
+                    "modelcontextprotocol".to_string(),
+                )])),
+            }),
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "CompleteResult",
+        CompleteResponse {
+            completion: CompletionResult {
+                values: vec!["python".to_string(), "pytorch".to_string()],
+                total: Some(10),
+                has_more: true,
+            },
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "CompleteResult",
+        CompletionCompleteResult {
+            completion: CompletionInfo {
+                values: vec!["src/main.rs".to_string()],
+                total: None,
+                has_more: false,
+            },
+            meta: None,
+        },
+    ));
+
+    cases
+}
+
+fn sampling_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    cases.extend(request(
+        "CreateMessageRequest",
+        methods::SAMPLING_CREATE_MESSAGE,
+        CreateMessageParams {
+            messages: vec![
+                Message {
+                    role: "user".to_string(),
+                    content: ContentBlock::text("What is in this picture?"),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: ContentBlock::image("aGVsbG8=", "image/jpeg"),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: ContentBlock::audio("aGVsbG8=", "audio/wav"),
+                },
+            ],
+            model_preferences: Some(ModelPreferences {
+                hints: Some(vec![ModelHint {
+                    name: "claude-3-sonnet".to_string(),
+                }]),
+                cost_priority: Some(0.3),
+                speed_priority: Some(0.8),
+                intelligence_priority: Some(0.5),
+            }),
+            system_prompt: Some("You are a helpful assistant.".to_string()),
+            include_context: Some("thisServer".to_string()),
+            max_tokens: Some(100),
+            temperature: Some(0.7),
+            top_p: None,
+            stop_sequences: Some(vec!["\n\n".to_string()]),
+            metadata: Some(json!({ "provider": "example" })),
+            context: None,
+            meta: None,
+        },
+    ));
+    cases.extend(result(
+        "CreateMessageResult",
+        CreateMessageResult {
+            role: "assistant".to_string(),
+            content: ContentBlock::text("A cat"),
+            model: Some("claude-3-sonnet-20240307".to_string()),
+            stop_reason: Some("endTurn".to_string()),
+            metadata: None,
+            meta: None,
+        },
+    ));
+
+    cases
+}
+
+fn error_cases() -> Vec<Case> {
+    vec![Case {
+        name: "error response".to_string(),
+        definition: "JSONRPCError",
+        instance: serde_json::to_value(JsonRpcMessage::error(
+            3,
+            -32602,
+            "Unknown tool: missing",
+            Some(json!({ "tool": "missing" })),
+        ))
+        .unwrap(),
+    }]
+}
+
+fn all_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+    cases.extend(lifecycle_cases());
+    cases.extend(tool_cases());
+    cases.extend(resource_cases());
+    cases.extend(prompt_cases());
+    cases.extend(completion_cases());
+    cases.extend(sampling_cases());
+    cases.extend(error_cases());
+    cases
+}
+
+fn schema_path(version: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/schema")
+        .join(version)
+        .join("schema.json")
+}
+
+/// Load the vendored schema for a protocol version
+fn load_schema(version: &str) -> Value {
+    let path = schema_path(version);
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "No schema vendored for protocol version {} at {}: {}",
+            version,
+            path.display(),
+            err
+        )
+    });
+    serde_json::from_str(&text).unwrap()
+}
+
+/// Validate `instance` against one definition of `schema`, returning the errors found
+fn validate(schema: &Value, definition: &str, instance: &Value) -> Vec<String> {
+    assert!(
+        schema["definitions"].get(definition).is_some(),
+        "The schema has no definition named {}",
+        definition
+    );
+
+    let mut root = schema.clone();
+    root["allOf"] = json!([{ "$ref": format!("#/definitions/{}", definition) }]);
+    let validator = jsonschema::draft7::new(&root).unwrap();
+    validator
+        .iter_errors(instance)
+        .map(|err| format!("{} at {}", err, err.instance_path()))
+        .collect()
+}
+
+#[test]
+fn serialized_messages_match_the_schema() {
+    for version in SUPPORTED_PROTOCOL_VERSIONS {
+        let schema = load_schema(version);
+        let failures: Vec<String> = all_cases()
+            .into_iter()
+            .flat_map(|case| {
+                validate(&schema, case.definition, &case.instance)
+                    .into_iter()
+                    .map(move |err| {
+                        format!("[{}] {} ({}): {}", version, case.name, case.definition, err)
+                    })
+            })
+            .collect();
+
+        assert!(
+            failures.is_empty(),
+            "Serialized messages do not match the schema:\n{}",
+            failures.join("\n")
+        );
+    }
+}
+
+#[test]
+fn harness_rejects_nonconforming_messages() {
+    for version in SUPPORTED_PROTOCOL_VERSIONS {
+        let schema = load_schema(version);
+
+        // snake_case field names and missing required fields must both be caught
+        let wrong_casing = json!({
+            "tools": [{ "name": "noop", "input_schema": { "type": "object" } }]
+        });
+        assert!(!validate(&schema, "ListToolsResult", &wrong_casing).is_empty());
+
+        let missing_required = json!({ "method": "resources/read", "params": {} });
+        assert!(!validate(&schema, "ReadResourceRequest", &missing_required).is_empty());
+    }
+}
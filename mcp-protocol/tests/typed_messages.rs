@@ -0,0 +1,100 @@
+// mcp-protocol/tests/typed_messages.rs
+//! Parsing JSON-RPC messages into typed requests and notifications
+use mcp_protocol::messages::{
+    ClientNotification, ClientRequest, JsonRpcMessage, MessageParseError, ServerNotification,
+    ServerRequest,
+};
+
+fn parse(text: &str) -> JsonRpcMessage {
+    serde_json::from_str(text).unwrap()
+}
+
+fn client_request(text: &str) -> Result<ClientRequest, MessageParseError> {
+    let JsonRpcMessage::Request(request) = parse(text) else {
+        panic!("expected a request");
+    };
+    ClientRequest::from_request(&request)
+}
+
+#[test]
+fn requests_parse_into_their_params() {
+    let request =
+        client_request(r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"echo","arguments":{"x":1}}}"#)
+            .unwrap();
+    let ClientRequest::CallTool(params) = request else {
+        panic!("expected tools/call");
+    };
+    assert_eq!(params.name, "echo");
+
+    // List params are all optional, so they may be left out
+    let request = client_request(r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#).unwrap();
+    let ClientRequest::ListTools(params) = request else {
+        panic!("expected tools/list");
+    };
+    assert_eq!(params.cursor, None);
+}
+
+#[test]
+fn unknown_methods_and_bad_params_are_told_apart() {
+    assert_eq!(
+        client_request(r#"{"jsonrpc":"2.0","id":1,"method":"tools/frobnicate"}"#).unwrap_err(),
+        MessageParseError::MethodNotFound("tools/frobnicate".to_string())
+    );
+
+    // resources/read needs a uri
+    let err = client_request(r#"{"jsonrpc":"2.0","id":1,"method":"resources/read"}"#).unwrap_err();
+    assert!(matches!(
+        err,
+        MessageParseError::InvalidParams { ref method, .. } if method == "resources/read"
+    ));
+
+    // Requests meant for the other side are not recognised
+    let JsonRpcMessage::Request(request) =
+        parse(r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#)
+    else {
+        panic!("expected a request");
+    };
+    assert!(ServerRequest::from_request(&request).is_err());
+}
+
+#[test]
+fn notifications_parse_into_their_params() {
+    let JsonRpcMessage::Notification(notification) =
+        parse(r#"{"jsonrpc":"2.0","method":"notifications/cancelled","params":{"requestId":"a"}}"#)
+    else {
+        panic!("expected a notification");
+    };
+    let ClientNotification::Cancelled(params) =
+        ClientNotification::from_notification(&notification).unwrap()
+    else {
+        panic!("expected notifications/cancelled");
+    };
+    assert_eq!(params.request_id, "a".into());
+
+    let JsonRpcMessage::Notification(notification) =
+        parse(r#"{"jsonrpc":"2.0","method":"notifications/tools/list_changed"}"#)
+    else {
+        panic!("expected a notification");
+    };
+    assert!(matches!(
+        ServerNotification::from_notification(&notification).unwrap(),
+        ServerNotification::ToolListChanged(_)
+    ));
+}
+
+#[test]
+fn typed_messages_round_trip() {
+    let request = client_request(
+        r#"{"jsonrpc":"2.0","id":7,"method":"prompts/get","params":{"name":"greet"}}"#,
+    )
+    .unwrap();
+    let JsonRpcMessage::Request(message) = request.into_message(7) else {
+        panic!("expected a request");
+    };
+    assert_eq!(message.method, "prompts/get");
+
+    let ClientRequest::GetPrompt(params) = ClientRequest::from_request(&message).unwrap() else {
+        panic!("expected prompts/get");
+    };
+    assert_eq!(params.name, "greet");
+}
@@ -0,0 +1,258 @@
+// mcp-protocol/tests/uri_template.rs
+//! Expansion examples from RFC 6570 and matching URIs back to their variables
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use mcp_protocol::uri_template::{TemplateValue, UriTemplate};
+
+/// The variables of RFC 6570 section 1.2
+fn rfc_values() -> HashMap<String, TemplateValue> {
+    let list = |items: &[&str]| TemplateValue::List(items.iter().map(|s| s.to_string()).collect());
+    let keys = TemplateValue::Map(
+        [("semi", ";"), ("dot", "."), ("comma", ",")]
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    );
+
+    [
+        ("var", "value".into()),
+        ("hello", "Hello World!".into()),
+        ("path", "/foo/bar".into()),
+        ("empty", "".into()),
+        ("x", "1024".into()),
+        ("y", "768".into()),
+        ("list", list(&["red", "green", "blue"])),
+        ("keys", keys),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+fn assert_expansions(cases: &[(&str, &str)]) {
+    let values = rfc_values();
+    for (template, expected) in cases {
+        let expanded = UriTemplate::parse(template).unwrap().expand(&values);
+        assert_eq!(&expanded, expected, "{}", template);
+    }
+}
+
+#[test]
+fn expands_level_1_examples() {
+    assert_expansions(&[("{var}", "value"), ("{hello}", "Hello%20World%21")]);
+}
+
+#[test]
+fn expands_level_2_examples() {
+    assert_expansions(&[
+        ("{+var}", "value"),
+        ("{+hello}", "Hello%20World!"),
+        ("{+path}/here", "/foo/bar/here"),
+        ("here?ref={+path}", "here?ref=/foo/bar"),
+        ("X{#var}", "X#value"),
+        ("X{#hello}", "X#Hello%20World!"),
+    ]);
+}
+
+#[test]
+fn expands_level_3_examples() {
+    assert_expansions(&[
+        ("map?{x,y}", "map?1024,768"),
+        ("{x,hello,y}", "1024,Hello%20World%21,768"),
+        ("{+x,hello,y}", "1024,Hello%20World!,768"),
+        ("{+path,x}/here", "/foo/bar,1024/here"),
+        ("{#x,hello,y}", "#1024,Hello%20World!,768"),
+        ("{#path,x}/here", "#/foo/bar,1024/here"),
+        ("X{.var}", "X.value"),
+        ("X{.x,y}", "X.1024.768"),
+        ("{/var}", "/value"),
+        ("{/var,x}/here", "/value/1024/here"),
+        ("{;x,y}", ";x=1024;y=768"),
+        ("{;x,y,empty}", ";x=1024;y=768;empty"),
+        ("{?x,y}", "?x=1024&y=768"),
+        ("{?x,y,empty}", "?x=1024&y=768&empty="),
+        ("?fixed=yes{&x}", "?fixed=yes&x=1024"),
+        ("{&x,y,empty}", "&x=1024&y=768&empty="),
+    ]);
+}
+
+#[test]
+fn expands_level_4_examples() {
+    assert_expansions(&[
+        ("{var:3}", "val"),
+        ("{var:30}", "value"),
+        ("{list}", "red,green,blue"),
+        ("{list*}", "red,green,blue"),
+        ("{keys}", "semi,%3B,dot,.,comma,%2C"),
+        ("{keys*}", "semi=%3B,dot=.,comma=%2C"),
+        ("{+path:6}/here", "/foo/b/here"),
+        ("{+list}", "red,green,blue"),
+        ("{+list*}", "red,green,blue"),
+        ("{+keys}", "semi,;,dot,.,comma,,"),
+        ("{+keys*}", "semi=;,dot=.,comma=,"),
+        ("{#path:6}/here", "#/foo/b/here"),
+        ("{#list}", "#red,green,blue"),
+        ("{#list*}", "#red,green,blue"),
+        ("{#keys}", "#semi,;,dot,.,comma,,"),
+        ("{#keys*}", "#semi=;,dot=.,comma=,"),
+        ("X{.var:3}", "X.val"),
+        ("X{.list}", "X.red,green,blue"),
+        ("X{.list*}", "X.red.green.blue"),
+        ("X{.keys}", "X.semi,%3B,dot,.,comma,%2C"),
+        ("X{.keys*}", "X.semi=%3B.dot=..comma=%2C"),
+        ("{/var:1,var}", "/v/value"),
+        ("{/list}", "/red,green,blue"),
+        ("{/list*}", "/red/green/blue"),
+        ("{/list*,path:4}", "/red/green/blue/%2Ffoo"),
+        ("{/keys}", "/semi,%3B,dot,.,comma,%2C"),
+        ("{/keys*}", "/semi=%3B/dot=./comma=%2C"),
+        ("{;hello:5}", ";hello=Hello"),
+        ("{;list}", ";list=red,green,blue"),
+        ("{;list*}", ";list=red;list=green;list=blue"),
+        ("{;keys}", ";keys=semi,%3B,dot,.,comma,%2C"),
+        ("{;keys*}", ";semi=%3B;dot=.;comma=%2C"),
+        ("{?var:3}", "?var=val"),
+        ("{?list}", "?list=red,green,blue"),
+        ("{?list*}", "?list=red&list=green&list=blue"),
+        ("{?keys}", "?keys=semi,%3B,dot,.,comma,%2C"),
+        ("{?keys*}", "?semi=%3B&dot=.&comma=%2C"),
+        ("{&var:3}", "&var=val"),
+        ("{&list}", "&list=red,green,blue"),
+        ("{&list*}", "&list=red&list=green&list=blue"),
+        ("{&keys}", "&keys=semi,%3B,dot,.,comma,%2C"),
+        ("{&keys*}", "&semi=%3B&dot=.&comma=%2C"),
+    ]);
+}
+
+#[test]
+fn undefined_variables_expand_to_nothing() {
+    assert_expansions(&[
+        ("{undef}", ""),
+        ("{/undef}", ""),
+        ("{?undef,x}", "?x=1024"),
+        ("X{.undef,var}", "X.value"),
+    ]);
+}
+
+fn strings(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Expanding string values and matching the result gives the values back
+fn assert_round_trip(template: &str, pairs: &[(&str, &str)]) {
+    let template = UriTemplate::parse(template).unwrap();
+    let values = strings(pairs);
+    let uri = template.expand_strings(&values);
+    assert_eq!(
+        template.match_uri(&uri),
+        Some(values),
+        "{} -> {}",
+        template,
+        uri
+    );
+}
+
+#[test]
+fn matched_uris_round_trip() {
+    assert_round_trip("file:///{path}", &[("path", "notes.txt")]);
+    assert_round_trip("file:///{+path}", &[("path", "docs/guide/intro.md")]);
+    assert_round_trip(
+        "repo://{owner}/{name}/issues/{number}",
+        &[("owner", "rust-lang"), ("name", "cargo"), ("number", "42")],
+    );
+    assert_round_trip(
+        "https://example.com/search{?q,lang,page}",
+        &[("q", "uri templates & more"), ("lang", "en"), ("page", "2")],
+    );
+    assert_round_trip(
+        "db://{table}{/id}{;fields}",
+        &[("table", "users"), ("id", "7"), ("fields", "name")],
+    );
+    assert_round_trip(
+        "weather://{city}/{date}",
+        &[("city", "São Paulo"), ("date", "2024-01-01")],
+    );
+    assert_round_trip(
+        "x{.ext}{#section}",
+        &[("ext", "tar"), ("section", "part 2")],
+    );
+}
+
+#[test]
+fn matching_omits_variables_that_expanded_to_nothing() {
+    let template = UriTemplate::parse("https://example.com/search{?q,lang,page}").unwrap();
+    assert_eq!(
+        template.match_uri("https://example.com/search?q=rust"),
+        Some(strings(&[("q", "rust")]))
+    );
+    assert_eq!(
+        template.match_uri("https://example.com/search"),
+        Some(HashMap::new())
+    );
+}
+
+#[test]
+fn matching_joins_lists_with_commas() {
+    let template = UriTemplate::parse("color{/list*}").unwrap();
+    assert_eq!(
+        template.match_uri("color/red/green/blue"),
+        Some(strings(&[("list", "red,green,blue")]))
+    );
+
+    let template = UriTemplate::parse("find{?list*}").unwrap();
+    assert_eq!(
+        template.match_uri("find?list=red&list=green"),
+        Some(strings(&[("list", "red,green")]))
+    );
+}
+
+#[test]
+fn matching_rejects_uris_the_template_cannot_produce() {
+    let template = UriTemplate::parse("repo://{owner}/{name}").unwrap();
+    assert_eq!(template.match_uri("repo://rust-lang"), None);
+    assert_eq!(template.match_uri("repo://rust-lang/cargo/extra"), None);
+    assert_eq!(template.match_uri("file:///rust-lang/cargo"), None);
+
+    // A repeated variable must have the same value everywhere
+    let template = UriTemplate::parse("{x}/{x}").unwrap();
+    assert_eq!(template.match_uri("a/a"), Some(strings(&[("x", "a")])));
+    assert_eq!(template.match_uri("a/b"), None);
+
+    // Neither occurrence may extend the other unless it was cut short by a prefix modifier
+    let template = UriTemplate::parse("x/{x}/{x}").unwrap();
+    assert_eq!(template.match_uri("x/a/ab"), None);
+    assert_eq!(template.match_uri("x/ab/a"), None);
+    let template = UriTemplate::parse("{x:2}/{x}").unwrap();
+    assert_eq!(template.match_uri("ab/abc"), Some(strings(&[("x", "abc")])));
+    assert_eq!(template.match_uri("a/abc"), None);
+    let template = UriTemplate::parse("{x}/{x:2}").unwrap();
+    assert_eq!(template.match_uri("abc/ab"), Some(strings(&[("x", "abc")])));
+    assert_eq!(template.match_uri("abc/ac"), None);
+
+    // Prefix modifiers bound the length of what they match
+    let template = UriTemplate::parse("{var:3}").unwrap();
+    assert_eq!(template.match_uri("val"), Some(strings(&[("var", "val")])));
+    assert_eq!(template.match_uri("value"), None);
+}
+
+#[test]
+fn matching_many_adjacent_expressions_does_not_backtrack_exponentially() {
+    let expressions: String = (0..24).map(|i| format!("{{v{}}}", i)).collect();
+    let template = UriTemplate::parse(&format!("{}x", expressions)).unwrap();
+    let uri = "a".repeat(48);
+
+    let started = Instant::now();
+    assert_eq!(template.match_uri(&uri), None);
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "took {:?}",
+        started.elapsed()
+    );
+
+    let matched = template.match_uri(&format!("{}x", uri)).unwrap();
+    assert_eq!(matched.values().map(String::len).sum::<usize>(), 48);
+}
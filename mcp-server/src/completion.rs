@@ -0,0 +1,162 @@
+// mcp-server/src/completion.rs
+//! Argument completion for resource templates and prompts
+use std::collections::HashMap;
+use std::future::Future;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use mcp_protocol::types::completion::CompletionResult;
+
+/// Most values a `completion/complete` response may carry, per the specification
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+/// What a [`CompletionProvider`] is asked to complete
+#[derive(Debug, Clone, Default)]
+pub struct CompletionQuery {
+    /// Name of the argument being completed
+    pub argument: String,
+    /// What the user has typed so far
+    pub value: String,
+    /// Arguments the client has already resolved, e.g. `owner` while completing `name`
+    pub arguments: HashMap<String, String>,
+}
+
+impl CompletionQuery {
+    /// Create a query with no resolved arguments
+    pub fn new(argument: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            argument: argument.into(),
+            value: value.into(),
+            arguments: HashMap::new(),
+        }
+    }
+
+    /// Set the arguments the client has already resolved
+    pub fn with_arguments(mut self, arguments: HashMap<String, String>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// The candidates starting with the typed value, see [`prefix_matches`]
+    pub fn prefix_matches<I, S>(&self, candidates: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        prefix_matches(candidates, &self.value)
+    }
+
+    /// The candidates matching the typed value, best first, see [`fuzzy_matches`]
+    pub fn fuzzy_matches<I, S>(&self, candidates: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        fuzzy_matches(candidates, &self.value)
+    }
+}
+
+/// Suggests values for the arguments of a resource template or prompt
+///
+/// Return every match, best first; the server caps the response at [`MAX_COMPLETION_VALUES`]
+/// and reports how many there were through `total` and `hasMore`.
+#[async_trait]
+pub trait CompletionProvider: Send + Sync + 'static {
+    async fn complete(&self, query: CompletionQuery) -> Result<Vec<String>>;
+}
+
+#[async_trait]
+impl<F, Fut> CompletionProvider for F
+where
+    F: Fn(CompletionQuery) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Vec<String>>> + Send + 'static,
+{
+    async fn complete(&self, query: CompletionQuery) -> Result<Vec<String>> {
+        self(query).await
+    }
+}
+
+/// The candidates starting with `value`, ignoring case, in their original order
+pub fn prefix_matches<I, S>(candidates: I, value: &str) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let value = value.to_lowercase();
+    candidates
+        .into_iter()
+        .map(Into::into)
+        .filter(|candidate| candidate.to_lowercase().starts_with(&value))
+        .collect()
+}
+
+/// The candidates containing the characters of `value` in order, ignoring case, best first
+///
+/// Exact matches rank first, then prefixes, then substrings (earlier is better), then
+/// scattered matches (tighter is better). Equally good candidates keep their original order.
+pub fn fuzzy_matches<I, S>(candidates: I, value: &str) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let value = value.to_lowercase();
+    let mut ranked = candidates
+        .into_iter()
+        .map(Into::into)
+        .filter_map(|candidate: String| {
+            fuzzy_score(&candidate.to_lowercase(), &value).map(|score| (score, candidate))
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by_key(|(score, _)| *score);
+    ranked.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+/// Rank of a lowercase candidate against a lowercase value; lower is better
+fn fuzzy_score(candidate: &str, value: &str) -> Option<(u8, usize)> {
+    if candidate == value {
+        return Some((0, 0));
+    }
+    if candidate.starts_with(value) {
+        return Some((1, 0));
+    }
+    if let Some(position) = candidate.find(value) {
+        return Some((2, position));
+    }
+
+    // Every character of the value in order, scored by the tightest span that covers them
+    let first = value.chars().next()?;
+    candidate
+        .char_indices()
+        .filter(|&(_, c)| c == first)
+        .filter_map(|(start, _)| scattered_end(&candidate[start..], value))
+        .min()
+        .map(|span| (3, span))
+}
+
+/// Byte offset in `text` of the last character of the earliest in-order match of `value`
+fn scattered_end(text: &str, value: &str) -> Option<usize> {
+    let mut wanted = value.chars().peekable();
+    for (index, c) in text.char_indices() {
+        if wanted.peek() == Some(&c) {
+            wanted.next();
+            if wanted.peek().is_none() {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+/// Build a completion result from every match, capped at [`MAX_COMPLETION_VALUES`]
+pub fn completion_result(mut values: Vec<String>) -> CompletionResult {
+    let total = values.len();
+    values.truncate(MAX_COMPLETION_VALUES);
+
+    CompletionResult {
+        values,
+        total: Some(total),
+        has_more: total > MAX_COMPLETION_VALUES,
+    }
+}
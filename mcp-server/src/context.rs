@@ -0,0 +1,96 @@
+// mcp-server/src/context.rs
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use mcp_protocol::messages::{Meta, RequestId};
+use mcp_protocol::types::ClientInfo;
+
+pub use tokio_util::sync::CancellationToken;
+
+static NEXT_SESSION: AtomicU64 = AtomicU64::new(1);
+
+/// The client connection a request arrived on
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// Identifier unique within this process
+    pub id: String,
+    /// Client information sent with `initialize`, if the client has initialized
+    pub client_info: Option<ClientInfo>,
+    /// Protocol version the client asked for in `initialize`
+    pub protocol_version: Option<String>,
+}
+
+impl Session {
+    /// Create a session for a connection that has not initialized yet
+    pub fn new() -> Self {
+        let n = NEXT_SESSION.fetch_add(1, Ordering::Relaxed);
+        Self {
+            id: format!("{}-{}", std::process::id(), n),
+            client_info: None,
+            protocol_version: None,
+        }
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Context handed to resource providers for a single read
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// URI being read
+    pub uri: String,
+    /// JSON-RPC id of the request, or None when the read did not come from a client
+    pub request_id: Option<RequestId>,
+    /// Session the request arrived on
+    pub session: Arc<Session>,
+    /// Cancelled when the client sends `notifications/cancelled` for this request
+    pub cancellation: CancellationToken,
+    /// The request's `_meta` object, if it has one
+    pub meta: Option<Meta>,
+}
+
+impl RequestContext {
+    /// Create a context for reading `uri` outside of a client request
+    pub fn new(uri: &str) -> Self {
+        Self {
+            uri: uri.to_string(),
+            request_id: None,
+            session: Arc::new(Session::new()),
+            cancellation: CancellationToken::new(),
+            meta: None,
+        }
+    }
+
+    /// Set the request id
+    pub fn with_request_id(mut self, request_id: RequestId) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
+
+    /// Set the session
+    pub fn with_session(mut self, session: Arc<Session>) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Set the cancellation token
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Set the request's `_meta` object
+    pub fn with_meta(mut self, meta: Meta) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+
+    /// Whether the client has cancelled this request
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+}
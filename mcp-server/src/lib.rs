@@ -4,12 +4,23 @@ pub mod transport;
 pub mod tools;
 pub mod resources;
 pub mod prompts;
+pub mod pagination;
+pub mod context;
+pub mod router;
+pub mod completion;
 mod completion_handler;
 mod resource_extensions;
 mod server_prompts;
 pub mod sampling;
 
+pub use completion::{CompletionProvider, CompletionQuery};
+pub use router::{HandlerResult, IncomingRequest, Middleware, Next, RequestHandler, Router};
 pub use server::{Server, ServerBuilder};
 pub use transport::Transport;
 
 pub use mcp_protocol;
+pub use schemars;
+pub use serde;
+
+#[cfg(feature = "macros")]
+pub use mcp_macros::{mcp_prompt, mcp_resource, mcp_tool};
@@ -0,0 +1,73 @@
+// mcp-server/src/pagination.rs
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use mcp_protocol::constants::error_codes;
+use mcp_protocol::messages::JsonRpcErrorObject;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+/// Number of items returned per page unless the server is configured otherwise
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Error returned when a client sends a cursor this server did not issue
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Invalid cursor: {cursor}")]
+pub struct InvalidCursor {
+    pub cursor: String,
+}
+
+/// Convert an error from listing a page into the JSON-RPC error to answer with
+///
+/// Cursors this server did not issue are the caller's fault and become `INVALID_PARAMS`;
+/// anything else is an internal error.
+pub(crate) fn list_error(err: anyhow::Error) -> JsonRpcErrorObject {
+    let code = if err.is::<InvalidCursor>() {
+        error_codes::INVALID_PARAMS
+    } else {
+        error_codes::INTERNAL_ERROR
+    };
+    JsonRpcErrorObject::new(code, &err.to_string())
+}
+
+/// Encode the key of the last item on a page as an opaque cursor
+pub fn encode_cursor(key: &str) -> String {
+    URL_SAFE_NO_PAD.encode(key.as_bytes())
+}
+
+/// Decode a cursor produced by [`encode_cursor`] back into the key it points after
+pub fn decode_cursor(cursor: &str) -> Result<String> {
+    let invalid = || anyhow!(InvalidCursor {
+        cursor: cursor.to_string(),
+    });
+
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Take one page of values from a map ordered by key
+///
+/// The cursor names the last key of the previous page rather than a position, so it stays
+/// valid when entries are added or removed between requests, including the entry it names.
+/// An empty cursor is treated as a request for the first page.
+pub fn paginate<'a, V>(
+    entries: &'a BTreeMap<String, V>,
+    cursor: Option<&str>,
+    page_size: usize,
+) -> Result<(Vec<&'a V>, Option<String>)> {
+    let start = match cursor {
+        Some(cursor) if !cursor.is_empty() => Bound::Excluded(decode_cursor(cursor)?),
+        _ => Bound::Unbounded,
+    };
+
+    let mut remaining = entries.range::<String, _>((start, Bound::Unbounded));
+    let page: Vec<(&String, &V)> = remaining.by_ref().take(page_size.max(1)).collect();
+
+    // Only hand out a cursor if there is something after this page
+    let next_cursor = match (page.last(), remaining.next()) {
+        (Some((last, _)), Some(_)) => Some(encode_cursor(last)),
+        _ => None,
+    };
+
+    Ok((page.into_iter().map(|(_, value)| value).collect(), next_cursor))
+}
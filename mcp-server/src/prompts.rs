@@ -1,26 +1,91 @@
 // mcp-server/src/prompts.rs
 use anyhow::{anyhow, Result};
-use mcp_protocol::types::prompt::{Prompt, PromptGetResult, PromptMessage};
-use std::collections::HashMap;
-use std::sync::{RwLock};
+use mcp_protocol::messages::Meta;
+use mcp_protocol::types::prompt::{Prompt, PromptArgument, PromptGetResult, PromptMessage};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
 use tokio::sync::broadcast;
 
-/// Handler type for generating prompt messages
-pub type PromptHandler = Box<dyn Fn(Option<HashMap<String, String>>) -> Result<Vec<PromptMessage>> + Send + Sync>;
+use crate::completion::{CompletionProvider, CompletionQuery};
+use crate::pagination;
 
-/// Handler type for generating parameter completions
-pub type CompletionHandler = Box<dyn Fn(String, Option<String>) -> Result<Vec<String>> + Send + Sync>;
+/// Handler type for generating prompt messages from the arguments and the request's `_meta`
+pub type PromptHandler = Box<dyn Fn(Option<HashMap<String, String>>, Option<&Meta>) -> Result<Vec<PromptMessage>> + Send + Sync>;
+
+/// Completion providers for one prompt, by parameter name
+type ParameterCompletions = HashMap<String, Arc<dyn CompletionProvider>>;
+
+/// A prompt paired with its handler, ready to be registered with a [`PromptManager`]
+///
+/// This is what the `#[mcp_prompt]` attribute generates.
+pub struct PromptDefinition {
+    pub prompt: Prompt,
+    pub handler: PromptHandler,
+}
+
+impl PromptDefinition {
+    /// Create a new prompt definition
+    pub fn new(
+        name: &str,
+        description: Option<&str>,
+        arguments: Vec<PromptArgument>,
+        handler: impl Fn(Option<HashMap<String, String>>) -> Result<Vec<PromptMessage>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            prompt: Prompt {
+                name: name.to_string(),
+                description: description.map(|s| s.to_string()),
+                arguments: if arguments.is_empty() { None } else { Some(arguments) },
+                annotations: None,
+                title: None,
+            },
+            handler: Box::new(move |arguments, _| handler(arguments)),
+        }
+    }
+
+    /// Set the human-readable name shown in place of the prompt's name
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.prompt.title = Some(title.to_string());
+        self
+    }
+}
+
+/// Parse a single prompt argument into `T`, returning `None` if it was not supplied
+pub fn parse_argument<T>(arguments: &HashMap<String, String>, name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match arguments.get(name) {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|err| anyhow!("Invalid value for argument {}: {}", name, err)),
+        None => Ok(None),
+    }
+}
+
+/// Parse a prompt argument that must be supplied
+pub fn require_argument<T>(arguments: &HashMap<String, String>, name: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_argument(arguments, name)?.ok_or_else(|| anyhow!("Missing required argument: {}", name))
+}
 
 /// Manages prompts for the MCP server
 pub struct PromptManager {
-    /// Map of prompt name to prompt definition
-    prompts: RwLock<HashMap<String, Prompt>>,
+    /// Map of prompt name to prompt definition, ordered by name for stable paging
+    prompts: RwLock<BTreeMap<String, Prompt>>,
     
     /// Map of prompt name to prompt handler
     handlers: RwLock<HashMap<String, PromptHandler>>,
     
-    /// Map of prompt name to parameter completion handlers
-    completion_handlers: RwLock<HashMap<String, HashMap<String, CompletionHandler>>>,
+    /// Map of prompt name to parameter completion providers
+    completion_handlers: RwLock<HashMap<String, ParameterCompletions>>,
     
     /// Sender for update notifications
     update_tx: broadcast::Sender<()>,
@@ -32,7 +97,7 @@ impl PromptManager {
         let (update_tx, _) = broadcast::channel(100);
         
         Self {
-            prompts: RwLock::new(HashMap::new()),
+            prompts: RwLock::new(BTreeMap::new()),
             handlers: RwLock::new(HashMap::new()),
             completion_handlers: RwLock::new(HashMap::new()),
             update_tx,
@@ -45,6 +110,27 @@ impl PromptManager {
         prompt: Prompt,
         handler: impl Fn(Option<HashMap<String, String>>) -> Result<Vec<PromptMessage>> + Send + Sync + 'static,
     ) {
+        self.register_definition(PromptDefinition {
+            prompt,
+            handler: Box::new(move |arguments, _| handler(arguments)),
+        });
+    }
+    
+    /// Register a prompt whose handler is also given the request's `_meta`
+    pub fn register_prompt_with_meta(
+        &self,
+        prompt: Prompt,
+        handler: impl Fn(Option<HashMap<String, String>>, Option<&Meta>) -> Result<Vec<PromptMessage>> + Send + Sync + 'static,
+    ) {
+        self.register_definition(PromptDefinition {
+            prompt,
+            handler: Box::new(handler),
+        });
+    }
+    
+    /// Register a prompt definition, such as one generated by `#[mcp_prompt]`
+    pub fn register_definition(&self, definition: PromptDefinition) {
+        let PromptDefinition { prompt, handler } = definition;
         let name = prompt.name.clone();
         
         // Add prompt to registry
@@ -56,7 +142,7 @@ impl PromptManager {
         // Add handler to registry
         {
             let mut handlers = self.handlers.write().unwrap();
-            handlers.insert(name, Box::new(handler));
+            handlers.insert(name, handler);
         }
         
         // Notify of update
@@ -68,79 +154,59 @@ impl PromptManager {
         &self,
         prompt_name: &str,
         param_name: &str,
-        handler: impl Fn(String, Option<String>) -> Result<Vec<String>> + Send + Sync + 'static,
+        provider: impl CompletionProvider,
     ) {
         let mut completion_handlers = self.completion_handlers.write().unwrap();
         
         // Get or create the map for this prompt
         let prompt_completions = completion_handlers
             .entry(prompt_name.to_string())
-            .or_insert_with(HashMap::new);
+            .or_default();
             
-        // Register the handler for this parameter
-        prompt_completions.insert(param_name.to_string(), Box::new(handler));
+        // Register the provider for this parameter
+        prompt_completions.insert(param_name.to_string(), Arc::new(provider));
     }
     
-    /// Get completions for a prompt parameter
+    /// Get completions for a prompt parameter, empty if the parameter has no provider
     pub async fn get_completions(
         &self,
         prompt_name: &str,
-        param_name: &str,
-        value: Option<String>,
+        query: CompletionQuery,
     ) -> Result<Vec<String>> {
-        let completion_handlers = self.completion_handlers.read().unwrap();
+        let provider = self
+            .completion_handlers
+            .read()
+            .unwrap()
+            .get(prompt_name)
+            .and_then(|prompt_completions| prompt_completions.get(&query.argument))
+            .cloned();
         
-        // Check if we have any completion handlers for this prompt
-        if let Some(prompt_completions) = completion_handlers.get(prompt_name) {
-            // Check if we have a handler for this parameter
-            if let Some(handler) = prompt_completions.get(param_name) {
-                // Call the handler
-                return handler(param_name.to_string(), value);
-            }
+        match provider {
+            Some(provider) => provider.complete(query).await,
+            None => Ok(Vec::new()),
         }
-        
-        // If we don't have a handler, return empty results
-        Ok(Vec::new())
     }
     
-    /// List all registered prompts with optional pagination
-    pub async fn list_prompts(&self, cursor: Option<String>) -> (Vec<Prompt>, Option<String>) {
+    /// List all registered prompts with pagination
+    pub async fn list_prompts(&self, cursor: Option<String>, page_size: usize) -> Result<(Vec<Prompt>, Option<String>)> {
         let prompts = self.prompts.read().unwrap();
+        let (page, next_cursor) = pagination::paginate(&prompts, cursor.as_deref(), page_size)?;
         
-        // Get all prompts in a vector
-        let mut prompt_list: Vec<Prompt> = prompts.values().cloned().collect();
-        
-        // Sort by name for consistent ordering
-        prompt_list.sort_by(|a, b| a.name.cmp(&b.name));
-        
-        // Simple pagination implementation
-        if let Some(cursor) = cursor {
-            if !cursor.is_empty() {
-                // Skip items before the cursor
-                prompt_list = prompt_list
-                    .into_iter()
-                    .skip_while(|p| p.name != cursor)
-                    .skip(1) // Skip the cursor item itself
-                    .collect();
-            }
-        }
-        
-        // For simplicity, we'll return at most 50 items per page
-        let page_size = 50;
-        let next_cursor = if prompt_list.len() > page_size {
-            // If we have more than page_size, return the next cursor
-            prompt_list[page_size - 1].name.clone()
-        } else {
-            // No more pages
-            return (prompt_list, None);
-        };
-        
-        // Return the current page and the next cursor
-        (prompt_list.into_iter().take(page_size).collect(), Some(next_cursor))
+        Ok((page.into_iter().cloned().collect(), next_cursor))
     }
     
     /// Get a prompt by name and generate its content with the provided arguments
     pub async fn get_prompt(&self, name: &str, arguments: Option<HashMap<String, String>>) -> Result<PromptGetResult> {
+        self.get_prompt_with_meta(name, arguments, None).await
+    }
+    
+    /// Get a prompt by name, handing the request's `_meta` to its handler
+    pub async fn get_prompt_with_meta(
+        &self,
+        name: &str,
+        arguments: Option<HashMap<String, String>>,
+        meta: Option<&Meta>,
+    ) -> Result<PromptGetResult> {
         // Get prompt definition
         let prompt = {
             let prompts = self.prompts.read().unwrap();
@@ -155,7 +221,7 @@ impl PromptManager {
             let handlers = self.handlers.read().unwrap();
             if let Some(handler) = handlers.get(name) {
                 // Execute handler
-                handler(arguments.clone())?                
+                handler(arguments.clone(), meta)?                
             } else {
                 return Err(anyhow!("Handler not found for prompt: {}", name));
             }
@@ -165,6 +231,7 @@ impl PromptManager {
         let result = PromptGetResult {
             description: prompt.description,
             messages,
+            meta: None,
         };
         
         Ok(result)
@@ -254,4 +321,10 @@ impl PromptManager {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl Default for PromptManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
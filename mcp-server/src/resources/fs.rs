@@ -0,0 +1,404 @@
+// mcp-server/src/resources/fs.rs
+//! Expose a directory tree as `file://` resources
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use mcp_protocol::types::resource::{Resource, ResourceContent};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::io::AsyncReadExt;
+use walkdir::WalkDir;
+
+use super::{ResourceManager, ResourceNotFound};
+
+/// Bytes read from the start of a file to sniff its MIME type
+const SNIFF_LEN: usize = 8192;
+
+/// Largest file served unless [`FileSystemProvider::with_max_file_size`] says otherwise
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Serves the files under a root directory as `file://` resources
+///
+/// Only regular files inside the root are served: URIs containing `..` and symlinks that
+/// lead outside the root are rejected, and reading a file larger than the maximum size fails.
+/// Include and exclude globs are matched against paths relative to the root, using `/` as the
+/// separator.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use modelcontextprotocol_server::resources::{fs::FileSystemProvider, ResourceManager};
+/// # fn main() -> anyhow::Result<()> {
+/// let manager = Arc::new(ResourceManager::new());
+/// let _watcher = FileSystemProvider::new("./docs")?
+///     .with_include("**/*.md")
+///     .with_exclude("drafts/**")
+///     .watch(manager.clone())?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FileSystemProvider {
+    root: PathBuf,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    max_file_size: u64,
+}
+
+impl FileSystemProvider {
+    /// Create a provider for the directory at `root`
+    pub fn new(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref();
+        let root = root
+            .canonicalize()
+            .with_context(|| format!("Cannot open resource root {}", root.display()))?;
+        if !root.is_dir() {
+            return Err(anyhow!("Resource root {} is not a directory", root.display()));
+        }
+
+        Ok(Self {
+            root,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        })
+    }
+
+    /// Only serve files matching `pattern`; with no include globs every file is served
+    pub fn with_include(mut self, pattern: &str) -> Self {
+        self.include.push(pattern.to_string());
+        self
+    }
+
+    /// Never serve files matching `pattern`, even if an include glob matches them
+    pub fn with_exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(pattern.to_string());
+        self
+    }
+
+    /// Fail reads of files larger than `bytes` instead of loading them into memory
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// The canonical root directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Register every file currently under the root, returning how many were registered
+    pub fn register(&self, manager: &Arc<ResourceManager>) -> Result<usize> {
+        let tree = self.tree()?;
+        Ok(tree.scan(&tree.root, manager))
+    }
+
+    /// Register every file under the root and keep the resources in sync with the directory
+    ///
+    /// Changed files are announced to subscribers with `notifications/resources/updated`; added and
+    /// removed files with `notifications/resources/list_changed`. Watching stops when the returned
+    /// watcher is dropped.
+    pub fn watch(&self, manager: Arc<ResourceManager>) -> Result<FileSystemWatcher> {
+        let tree = self.tree()?;
+        tree.scan(&tree.root, &manager);
+
+        let handler_tree = tree.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            match event {
+                Ok(event) => handler_tree.apply(event, &manager),
+                Err(err) => tracing::warn!("File watch error: {}", err),
+            }
+        })?;
+        watcher.watch(&tree.root, RecursiveMode::Recursive)?;
+
+        Ok(FileSystemWatcher { _watcher: watcher })
+    }
+
+    fn tree(&self) -> Result<Arc<Tree>> {
+        Ok(Arc::new(Tree {
+            root: self.root.clone(),
+            include: build_globs(&self.include)?,
+            exclude: build_globs(&self.exclude)?,
+            max_file_size: self.max_file_size,
+            known: Mutex::new(BTreeSet::new()),
+        }))
+    }
+}
+
+/// Keeps a [`FileSystemProvider`]'s resources in sync with the directory while alive
+pub struct FileSystemWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Compiled filters and the URIs registered so far, shared with the watcher
+struct Tree {
+    root: PathBuf,
+    include: Option<GlobSet>, // None means every file is included
+    exclude: Option<GlobSet>,
+    max_file_size: u64,
+    known: Mutex<BTreeSet<String>>,
+}
+
+impl Tree {
+    /// Path relative to the root with `/` separators, or None if the path is outside it
+    fn relative(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let parts: Vec<_> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+        Some(parts.join("/"))
+    }
+
+    fn accepts(&self, relative: &str) -> bool {
+        let included = self.include.as_ref().is_none_or(|globs| globs.is_match(relative));
+        let excluded = self.exclude.as_ref().is_some_and(|globs| globs.is_match(relative));
+        included && !excluded
+    }
+
+    /// Resolve a path to the file it names, if it is a servable file inside the root
+    fn servable(&self, path: &Path) -> Option<PathBuf> {
+        let relative = self.relative(path)?;
+        if relative.is_empty() || !self.accepts(&relative) {
+            return None;
+        }
+
+        // Symlinks may point anywhere; only follow them if they stay inside the root
+        let target = path.canonicalize().ok()?;
+        (target.starts_with(&self.root) && target.is_file()).then_some(target)
+    }
+
+    /// Map a URI back to the file it names, rejecting anything outside the root
+    fn resolve(&self, uri: &str) -> Result<PathBuf> {
+        let path = uri
+            .strip_prefix("file://")
+            .and_then(percent_decode)
+            .ok_or_else(|| anyhow!("Not a file URI: {}", uri))?;
+        let path = Path::new(&path);
+
+        if path.components().any(|c| matches!(c, Component::ParentDir)) {
+            return Err(anyhow!("Path traversal rejected: {}", uri));
+        }
+
+        self.servable(path).ok_or_else(|| {
+            ResourceNotFound {
+                uri: uri.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Register every servable file under `dir`, returning how many were new
+    fn scan(self: &Arc<Self>, dir: &Path, manager: &ResourceManager) -> usize {
+        let mut added = 0;
+        let walk = WalkDir::new(dir)
+            .follow_links(true)
+            .into_iter()
+            // Don't descend into directories that symlinks lead to outside the root
+            .filter_entry(|entry| {
+                !(entry.path_is_symlink() && entry.file_type().is_dir())
+                    || entry
+                        .path()
+                        .canonicalize()
+                        .is_ok_and(|target| target.starts_with(&self.root))
+            });
+        for entry in walk.flatten() {
+            if entry.file_type().is_file() && self.register(entry.path(), manager) {
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Register or refresh the resource for `path`, returning true if it was not known before
+    fn register(self: &Arc<Self>, path: &Path, manager: &ResourceManager) -> bool {
+        let Some(target) = self.servable(path) else {
+            return false;
+        };
+        let Some(relative) = self.relative(path) else {
+            return false;
+        };
+
+        let uri = file_uri(path);
+        let resource = Resource {
+            uri: uri.clone(),
+            name: relative,
+            description: None,
+            mime_type: Some(sniff_file(&target)),
+            size: target.metadata().ok().map(|metadata| metadata.len()),
+            annotations: None,
+            title: None,
+        };
+
+        let tree = self.clone();
+        manager.register_async_resource(resource, move |context| {
+            let tree = tree.clone();
+            async move { tree.read(&context.uri).await }
+        });
+
+        self.known.lock().unwrap().insert(uri)
+    }
+
+    /// Remove the resource for `path` and any resources below it
+    fn remove(&self, path: &Path, manager: &ResourceManager) {
+        let uri = file_uri(path);
+        let prefix = format!("{}/", uri);
+
+        let mut known = self.known.lock().unwrap();
+        let removed: Vec<String> = known
+            .iter()
+            .filter(|known_uri| **known_uri == uri || known_uri.starts_with(&prefix))
+            .cloned()
+            .collect();
+        for removed_uri in &removed {
+            known.remove(removed_uri);
+            let _ = manager.remove_resource(removed_uri);
+        }
+    }
+
+    /// Bring the resources for the paths in a watch event up to date
+    ///
+    /// Registering and removing resources notifies list change subscribers; changes to files
+    /// that were already registered are announced as content updates.
+    fn apply(self: &Arc<Self>, event: Event, manager: &ResourceManager) {
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+
+        for path in &event.paths {
+            if path.is_dir() {
+                self.scan(path, manager);
+            } else if self.servable(path).is_some() {
+                if !self.register(path, manager) {
+                    manager.notify_updated(&file_uri(path));
+                }
+            } else {
+                self.remove(path, manager);
+            }
+        }
+    }
+
+    async fn read(&self, uri: &str) -> Result<Vec<ResourceContent>> {
+        let path = self.resolve(uri)?;
+        let file = tokio::fs::File::open(&path)
+            .await
+            .with_context(|| format!("Cannot read {}", uri))?;
+
+        // Read one byte past the limit to tell a file at the limit from one over it
+        let mut bytes = Vec::new();
+        file.take(self.max_file_size.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .await
+            .with_context(|| format!("Cannot read {}", uri))?;
+        if bytes.len() as u64 > self.max_file_size {
+            return Err(anyhow!(
+                "{} is larger than the {} byte limit",
+                uri,
+                self.max_file_size
+            ));
+        }
+
+        let head = &bytes[..bytes.len().min(SNIFF_LEN)];
+        let mime_type = sniff_mime(&path, head, bytes.len() > SNIFF_LEN);
+        let (text, blob) = match String::from_utf8(bytes) {
+            Ok(text) if !text.contains('\0') => (Some(text), None),
+            Ok(text) => (None, Some(base64::engine::general_purpose::STANDARD.encode(text))),
+            Err(err) => (
+                None,
+                Some(base64::engine::general_purpose::STANDARD.encode(err.into_bytes())),
+            ),
+        };
+
+        Ok(vec![ResourceContent {
+            uri: uri.to_string(),
+            mime_type,
+            text,
+            blob,
+            meta: None,
+        }])
+    }
+}
+
+fn build_globs(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| format!("Invalid glob {}", pattern))?);
+    }
+    Ok(Some(builder.build()?))
+}
+
+/// Guess a file's MIME type from its extension, falling back to its leading bytes
+fn sniff_file(path: &Path) -> String {
+    // One byte more than is sniffed tells whether the head is the whole file
+    let mut head = Vec::with_capacity(SNIFF_LEN + 1);
+    if let Ok(file) = std::fs::File::open(path) {
+        let _ = file.take(SNIFF_LEN as u64 + 1).read_to_end(&mut head);
+    }
+    let truncated = head.len() > SNIFF_LEN;
+    head.truncate(SNIFF_LEN);
+    sniff_mime(path, &head, truncated)
+}
+
+/// `truncated` says whether the file continues past `head`
+fn sniff_mime(path: &Path, head: &[u8], truncated: bool) -> String {
+    if let Some(mime_type) = mime_guess::from_path(path).first_raw() {
+        return mime_type.to_string();
+    }
+    if let Some(kind) = infer::get(head) {
+        return kind.mime_type().to_string();
+    }
+    if looks_like_text(head, truncated) {
+        "text/plain".to_string()
+    } else {
+        "application/octet-stream".to_string()
+    }
+}
+
+/// Whether the leading bytes of a file look like UTF-8 text
+fn looks_like_text(head: &[u8], truncated: bool) -> bool {
+    if head.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        // A multi-byte character cut off at the end of the sniffed bytes is fine, but not at
+        // the end of the file
+        Err(err) => truncated && err.error_len().is_none(),
+    }
+}
+
+/// The `file://` URI for an absolute path
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
@@ -1,42 +1,148 @@
 // mcp-server/src/resources/mod.rs
-use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 use anyhow::{anyhow, Result};
-use tokio::sync::{RwLock, broadcast};
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+use tokio::sync::broadcast;
 use mcp_protocol::types::resource::{
     Resource, ResourceContent, ResourceTemplate
 };
-use mcp_protocol::types::completion::CompletionItem;
+use mcp_protocol::uri_template::UriTemplate;
 
-const DEFAULT_PAGE_SIZE: usize = 50;
+#[cfg(feature = "fs")]
+pub mod fs;
+
+use crate::completion::{CompletionProvider, CompletionQuery};
+use crate::context::RequestContext;
+use crate::pagination;
 
 /// Resource content provider function type
 pub type ResourceContentProvider = Arc<dyn Fn() -> Result<Vec<ResourceContent>> + Send + Sync>;
 
-/// Template completion provider function type
-pub type TemplateCompletionProvider = Arc<dyn Fn(String, String, Option<String>) -> Result<Vec<CompletionItem>> + Send + Sync>;
+/// Stream of resource contents, pulled one item at a time while a read is served
+pub type ResourceContentStream = BoxStream<'static, Result<ResourceContent>>;
+
+/// Async resource content provider function type
+pub type AsyncResourceContentProvider =
+    Arc<dyn Fn(RequestContext) -> BoxFuture<'static, Result<ResourceContentStream>> + Send + Sync>;
+
+/// Template read handler type, called with the variables extracted from the requested URI
+pub type TemplateReadHandler = Arc<
+    dyn Fn(RequestContext, HashMap<String, String>) -> BoxFuture<'static, Result<ResourceContentStream>>
+        + Send
+        + Sync,
+>;
+
+/// Error returned when no resource or template matches a URI
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Resource not found: {uri}")]
+pub struct ResourceNotFound {
+    pub uri: String,
+}
+
+/// Error returned when a read is cancelled before its contents are complete
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Read of {uri} was cancelled")]
+pub struct ResourceReadCancelled {
+    pub uri: String,
+}
 
-/// Template expansion function type
-pub type TemplateExpanderFn = Arc<dyn Fn(String, HashMap<String, String>) -> Result<String> + Send + Sync>;
+/// A registered template with its parsed form and read handler
+struct TemplateEntry {
+    template: ResourceTemplate,
+    parsed: Option<UriTemplate>, // None if the template is not valid RFC 6570
+    handler: TemplateReadHandler,
+}
+
+/// A resource paired with its content provider, ready to be registered with a [`ResourceManager`]
+///
+/// This is what the `#[mcp_resource]` attribute generates.
+pub struct ResourceDefinition {
+    pub resource: Resource,
+    pub content_provider: AsyncResourceContentProvider,
+}
+
+impl ResourceDefinition {
+    /// Create a new resource definition
+    pub fn new(
+        uri: &str,
+        name: &str,
+        description: Option<&str>,
+        mime_type: Option<&str>,
+        content_provider: impl Fn() -> Result<Vec<ResourceContent>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            resource: Resource {
+                uri: uri.to_string(),
+                name: name.to_string(),
+                description: description.map(|s| s.to_string()),
+                mime_type: mime_type.map(|s| s.to_string()),
+                size: None,
+                annotations: None,
+                title: None,
+            },
+            content_provider: from_sync(content_provider),
+        }
+    }
+
+    /// Set the human-readable name shown in place of the resource's name
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.resource.title = Some(title.to_string());
+        self
+    }
+}
+
+/// Turn contents that are already in memory into a content stream
+pub fn content_stream(contents: Vec<ResourceContent>) -> ResourceContentStream {
+    stream::iter(contents.into_iter().map(Ok)).boxed()
+}
+
+/// Adapt a synchronous provider to the async provider type
+fn from_sync(
+    provider: impl Fn() -> Result<Vec<ResourceContent>> + Send + Sync + 'static,
+) -> AsyncResourceContentProvider {
+    let provider = Arc::new(provider);
+    Arc::new(move |_| {
+        let provider = provider.clone();
+        spawn_provider(move || provider())
+    })
+}
+
+/// Run a synchronous provider on the blocking thread pool so a slow read can't stall other requests
+fn spawn_provider(
+    provider: impl FnOnce() -> Result<Vec<ResourceContent>> + Send + 'static,
+) -> BoxFuture<'static, Result<ResourceContentStream>> {
+    async move {
+        let contents = tokio::task::spawn_blocking(provider).await??;
+        Ok(content_stream(contents))
+    }
+    .boxed()
+}
 
 /// Resource manager for registering and accessing resources
 pub struct ResourceManager {
-    resources: Arc<RwLock<HashMap<String, (Resource, ResourceContentProvider)>>>,
-    templates: Arc<RwLock<HashMap<String, (ResourceTemplate, TemplateExpanderFn)>>>,
+    resources: Arc<RwLock<BTreeMap<String, (Resource, AsyncResourceContentProvider)>>>, // Ordered by URI for stable paging
+    templates: Arc<RwLock<BTreeMap<String, TemplateEntry>>>, // Ordered by URI template
     subscriptions: Arc<RwLock<HashMap<String, HashSet<String>>>>, // Maps resource URI to set of client IDs
     update_tx: broadcast::Sender<String>, // Channel for notifying resource updates
-    completion_providers: Arc<RwLock<HashMap<String, TemplateCompletionProvider>>>,
+    list_changed_tx: broadcast::Sender<()>, // Channel for notifying changes to the set of resources
+    completion_providers: Arc<RwLock<HashMap<String, Arc<dyn CompletionProvider>>>>,
 }
 
 impl ResourceManager {
     /// Create a new resource manager
     pub fn new() -> Self {
         let (update_tx, _) = broadcast::channel(100);
+        let (list_changed_tx, _) = broadcast::channel(100);
         Self {
-            resources: Arc::new(RwLock::new(HashMap::new())),
-            templates: Arc::new(RwLock::new(HashMap::new())),
+            resources: Arc::new(RwLock::new(BTreeMap::new())),
+            templates: Arc::new(RwLock::new(BTreeMap::new())),
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
             update_tx,
+            list_changed_tx,
             completion_providers: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -47,158 +153,278 @@ impl ResourceManager {
         resource: Resource, 
         content_provider: impl Fn() -> Result<Vec<ResourceContent>> + Send + Sync + 'static
     ) {
-        let resources = self.resources.clone();
-        let content_provider = Arc::new(content_provider);
-        
-        tokio::spawn(async move {
-            let mut resources = resources.write().await;
-            resources.insert(resource.uri.clone(), (resource, content_provider));
+        self.register_definition(ResourceDefinition {
+            resource,
+            content_provider: from_sync(content_provider),
         });
     }
     
-    /// Get registered resources with pagination
-    pub async fn list_resources(&self, cursor: Option<String>) -> (Vec<Resource>, Option<String>) {
-        let resources = self.resources.read().await;
-        let all_resources: Vec<Resource> = resources.values().map(|(resource, _)| resource.clone()).collect();
+    /// Register a resource whose contents are produced by an async provider
+    pub fn register_async_resource<F, Fut>(&self, resource: Resource, provider: F)
+    where
+        F: Fn(RequestContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<ResourceContent>>> + Send + 'static,
+    {
+        self.register_definition(ResourceDefinition {
+            resource,
+            content_provider: Arc::new(move |context| {
+                provider(context)
+                    .map(|contents| contents.map(content_stream))
+                    .boxed()
+            }),
+        });
+    }
+    
+    /// Register a resource whose contents are produced by a stream
+    ///
+    /// The stream is pulled one item at a time while the read is served and dropped as soon as
+    /// the read is cancelled, so no items are produced for a client that stopped waiting. Every
+    /// item still goes into the single `resources/read` response.
+    pub fn register_streaming_resource<F, Fut>(&self, resource: Resource, provider: F)
+    where
+        F: Fn(RequestContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ResourceContentStream>> + Send + 'static,
+    {
+        self.register_definition(ResourceDefinition {
+            resource,
+            content_provider: Arc::new(move |context| provider(context).boxed()),
+        });
+    }
+    
+    /// Register a resource definition, such as one generated by `#[mcp_resource]`
+    ///
+    /// Subscribers to list changes are notified if no resource had this URI before.
+    pub fn register_definition(&self, definition: ResourceDefinition) {
+        let ResourceDefinition { resource, content_provider } = definition;
         
-        // If we have a cursor, find its position
-        let start_pos = match cursor {
-            Some(cursor) => {
-                // Find the index of the resource after the cursor
-                let pos = all_resources.iter().position(|r| r.uri == cursor);
-                pos.map(|p| p + 1).unwrap_or(0)
-            },
-            None => 0,
+        let added = {
+            let mut resources = self.resources.write().unwrap();
+            resources.insert(resource.uri.clone(), (resource, content_provider)).is_none()
         };
         
-        // Get a page of resources
-        let end_pos = std::cmp::min(start_pos + DEFAULT_PAGE_SIZE, all_resources.len());
-        let page = all_resources[start_pos..end_pos].to_vec();
+        if added {
+            let _ = self.list_changed_tx.send(());
+        }
+    }
+    
+    /// Remove a resource, returning its definition
+    ///
+    /// Subscriptions to the resource are dropped and subscribers to list changes are notified.
+    pub fn remove_resource(&self, uri: &str) -> Result<Resource> {
+        let (resource, _) = self
+            .resources
+            .write()
+            .unwrap()
+            .remove(uri)
+            .ok_or_else(|| anyhow!("Resource not found: {}", uri))?;
         
-        // Set the next cursor if there are more resources
-        let next_cursor = if end_pos < all_resources.len() {
-            Some(all_resources[end_pos - 1].uri.clone())
-        } else {
-            None
-        };
+        self.subscriptions.write().unwrap().remove(uri);
         
-        (page, next_cursor)
+        // Notify of list change
+        let _ = self.list_changed_tx.send(());
+        Ok(resource)
+    }
+    
+    /// Get registered resources with pagination
+    pub async fn list_resources(&self, cursor: Option<String>, page_size: usize) -> Result<(Vec<Resource>, Option<String>)> {
+        let resources = self.resources.read().unwrap();
+        let (page, next_cursor) = pagination::paginate(&resources, cursor.as_deref(), page_size)?;
+        
+        Ok((page.into_iter().map(|(resource, _)| resource.clone()).collect(), next_cursor))
     }
     
     /// Get a specific resource's content
     pub async fn get_resource_content(&self, uri: &str) -> Result<Vec<ResourceContent>> {
+        self.read_resource(RequestContext::new(uri)).await
+    }
+    
+    /// Read the resource named by `context.uri`
+    ///
+    /// Fails with [`ResourceNotFound`] if no resource or template matches the URI, and with
+    /// [`ResourceReadCancelled`] if the context is cancelled before every content item has
+    /// been produced.
+    pub async fn read_resource(&self, context: RequestContext) -> Result<Vec<ResourceContent>> {
+        let cancellation = context.cancellation.clone();
+        let uri = context.uri.clone();
+        let read = self.open_resource(context)?;
+        
+        let cancelled = || anyhow::Error::new(ResourceReadCancelled { uri: uri.clone() });
+        let mut contents_stream = tokio::select! {
+            _ = cancellation.cancelled() => return Err(cancelled()),
+            stream = read => stream?,
+        };
+        
+        let mut contents = Vec::new();
+        loop {
+            tokio::select! {
+                _ = cancellation.cancelled() => return Err(cancelled()),
+                next = contents_stream.next() => match next {
+                    Some(content) => contents.push(content?),
+                    None => return Ok(contents),
+                },
+            }
+        }
+    }
+    
+    /// Start a read, cloning the provider out of the registry so no lock is held while it runs
+    fn open_resource(&self, context: RequestContext) -> Result<BoxFuture<'static, Result<ResourceContentStream>>> {
         // First check if this is a direct resource
-        let resources = self.resources.read().await;
-        if let Some((_, content_provider)) = resources.get(uri) {
-            return content_provider();
+        let provider = {
+            let resources = self.resources.read().unwrap();
+            resources.get(&context.uri).map(|(_, provider)| provider.clone())
+        };
+        if let Some(provider) = provider {
+            return Ok(provider(context));
         }
         
-        // If not a direct resource, check if it matches a template
-        let templates = self.templates.read().await;
-        for (template_uri, (_, _expander)) in templates.iter() {
-            // Check if the URI could be from this template (simple prefix check)
-            // In a real implementation, you'd want a more sophisticated matching algorithm
-            if uri.starts_with(template_uri.split('{').next().unwrap_or("")) {
-                // Try to find a resource provider for the expanded URI
-                if let Some((_, content_provider)) = resources.get(uri) {
-                    return content_provider();
-                }
-            }
+        // If not a direct resource, read it through the first template that matches
+        let matched = {
+            let templates = self.templates.read().unwrap();
+            templates.values().find_map(|entry| {
+                let params = entry.parsed.as_ref()?.match_uri(&context.uri)?;
+                Some((entry.handler.clone(), params))
+            })
+        };
+        if let Some((handler, params)) = matched {
+            return Ok(handler(context, params));
         }
         
-        Err(anyhow!("Resource not found: {}", uri))
+        Err(ResourceNotFound { uri: context.uri }.into())
     }
     
-    /// Register a template
+    /// Register a template whose resources are read through `handler`
+    ///
+    /// When a requested URI matches the template and no concrete resource has that URI, the
+    /// handler is called with the variables extracted from the URI.
     pub fn register_template(
         &self,
         template: ResourceTemplate,
-        expander: impl Fn(String, HashMap<String, String>) -> Result<String> + Send + Sync + 'static,
+        handler: impl Fn(HashMap<String, String>) -> Result<Vec<ResourceContent>> + Send + Sync + 'static,
     ) {
-        let templates = self.templates.clone();
-        let expander = Arc::new(expander);
+        let handler = Arc::new(handler);
+        self.insert_template(
+            template,
+            Arc::new(move |_, params| {
+                let handler = handler.clone();
+                spawn_provider(move || handler(params))
+            }),
+        );
+    }
+    
+    /// Register a template whose resources are read through an async handler
+    pub fn register_async_template<F, Fut>(&self, template: ResourceTemplate, handler: F)
+    where
+        F: Fn(RequestContext, HashMap<String, String>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<ResourceContent>>> + Send + 'static,
+    {
+        self.insert_template(
+            template,
+            Arc::new(move |context, params| {
+                handler(context, params)
+                    .map(|contents| contents.map(content_stream))
+                    .boxed()
+            }),
+        );
+    }
+    
+    /// Register a template whose resources are produced by a stream from `handler`
+    pub fn register_streaming_template<F, Fut>(&self, template: ResourceTemplate, handler: F)
+    where
+        F: Fn(RequestContext, HashMap<String, String>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ResourceContentStream>> + Send + 'static,
+    {
+        self.insert_template(template, Arc::new(move |context, params| handler(context, params).boxed()));
+    }
+    
+    fn insert_template(&self, template: ResourceTemplate, handler: TemplateReadHandler) {
+        let parsed = match UriTemplate::parse(&template.uri_template) {
+            Ok(parsed) => Some(parsed),
+            Err(err) => {
+                tracing::warn!("Template {} will never match: {}", template.uri_template, err);
+                None
+            }
+        };
         
-        tokio::spawn(async move {
-            let mut templates = templates.write().await;
-            templates.insert(template.uri_template.clone(), (template, expander));
-        });
+        let added = {
+            let mut templates = self.templates.write().unwrap();
+            templates
+                .insert(
+                    template.uri_template.clone(),
+                    TemplateEntry {
+                        template,
+                        parsed,
+                        handler,
+                    },
+                )
+                .is_none()
+        };
+        
+        if added {
+            let _ = self.list_changed_tx.send(());
+        }
+    }
+    
+    /// Remove a template and its completion provider, returning the template
+    pub fn remove_template(&self, uri_template: &str) -> Result<ResourceTemplate> {
+        let entry = self
+            .templates
+            .write()
+            .unwrap()
+            .remove(uri_template)
+            .ok_or_else(|| anyhow!("Template not found: {}", uri_template))?;
+        
+        self.completion_providers.write().unwrap().remove(uri_template);
+        
+        // Notify of list change
+        let _ = self.list_changed_tx.send(());
+        Ok(entry.template)
     }
     
-    /// Register a completion provider for a template parameter
+    /// Register the completion provider for a template's parameters
     pub fn register_completion_provider(
         &self,
         template_uri: &str,
-        provider: impl Fn(String, String, Option<String>) -> Result<Vec<CompletionItem>> + Send + Sync + 'static,
+        provider: impl CompletionProvider,
     ) {
-        let providers = self.completion_providers.clone();
-        let template_uri = template_uri.to_string();
-        let provider = Arc::new(provider);
-        
-        tokio::spawn(async move {
-            let mut providers = providers.write().await;
-            providers.insert(template_uri, provider);
-        });
+        let mut providers = self.completion_providers.write().unwrap();
+        providers.insert(template_uri.to_string(), Arc::new(provider));
     }
     
-    /// Get completion items for a template parameter
+    /// Get completions for a template parameter, empty if the template has no provider
     pub async fn get_completions(
         &self,
         template_uri: &str,
-        parameter: &str,
-        value: Option<String>,
-    ) -> Result<Vec<CompletionItem>> {
-        let providers = self.completion_providers.read().await;
+        query: CompletionQuery,
+    ) -> Result<Vec<String>> {
+        let provider = self.completion_providers.read().unwrap().get(template_uri).cloned();
         
-        if let Some(provider) = providers.get(template_uri) {
-            return provider(template_uri.to_string(), parameter.to_string(), value);
+        match provider {
+            Some(provider) => provider.complete(query).await,
+            None => Ok(Vec::new()),
         }
-        
-        // Return empty results if no provider is registered
-        Ok(Vec::new())
     }
     
     /// Get all registered templates with pagination
-    pub async fn list_templates(&self, cursor: Option<String>) -> (Vec<ResourceTemplate>, Option<String>) {
-        let templates = self.templates.read().await;
-        let all_templates: Vec<ResourceTemplate> = templates.values().map(|(template, _)| template.clone()).collect();
+    pub async fn list_templates(&self, cursor: Option<String>, page_size: usize) -> Result<(Vec<ResourceTemplate>, Option<String>)> {
+        let templates = self.templates.read().unwrap();
+        let (page, next_cursor) = pagination::paginate(&templates, cursor.as_deref(), page_size)?;
         
-        // If we have a cursor, find its position
-        let start_pos = match cursor {
-            Some(cursor) => {
-                // Find the index of the template after the cursor
-                let pos = all_templates.iter().position(|t| t.uri_template == cursor);
-                pos.map(|p| p + 1).unwrap_or(0)
-            },
-            None => 0,
-        };
-        
-        // Get a page of templates
-        let end_pos = std::cmp::min(start_pos + DEFAULT_PAGE_SIZE, all_templates.len());
-        let page = all_templates[start_pos..end_pos].to_vec();
-        
-        // Set the next cursor if there are more templates
-        let next_cursor = if end_pos < all_templates.len() {
-            Some(all_templates[end_pos - 1].uri_template.clone())
-        } else {
-            None
-        };
-        
-        (page, next_cursor)
+        Ok((page.into_iter().map(|entry| entry.template.clone()).collect(), next_cursor))
     }
     
     /// Subscribe to resource updates
     pub async fn subscribe(&self, client_id: &str, uri: &str) -> Result<()> {
         // Check if resource exists
         {
-            let resources = self.resources.read().await;
+            let resources = self.resources.read().unwrap();
             if !resources.contains_key(uri) {
                 return Err(anyhow::anyhow!("Resource not found: {}", uri));
             }
         }
         
         // Add subscription
-        let mut subscriptions = self.subscriptions.write().await;
-        let subscribers = subscriptions.entry(uri.to_string()).or_insert_with(HashSet::new);
+        let mut subscriptions = self.subscriptions.write().unwrap();
+        let subscribers = subscriptions.entry(uri.to_string()).or_default();
         subscribers.insert(client_id.to_string());
         
         Ok(())
@@ -206,7 +432,7 @@ impl ResourceManager {
     
     /// Unsubscribe from resource updates
     pub async fn unsubscribe(&self, client_id: &str, uri: &str) -> Result<()> {
-        let mut subscriptions = self.subscriptions.write().await;
+        let mut subscriptions = self.subscriptions.write().unwrap();
         if let Some(subscribers) = subscriptions.get_mut(uri) {
             subscribers.remove(client_id);
             if subscribers.is_empty() {
@@ -217,16 +443,32 @@ impl ResourceManager {
         Ok(())
     }
     
-    /// Update a resource and notify subscribers
+    /// Whether a client is subscribed to a resource's updates
+    pub fn is_subscribed(&self, client_id: &str, uri: &str) -> bool {
+        self.subscriptions
+            .read()
+            .unwrap()
+            .get(uri)
+            .is_some_and(|subscribers| subscribers.contains(client_id))
+    }
+    
+    /// Update a resource and notify subscribers to its content
+    ///
+    /// This is a content update, so list change subscribers are only told if the URI is new.
     pub async fn update_resource(
         &self, 
         resource: Resource, 
         content_provider: impl Fn() -> Result<Vec<ResourceContent>> + Send + Sync + 'static
     ) -> Result<()> {
         // Update resource
-        {
-            let mut resources = self.resources.write().await;
-            resources.insert(resource.uri.clone(), (resource.clone(), Arc::new(content_provider)));
+        let added = {
+            let mut resources = self.resources.write().unwrap();
+            resources
+                .insert(resource.uri.clone(), (resource.clone(), from_sync(content_provider)))
+                .is_none()
+        };
+        if added {
+            let _ = self.list_changed_tx.send(());
         }
         
         // Notify subscribers
@@ -240,69 +482,31 @@ impl ResourceManager {
         self.update_tx.subscribe()
     }
     
-    /// Parse template parameters from a URI
-    /// This is a simple implementation - a production version would need more robust parsing
+    /// Get a channel for subscribing to changes in the set of resources and templates
+    pub fn subscribe_to_list_changes(&self) -> broadcast::Receiver<()> {
+        self.list_changed_tx.subscribe()
+    }
+    
+    /// Tell subscribers that a resource's content changed
+    #[cfg(feature = "fs")]
+    pub(crate) fn notify_updated(&self, uri: &str) {
+        let _ = self.update_tx.send(uri.to_string());
+    }
+    
+    /// Extract template parameters from a URI produced by an RFC 6570 template
+    ///
+    /// Returns an empty map if the template is invalid or the URI does not match it.
     pub fn parse_template_parameters(&self, template: &str, uri: &str) -> HashMap<String, String> {
-        let mut params = HashMap::new();
-        
-        // Extract template parts - this is a very simple implementation
-        // A real implementation would parse RFC 6570 URI templates properly
-        let template_parts: Vec<&str> = template.split('{')
-            .flat_map(|part| part.split('}')).collect();
-        
-        let mut uri_cursor = uri;
-        
-        for (i, part) in template_parts.iter().enumerate() {
-            if i % 2 == 0 {
-                // This is a literal part
-                if uri_cursor.starts_with(part) {
-                    uri_cursor = &uri_cursor[part.len()..];
-                }
-            } else {
-                // This is a parameter name
-                let param_name = *part;
-                
-                // Find the next literal part, if any
-                let next_literal = if i + 1 < template_parts.len() {
-                    template_parts[i + 1]
-                } else {
-                    ""
-                };
-                
-                // Extract the parameter value
-                let param_value = if next_literal.is_empty() {
-                    uri_cursor.to_string()
-                } else if let Some(pos) = uri_cursor.find(next_literal) {
-                    let value = &uri_cursor[..pos];
-                    uri_cursor = &uri_cursor[pos + next_literal.len()..];
-                    value.to_string()
-                } else {
-                    uri_cursor.to_string()
-                };
-                
-                params.insert(param_name.to_string(), param_value);
-            }
-        }
-        
-        params
+        UriTemplate::parse(template)
+            .ok()
+            .and_then(|template| template.match_uri(uri))
+            .unwrap_or_default()
     }
     
-    /// Expand a template with parameters
+    /// Expand an RFC 6570 template with parameters
     pub async fn expand_template(&self, template_uri: &str, params: HashMap<String, String>) -> Result<String> {
-        let templates = self.templates.read().await;
-        
-        if let Some((_, expander)) = templates.get(template_uri) {
-            return expander(template_uri.to_string(), params);
-        }
-        
-        // Fallback to simple expansion if no custom expander is registered
-        let mut result = template_uri.to_string();
-        
-        for (name, value) in params {
-            result = result.replace(&format!("{{{}}}", name), &value);
-        }
-        
-        Ok(result)
+        let template = UriTemplate::parse(template_uri)?;
+        Ok(template.expand_strings(&params))
     }
 }
 
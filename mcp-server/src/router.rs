@@ -0,0 +1,206 @@
+// mcp-server/src/router.rs
+//! Request routing by method name, with a middleware stack around every handler
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use mcp_protocol::constants::error_codes;
+use mcp_protocol::messages::{JsonRpcErrorObject, Meta, RequestId};
+
+use crate::context::{CancellationToken, Session};
+
+/// What a handler produces: the result to respond with, or the error to send instead
+pub type HandlerResult = Result<Value, JsonRpcErrorObject>;
+
+/// A client request on its way to its handler
+#[derive(Debug, Clone)]
+pub struct IncomingRequest {
+    /// JSON-RPC id of the request
+    pub id: RequestId,
+    /// Method being called
+    pub method: String,
+    /// Raw params, if the client sent any
+    pub params: Option<Value>,
+    /// The params' `_meta` object, if they have one
+    pub meta: Option<Meta>,
+    /// Session the request arrived on
+    pub session: Arc<Session>,
+    /// Cancelled when the client sends `notifications/cancelled` for this request
+    pub cancellation: CancellationToken,
+}
+
+impl IncomingRequest {
+    /// Create a request outside of a client connection
+    pub fn new(id: impl Into<RequestId>, method: &str, params: Option<Value>) -> Self {
+        Self {
+            id: id.into(),
+            method: method.to_string(),
+            meta: params.as_ref().and_then(Meta::from_value),
+            params,
+            session: Arc::new(Session::new()),
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// Deserialize the params, treating missing params as an empty object
+    pub fn params<P: DeserializeOwned>(&self) -> Result<P, JsonRpcErrorObject> {
+        let params = self
+            .params
+            .clone()
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        serde_json::from_value(params).map_err(|err| {
+            JsonRpcErrorObject::new(
+                error_codes::INVALID_PARAMS,
+                &format!("Invalid {} parameters: {}", self.method, err),
+            )
+        })
+    }
+}
+
+/// Handles the requests for one method
+#[async_trait]
+pub trait RequestHandler: Send + Sync + 'static {
+    async fn handle(&self, request: IncomingRequest) -> HandlerResult;
+}
+
+#[async_trait]
+impl<F, Fut> RequestHandler for F
+where
+    F: Fn(IncomingRequest) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = HandlerResult> + Send + 'static,
+{
+    async fn handle(&self, request: IncomingRequest) -> HandlerResult {
+        self(request).await
+    }
+}
+
+/// Wraps the handling of every request, e.g. for auth, logging, metrics or rate limiting
+///
+/// A middleware either passes the request on with `next.run(request)`, possibly looking at or
+/// changing the request and result on the way, or answers it itself without calling `next`.
+#[async_trait]
+pub trait Middleware: Send + Sync + 'static {
+    async fn handle(&self, request: IncomingRequest, next: Next<'_>) -> HandlerResult;
+}
+
+/// Where a request ends up once it has passed through every middleware
+pub(crate) trait Endpoint: Send + Sync {
+    fn call(&self, request: IncomingRequest) -> BoxFuture<'_, HandlerResult>;
+}
+
+/// The rest of the middleware stack, followed by the request's handler
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    endpoint: &'a dyn Endpoint,
+}
+
+impl Next<'_> {
+    /// Pass the request to the next middleware, or to its handler if none are left
+    pub async fn run(self, request: IncomingRequest) -> HandlerResult {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                let next = Next {
+                    middleware: rest,
+                    endpoint: self.endpoint,
+                };
+                middleware.handle(request, next).await
+            }
+            None => self.endpoint.call(request).await,
+        }
+    }
+}
+
+/// Request handlers by method, and the middleware that wraps them
+///
+/// Middleware runs in the order it was added, the first added outermost, and also wraps
+/// requests for methods that have no handler.
+#[derive(Clone, Default)]
+pub struct Router {
+    routes: HashMap<String, Arc<dyn RequestHandler>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl Router {
+    /// Create an empty router
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the handler for `method`, replacing any earlier one
+    pub fn route(mut self, method: &str, handler: impl RequestHandler) -> Self {
+        self.routes.insert(method.to_string(), Arc::new(handler));
+        self
+    }
+
+    /// Add a middleware layer inside the ones already added
+    pub fn layer(mut self, middleware: impl Middleware) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Whether a handler is registered for `method`
+    pub fn has_route(&self, method: &str) -> bool {
+        self.routes.contains_key(method)
+    }
+
+    /// Run a request through the middleware to its handler
+    ///
+    /// Requests for methods without a handler get a method-not-found error.
+    pub async fn handle(&self, request: IncomingRequest) -> HandlerResult {
+        self.dispatch(request, &MethodNotFound).await
+    }
+
+    /// Run a request through the middleware to its handler, or to `fallback` if it has none
+    pub(crate) async fn dispatch(
+        &self,
+        request: IncomingRequest,
+        fallback: &dyn Endpoint,
+    ) -> HandlerResult {
+        let routes = Routes {
+            routes: &self.routes,
+            fallback,
+        };
+        let next = Next {
+            middleware: &self.middleware,
+            endpoint: &routes,
+        };
+        next.run(request).await
+    }
+}
+
+/// Sends requests to their registered handler, or to a fallback
+struct Routes<'a> {
+    routes: &'a HashMap<String, Arc<dyn RequestHandler>>,
+    fallback: &'a dyn Endpoint,
+}
+
+impl Endpoint for Routes<'_> {
+    fn call(&self, request: IncomingRequest) -> BoxFuture<'_, HandlerResult> {
+        match self.routes.get(&request.method) {
+            Some(handler) => handler.handle(request),
+            None => self.fallback.call(request),
+        }
+    }
+}
+
+/// Answers every request with a method-not-found error
+struct MethodNotFound;
+
+impl Endpoint for MethodNotFound {
+    fn call(&self, request: IncomingRequest) -> BoxFuture<'_, HandlerResult> {
+        Box::pin(async move { Err(method_not_found(&request.method)) })
+    }
+}
+
+/// The error for a request whose method has no handler
+pub(crate) fn method_not_found(method: &str) -> JsonRpcErrorObject {
+    JsonRpcErrorObject::new(
+        error_codes::METHOD_NOT_FOUND,
+        &format!("Method not found: {}", method),
+    )
+}
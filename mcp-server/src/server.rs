@@ -1,39 +1,66 @@
 // mcp-server/src/server.rs
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, StreamExt};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json::json;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::atomic::{AtomicU8, Ordering};
-use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::{broadcast, mpsc};
 use tracing::debug;
 
 use mcp_protocol::{
     constants::{error_codes, methods, PROTOCOL_VERSION},
-    messages::{InitializeParams, InitializeResult, JsonRpcMessage, ServerCapabilities},
+    messages::{
+        CancelledParams, ClientNotification, ClientRequest, InitializeParams, InitializeResult,
+        JsonRpcErrorObject, JsonRpcMessage, JsonRpcRequest, MessageParseError, Meta, RequestId,
+        ServerCapabilities,
+    },
     types::{
         resource::{
             Resource, ResourceContent, ResourceReadParams, ResourceSubscribeParams,
-            ResourcesListParams,
+            ResourcesListParams, ResourcesListResult,
+        },
+        tool::{
+            Tool, ToolAnnotations, ToolCallParams, ToolCallResult, ToolsListParams,
+            ToolsListResult,
         },
-        tool::{Tool, ToolCallParams, ToolCallResult},
         ServerInfo, ServerState,
     },
     version::{is_supported_version, version_mismatch_error},
 };
 
-use crate::prompts::PromptManager;
-use crate::resources::ResourceManager;
-use crate::tools::ToolManager;
+use crate::completion::CompletionProvider;
+use crate::context::{CancellationToken, RequestContext, Session};
+use crate::pagination::{self, DEFAULT_PAGE_SIZE};
+use crate::prompts::{PromptDefinition, PromptManager};
+use crate::resources::{
+    ResourceContentStream, ResourceDefinition, ResourceManager, ResourceNotFound,
+};
+use crate::router::{
+    method_not_found, Endpoint, HandlerResult, IncomingRequest, Middleware, Next, RequestHandler,
+    Router,
+};
+use crate::tools::{InvalidToolArguments, InvalidToolOutput, ToolDefinition, ToolManager};
 use crate::transport::Transport;
 
 /// MCP server builder
 pub struct ServerBuilder {
     name: String,
+    title: Option<String>,
     version: String,
     transport: Option<Box<dyn Transport>>,
     tool_manager: Option<Arc<ToolManager>>,
     resource_manager: Option<Arc<ResourceManager>>,
     prompt_manager: Option<Arc<PromptManager>>,
+    page_size: usize,
+    router: Router,
+    registration_error: Option<anyhow::Error>, // First registration failure, reported by `build`
 }
 
 impl ServerBuilder {
@@ -42,14 +69,24 @@ impl ServerBuilder {
         debug!("Creating new server builder");
         Self {
             name: name.to_string(),
+            title: None,
             version: version.to_string(),
             transport: None,
             tool_manager: None,
             resource_manager: None,
             prompt_manager: None,
+            page_size: DEFAULT_PAGE_SIZE,
+            router: Router::new(),
+            registration_error: None,
         }
     }
 
+    /// Set the human-readable name sent in `ServerInfo`, for display in place of `name`
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
     /// Set the transport to use
     pub fn with_transport<T: Transport>(mut self, transport: T) -> Self {
         self.transport = Some(Box::new(transport));
@@ -74,12 +111,45 @@ impl ServerBuilder {
         self
     }
 
+    /// Set the number of items returned per page by the list methods
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Register the handler for a request method, such as a custom `experimental/*` method
+    ///
+    /// A handler registered for one of the methods the server implements replaces it.
+    pub fn with_route(mut self, method: &str, handler: impl RequestHandler) -> Self {
+        debug!("Registering route: {}", method);
+        self.router = self.router.route(method, handler);
+        self
+    }
+
+    /// Add a middleware layer around every request, inside the layers already added
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        self.router = self.router.layer(middleware);
+        self
+    }
+
     /// Register a tool (creates a tool manager if not already set)
     pub fn with_tool(
+        self,
+        name: &str,
+        description: Option<&str>,
+        input_schema: serde_json::Value,
+        handler: impl Fn(serde_json::Value) -> Result<ToolCallResult> + Send + Sync + 'static,
+    ) -> Self {
+        self.with_annotated_tool(name, description, input_schema, None, handler)
+    }
+
+    /// Register a tool with behavior hints (creates a tool manager if not already set)
+    pub fn with_annotated_tool(
         mut self,
         name: &str,
         description: Option<&str>,
         input_schema: serde_json::Value,
+        annotations: Option<ToolAnnotations>,
         handler: impl Fn(serde_json::Value) -> Result<ToolCallResult> + Send + Sync + 'static,
     ) -> Self {
         debug!("Registering tool: {}", name);
@@ -93,7 +163,9 @@ impl ServerBuilder {
             name: name.to_string(),
             description: description.map(|s| s.to_string()),
             input_schema,
-            annotations: None,
+            output_schema: None,
+            annotations,
+            title: None,
         };
 
         // Register tool
@@ -103,6 +175,74 @@ impl ServerBuilder {
         self
     }
 
+    /// Register a tool with typed arguments (creates a tool manager if not already set)
+    ///
+    /// The input schema is generated from `Args` and incoming arguments are deserialized
+    /// into it before `handler` is called.
+    pub fn with_typed_tool<Args, F>(mut self, name: &str, description: Option<&str>, handler: F) -> Self
+    where
+        Args: DeserializeOwned + JsonSchema,
+        F: Fn(Args) -> Result<ToolCallResult> + Send + Sync + 'static,
+    {
+        debug!("Registering typed tool: {}", name);
+        // Create tool manager if not already set
+        if self.tool_manager.is_none() {
+            self.tool_manager = Some(Arc::new(ToolManager::new()));
+        }
+
+        // Register tool
+        let tool_manager = self.tool_manager.as_ref().unwrap();
+        tool_manager.register_typed_tool(name, description, handler);
+
+        self
+    }
+
+    /// Register a tool with typed arguments and structured output (creates a tool manager if not already set)
+    ///
+    /// The input and output schemas are generated from `Args` and `Output`, and the
+    /// handler's output is returned as `structuredContent`. `Output` must serialize as a
+    /// JSON object; if it does not, [`build`](Self::build) fails.
+    pub fn with_structured_tool<Args, Output, F>(
+        mut self,
+        name: &str,
+        description: Option<&str>,
+        handler: F,
+    ) -> Self
+    where
+        Args: DeserializeOwned + JsonSchema,
+        Output: Serialize + JsonSchema,
+        F: Fn(Args) -> Result<Output> + Send + Sync + 'static,
+    {
+        debug!("Registering structured tool: {}", name);
+        // Create tool manager if not already set
+        if self.tool_manager.is_none() {
+            self.tool_manager = Some(Arc::new(ToolManager::new()));
+        }
+
+        // Register tool
+        let tool_manager = self.tool_manager.as_ref().unwrap();
+        if let Err(err) = tool_manager.register_structured_tool(name, description, handler) {
+            self.registration_error.get_or_insert(err);
+        }
+
+        self
+    }
+
+    /// Register a tool definition, such as one generated by `#[mcp_tool]`
+    pub fn with_tool_definition(mut self, definition: ToolDefinition) -> Self {
+        debug!("Registering tool: {}", definition.tool.name);
+        // Create tool manager if not already set
+        if self.tool_manager.is_none() {
+            self.tool_manager = Some(Arc::new(ToolManager::new()));
+        }
+
+        // Register tool
+        let tool_manager = self.tool_manager.as_ref().unwrap();
+        tool_manager.register_definition(definition);
+
+        self
+    }
+
     /// Register a resource (creates a resource manager if not already set)
     pub fn with_resource(
         mut self,
@@ -126,6 +266,7 @@ impl ServerBuilder {
             mime_type: mime_type.map(|s| s.to_string()),
             size,
             annotations: None,
+            title: None,
         };
 
         // Register resource
@@ -135,14 +276,107 @@ impl ServerBuilder {
         self
     }
 
+    /// Register a resource served by an async provider (creates a resource manager if not already set)
+    pub fn with_async_resource<F, Fut>(
+        mut self,
+        uri: &str,
+        name: &str,
+        description: Option<&str>,
+        mime_type: Option<&str>,
+        size: Option<u64>,
+        provider: F,
+    ) -> Self
+    where
+        F: Fn(RequestContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<ResourceContent>>> + Send + 'static,
+    {
+        // Create resource manager if not already set
+        if self.resource_manager.is_none() {
+            self.resource_manager = Some(Arc::new(ResourceManager::new()));
+        }
+
+        // Create resource
+        let resource = Resource {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            mime_type: mime_type.map(|s| s.to_string()),
+            size,
+            annotations: None,
+            title: None,
+        };
+
+        // Register resource
+        let resource_manager = self.resource_manager.as_ref().unwrap();
+        resource_manager.register_async_resource(resource, provider);
+
+        self
+    }
+
+    /// Register a resource whose contents are produced by a stream from `provider` (creates a resource manager if not already set)
+    pub fn with_streaming_resource<F, Fut>(
+        mut self,
+        uri: &str,
+        name: &str,
+        description: Option<&str>,
+        mime_type: Option<&str>,
+        size: Option<u64>,
+        provider: F,
+    ) -> Self
+    where
+        F: Fn(RequestContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ResourceContentStream>> + Send + 'static,
+    {
+        // Create resource manager if not already set
+        if self.resource_manager.is_none() {
+            self.resource_manager = Some(Arc::new(ResourceManager::new()));
+        }
+
+        // Create resource
+        let resource = Resource {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            mime_type: mime_type.map(|s| s.to_string()),
+            size,
+            annotations: None,
+            title: None,
+        };
+
+        // Register resource
+        let resource_manager = self.resource_manager.as_ref().unwrap();
+        resource_manager.register_streaming_resource(resource, provider);
+
+        self
+    }
+
+    /// Register a resource definition, such as one generated by `#[mcp_resource]`
+    pub fn with_resource_definition(mut self, definition: ResourceDefinition) -> Self {
+        // Create resource manager if not already set
+        if self.resource_manager.is_none() {
+            self.resource_manager = Some(Arc::new(ResourceManager::new()));
+        }
+
+        // Register resource
+        let resource_manager = self.resource_manager.as_ref().unwrap();
+        resource_manager.register_definition(definition);
+
+        self
+    }
+
     /// Register a resource template (creates a resource manager if not already set)
+    ///
+    /// Reads of URIs matching the template call `handler` with the extracted variables.
     pub fn with_template(
         mut self,
         uri_template: &str,
         name: &str,
         description: Option<&str>,
         mime_type: Option<&str>,
-        expander: impl Fn(String, HashMap<String, String>) -> Result<String> + Send + Sync + 'static,
+        handler: impl Fn(HashMap<String, String>) -> Result<Vec<ResourceContent>>
+            + Send
+            + Sync
+            + 'static,
     ) -> Self {
         // Create resource manager if not already set
         if self.resource_manager.is_none() {
@@ -156,27 +390,56 @@ impl ServerBuilder {
             description: description.map(|s| s.to_string()),
             mime_type: mime_type.map(|s| s.to_string()),
             annotations: None,
+            title: None,
+        };
+
+        // Register template
+        let resource_manager = self.resource_manager.as_ref().unwrap();
+        resource_manager.register_template(template, handler);
+
+        self
+    }
+
+    /// Register a resource template read through an async handler (creates a resource manager if not already set)
+    pub fn with_async_template<F, Fut>(
+        mut self,
+        uri_template: &str,
+        name: &str,
+        description: Option<&str>,
+        mime_type: Option<&str>,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(RequestContext, HashMap<String, String>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<ResourceContent>>> + Send + 'static,
+    {
+        // Create resource manager if not already set
+        if self.resource_manager.is_none() {
+            self.resource_manager = Some(Arc::new(ResourceManager::new()));
+        }
+
+        // Create template
+        let template = mcp_protocol::types::resource::ResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            mime_type: mime_type.map(|s| s.to_string()),
+            annotations: None,
+            title: None,
         };
 
         // Register template
         let resource_manager = self.resource_manager.as_ref().unwrap();
-        resource_manager.register_template(template, expander);
+        resource_manager.register_async_template(template, handler);
 
         self
     }
 
-    /// Register a template parameter completion provider
+    /// Register the completion provider for a template's parameters
     pub fn with_template_completion(
         mut self,
         template_uri: &str,
-        provider: impl Fn(
-                String,
-                String,
-                Option<String>,
-            ) -> Result<Vec<mcp_protocol::types::completion::CompletionItem>>
-            + Send
-            + Sync
-            + 'static,
+        provider: impl CompletionProvider,
     ) -> Self {
         // Create resource manager if not already set
         if self.resource_manager.is_none() {
@@ -195,7 +458,7 @@ impl ServerBuilder {
         mut self,
         prompt_name: &str,
         param_name: &str,
-        provider: impl Fn(String, Option<String>) -> Result<Vec<String>> + Send + Sync + 'static,
+        provider: impl CompletionProvider,
     ) -> Self {
         // Create prompt manager if not already set
         if self.prompt_manager.is_none() {
@@ -233,6 +496,7 @@ impl ServerBuilder {
             description: description.map(|s| s.to_string()),
             arguments,
             annotations: None,
+            title: None,
         };
 
         // Register prompt
@@ -242,14 +506,39 @@ impl ServerBuilder {
         self
     }
 
+    /// Register a prompt definition, such as one generated by `#[mcp_prompt]`
+    pub fn with_prompt_definition(mut self, definition: PromptDefinition) -> Self {
+        // Create prompt manager if not already set
+        if self.prompt_manager.is_none() {
+            self.prompt_manager = Some(Arc::new(PromptManager::new()));
+        }
+
+        // Register prompt
+        let prompt_manager = self.prompt_manager.as_ref().unwrap();
+        prompt_manager.register_definition(definition);
+
+        self
+    }
+
     /// Build the server
     pub fn build(self) -> Result<Server> {
+        if let Some(err) = self.registration_error {
+            return Err(err);
+        }
+
         let transport = self
             .transport
             .ok_or_else(|| anyhow!("Transport is required"))?;
 
+        // The initialization check runs inside the caller's middleware so they see its rejections
+        let state = Arc::new(AtomicU8::new(ServerState::Created as u8));
+        let router = self.router.layer(RequireInitialized {
+            state: state.clone(),
+        });
+
         Ok(Server {
             name: self.name,
+            title: self.title,
             version: self.version,
             transport,
             tool_manager: self
@@ -261,20 +550,50 @@ impl ServerBuilder {
             prompt_manager: self
                 .prompt_manager
                 .unwrap_or_else(|| Arc::new(PromptManager::new())),
-            state: Arc::new(AtomicU8::new(ServerState::Created as u8)),
+            state,
+            page_size: self.page_size,
+            router,
+            session: RwLock::new(Arc::new(Session::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 }
 
+/// Rejects every request but `initialize` until the client has initialized
+struct RequireInitialized {
+    state: Arc<AtomicU8>,
+}
+
+#[async_trait]
+impl Middleware for RequireInitialized {
+    async fn handle(&self, request: IncomingRequest, next: Next<'_>) -> HandlerResult {
+        if request.method != methods::INITIALIZE
+            && self.state.load(Ordering::SeqCst) != ServerState::Ready as u8
+        {
+            return Err(JsonRpcErrorObject::new(
+                error_codes::SERVER_NOT_INITIALIZED,
+                "Server not initialized",
+            ));
+        }
+
+        next.run(request).await
+    }
+}
+
 /// MCP server
 pub struct Server {
     name: String,
+    title: Option<String>,
     version: String,
     transport: Box<dyn Transport>,
     tool_manager: Arc<ToolManager>,
     resource_manager: Arc<ResourceManager>,
     prompt_manager: Arc<PromptManager>,
     state: Arc<AtomicU8>,
+    page_size: usize,
+    router: Router,
+    session: RwLock<Arc<Session>>,
+    in_flight: Arc<Mutex<HashMap<RequestId, CancellationToken>>>, // Cancellation tokens of requests being served, by request id
 }
 
 impl Server {
@@ -305,89 +624,55 @@ impl Server {
         ServerInfo {
             name: self.name.clone(),
             version: self.version.clone(),
+            title: self.title.clone(),
         }
     }
 
     /// Handle initialize request
-    async fn handle_initialize(&self, message: JsonRpcMessage) -> Result<()> {
-        match message {
-            JsonRpcMessage::Request { id, params, .. } => {
-                // Parse initialize parameters
-                let params: InitializeParams = match params {
-                    Some(params) => match serde_json::from_value(params) {
-                        Ok(params) => params,
-                        Err(err) => {
-                            // Send error response
-                            self.transport
-                                .send(JsonRpcMessage::error(
-                                    id,
-                                    error_codes::INVALID_PARAMS,
-                                    &format!("Invalid initialize parameters: {}", err),
-                                    None,
-                                ))
-                                .await?;
-                            return Ok(());
-                        }
-                    },
-                    None => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::INVALID_PARAMS,
-                                "Missing initialize parameters",
-                                None,
-                            ))
-                            .await?;
-                        return Ok(());
-                    }
-                };
-
-                // Validate protocol version
-                if !is_supported_version(&params.protocol_version) {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::INVALID_PARAMS,
-                            "Unsupported protocol version",
-                            Some(json!(version_mismatch_error(&params.protocol_version))),
-                        ))
-                        .await?;
-                    return Ok(());
-                }
+    async fn handle_initialize(&self, params: InitializeParams) -> HandlerResult {
+        // Validate protocol version
+        if !is_supported_version(&params.protocol_version) {
+            return Err(
+                JsonRpcErrorObject::new(error_codes::INVALID_PARAMS, "Unsupported protocol version")
+                    .with_data(json!(version_mismatch_error(&params.protocol_version))),
+            );
+        }
 
-                // Update server state
-                self.state
-                    .store(ServerState::Initializing as u8, Ordering::SeqCst);
-
-                // Get capabilities
-                let tools_capabilities = self.get_tool_capabilities();
-                let resources_capabilities = self.get_resource_capabilities();
-                let prompts_capabilities = self.get_prompt_capabilities();
-
-                // Create initialize result
-                let result = InitializeResult {
-                    protocol_version: PROTOCOL_VERSION.to_string(),
-                    capabilities: ServerCapabilities {
-                        tools: Some(tools_capabilities),
-                        resources: Some(resources_capabilities),
-                        prompts: Some(prompts_capabilities),
-                        ..Default::default()
-                    },
-                    server_info: self.get_server_info(),
-                    instructions: None,
-                };
+        // Record who is connected
+        {
+            let mut session = self.session.write().unwrap();
+            *session = Arc::new(Session {
+                id: session.id.clone(),
+                client_info: Some(params.client_info.clone()),
+                protocol_version: Some(params.protocol_version.clone()),
+            });
+        }
 
-                // Send initialize response
-                self.transport
-                    .send(JsonRpcMessage::response(id, json!(result)))
-                    .await?;
+        // Update server state
+        self.state
+            .store(ServerState::Initializing as u8, Ordering::SeqCst);
+
+        // Get capabilities
+        let tools_capabilities = self.get_tool_capabilities();
+        let resources_capabilities = self.get_resource_capabilities();
+        let prompts_capabilities = self.get_prompt_capabilities();
+
+        // Create initialize result
+        let result = InitializeResult {
+            protocol_version: PROTOCOL_VERSION.to_string(),
+            capabilities: ServerCapabilities {
+                tools: Some(tools_capabilities),
+                resources: Some(resources_capabilities),
+                prompts: Some(prompts_capabilities),
+                completions: Some(HashMap::new()),
+                ..Default::default()
+            },
+            server_info: self.get_server_info(),
+            instructions: None,
+            meta: None,
+        };
 
-                Ok(())
-            }
-            _ => Err(anyhow!("Expected request message for initialize")),
-        }
+        Ok(json!(result))
     }
 
     /// Handle initialized notification
@@ -399,405 +684,232 @@ impl Server {
         Ok(())
     }
 
-    /// Handle tools/list request
-    async fn handle_tools_list(&self, message: JsonRpcMessage) -> Result<()> {
-        match message {
-            JsonRpcMessage::Request { id, .. } => {
-                // Check if server is ready
-                if self.state.load(Ordering::SeqCst) != ServerState::Ready as u8 {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::SERVER_NOT_INITIALIZED,
-                            "Server not initialized",
-                            None,
-                        ))
-                        .await?;
-                    return Ok(());
-                }
+    /// Handle cancelled notification
+    fn handle_cancelled(&self, params: CancelledParams) -> Result<()> {
+        // Requests that already finished are ignored
+        let cancellation = self.in_flight.lock().unwrap().remove(&params.request_id);
+        if let Some(cancellation) = cancellation {
+            debug!(
+                "Cancelling request {}: {}",
+                params.request_id,
+                params.reason.as_deref().unwrap_or("no reason given")
+            );
+            cancellation.cancel();
+        }
 
-                // Get tools from manager
-                let tools = self.tool_manager.list_tools().await;
-
-                // Send response
-                self.transport
-                    .send(JsonRpcMessage::response(
-                        id,
-                        json!({
-                            "tools": tools,
-                            "nextCursor": ""
-                        }),
-                    ))
-                    .await?;
+        Ok(())
+    }
 
-                Ok(())
-            }
-            _ => Err(anyhow!("Expected request message for tools/list")),
-        }
+    /// Handle tools/list request
+    async fn handle_tools_list(&self, params: ToolsListParams) -> HandlerResult {
+        // Get tools from manager with pagination
+        let (tools, next_cursor) = self
+            .tool_manager
+            .list_tools_page(params.cursor, self.page_size)
+            .await
+            .map_err(pagination::list_error)?;
+
+        Ok(json!(ToolsListResult { tools, next_cursor, meta: None }))
     }
 
     /// Handle tools/call request
-    async fn handle_tools_call(&self, message: JsonRpcMessage) -> Result<()> {
-        match message {
-            JsonRpcMessage::Request { id, params, .. } => {
-                // Check if server is ready
-                if self.state.load(Ordering::SeqCst) != ServerState::Ready as u8 {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::SERVER_NOT_INITIALIZED,
-                            "Server not initialized",
-                            None,
-                        ))
-                        .await?;
-                    return Ok(());
-                }
-
-                // Parse tool call parameters
-                let params: ToolCallParams = match params {
-                    Some(params) => match serde_json::from_value(params) {
-                        Ok(params) => params,
-                        Err(err) => {
-                            // Send error response
-                            self.transport
-                                .send(JsonRpcMessage::error(
-                                    id,
-                                    error_codes::INVALID_PARAMS,
-                                    &format!("Invalid tool call parameters: {}", err),
-                                    None,
-                                ))
-                                .await?;
-                            return Ok(());
-                        }
-                    },
-                    None => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::INVALID_PARAMS,
-                                "Missing tool call parameters",
-                                None,
-                            ))
-                            .await?;
-                        return Ok(());
-                    }
+    async fn handle_tools_call(&self, params: ToolCallParams) -> HandlerResult {
+        // Execute tool
+        match self
+            .tool_manager
+            .execute_tool_with_meta(&params.name, params.arguments, params.meta)
+            .await
+        {
+            Ok(result) => Ok(json!(result)),
+            Err(err) => {
+                // Arguments that fail schema validation are the caller's fault
+                let error = if let Some(invalid) = err.downcast_ref::<InvalidToolArguments>() {
+                    JsonRpcErrorObject::new(error_codes::INVALID_PARAMS, &invalid.to_string())
+                        .with_data(json!({
+                            "name": invalid.tool,
+                            "errors": invalid.violations
+                        }))
+                } else if let Some(invalid) = err.downcast_ref::<InvalidToolOutput>() {
+                    JsonRpcErrorObject::new(error_codes::INTERNAL_ERROR, &invalid.to_string())
+                        .with_data(json!({
+                            "name": invalid.tool,
+                            "errors": invalid.violations
+                        }))
+                } else {
+                    JsonRpcErrorObject::new(
+                        error_codes::INTERNAL_ERROR,
+                        &format!("Tool execution error: {}", err),
+                    )
                 };
 
-                // Execute tool
-                match self
-                    .tool_manager
-                    .execute_tool(&params.name, params.arguments)
-                    .await
-                {
-                    Ok(result) => {
-                        // Send response
-                        self.transport
-                            .send(JsonRpcMessage::response(id, json!(result)))
-                            .await?;
-                    }
-                    Err(err) => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::INTERNAL_ERROR,
-                                &format!("Tool execution error: {}", err),
-                                None,
-                            ))
-                            .await?;
-                    }
-                }
-
-                Ok(())
+                Err(error)
             }
-            _ => Err(anyhow!("Expected request message for tools/call")),
         }
     }
 
     /// Handle resources/list request
-    async fn handle_resources_list(&self, message: JsonRpcMessage) -> Result<()> {
-        match message {
-            JsonRpcMessage::Request { id, params, .. } => {
-                // Check if server is ready
-                if self.state.load(Ordering::SeqCst) != ServerState::Ready as u8 {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::SERVER_NOT_INITIALIZED,
-                            "Server not initialized",
-                            None,
-                        ))
-                        .await?;
-                    return Ok(());
-                }
-
-                // Parse parameters (optional)
-                let params: Option<ResourcesListParams> = match params {
-                    Some(params) => match serde_json::from_value(params) {
-                        Ok(params) => Some(params),
-                        Err(err) => {
-                            // Send error response
-                            self.transport
-                                .send(JsonRpcMessage::error(
-                                    id,
-                                    error_codes::INVALID_PARAMS,
-                                    &format!("Invalid resource list parameters: {}", err),
-                                    None,
-                                ))
-                                .await?;
-                            return Ok(());
-                        }
-                    },
-                    None => None,
-                };
-
-                // Get cursor from parameters
-                let cursor = params.and_then(|p| p.cursor);
-
-                // Get resources from manager with pagination
-                let (resources, next_cursor) = self.resource_manager.list_resources(cursor).await;
-
-                // Send response
-                self.transport
-                    .send(JsonRpcMessage::response(
-                        id,
-                        json!({
-                            "resources": resources,
-                            "nextCursor": next_cursor.unwrap_or_default()
-                        }),
-                    ))
-                    .await?;
-
-                Ok(())
-            }
-            _ => Err(anyhow!("Expected request message for resources/list")),
-        }
+    async fn handle_resources_list(&self, params: ResourcesListParams) -> HandlerResult {
+        // Get resources from manager with pagination
+        let (resources, next_cursor) = self
+            .resource_manager
+            .list_resources(params.cursor, self.page_size)
+            .await
+            .map_err(pagination::list_error)?;
+
+        Ok(json!(ResourcesListResult {
+            resources,
+            next_cursor,
+            meta: None,
+        }))
     }
 
     /// Handle resources/read request
-    async fn handle_resources_read(&self, message: JsonRpcMessage) -> Result<()> {
-        match message {
-            JsonRpcMessage::Request { id, params, .. } => {
-                // Check if server is ready
-                if self.state.load(Ordering::SeqCst) != ServerState::Ready as u8 {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::SERVER_NOT_INITIALIZED,
-                            "Server not initialized",
-                            None,
-                        ))
-                        .await?;
-                    return Ok(());
-                }
+    async fn handle_resources_read(
+        &self,
+        request: &IncomingRequest,
+        params: ResourceReadParams,
+    ) -> HandlerResult {
+        let mut context = RequestContext::new(&params.uri)
+            .with_request_id(request.id.clone())
+            .with_session(request.session.clone())
+            .with_cancellation(request.cancellation.clone());
+        context.meta = params.meta.clone();
+
+        match self.resource_manager.read_resource(context).await {
+            Ok(contents) => Ok(json!({
+                "contents": contents
+            })),
+            // Only a URI nothing serves is not found; a failing provider is the server's problem.
+            // A cancelled read gets no response, so its error is never sent.
+            Err(err) if err.is::<ResourceNotFound>() => Err(JsonRpcErrorObject::new(
+                error_codes::RESOURCE_NOT_FOUND,
+                &err.to_string(),
+            )
+            .with_data(json!({
+                "uri": params.uri
+            }))),
+            Err(err) => Err(JsonRpcErrorObject::new(
+                error_codes::INTERNAL_ERROR,
+                &format!("Resource read error: {}", err),
+            )
+            .with_data(json!({
+                "uri": params.uri
+            }))),
+        }
+    }
 
-                // Parse parameters
-                let params: ResourceReadParams = match params {
-                    Some(params) => match serde_json::from_value(params) {
-                        Ok(params) => params,
-                        Err(err) => {
-                            // Send error response
-                            self.transport
-                                .send(JsonRpcMessage::error(
-                                    id,
-                                    error_codes::INVALID_PARAMS,
-                                    &format!("Invalid resource read parameters: {}", err),
-                                    None,
-                                ))
-                                .await?;
-                            return Ok(());
-                        }
-                    },
-                    None => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::INVALID_PARAMS,
-                                "Missing resource read parameters",
-                                None,
-                            ))
-                            .await?;
-                        return Ok(());
-                    }
-                };
+    /// Handle resources/subscribe request
+    async fn handle_resources_subscribe(
+        &self,
+        request: &IncomingRequest,
+        params: ResourceSubscribeParams,
+    ) -> HandlerResult {
+        // Subscribe to resource
+        // Keyed by connection, so a later unsubscribe finds the subscription
+        let client_id = &request.session.id;
+        match self
+            .resource_manager
+            .subscribe(client_id, &params.uri)
+            .await
+        {
+            Ok(_) => Ok(json!({
+                "success": true
+            })),
+            Err(err) => Err(JsonRpcErrorObject::new(
+                error_codes::RESOURCE_NOT_FOUND,
+                &format!("Resource subscription error: {}", err),
+            )
+            .with_data(json!({
+                "uri": params.uri
+            }))),
+        }
+    }
 
-                // Read resource
-                match self
-                    .resource_manager
-                    .get_resource_content(&params.uri)
-                    .await
-                {
-                    Ok(contents) => {
-                        // Send response
-                        self.transport
-                            .send(JsonRpcMessage::response(
-                                id,
-                                json!({
-                                    "contents": contents
-                                }),
-                            ))
-                            .await?;
-                    }
-                    Err(err) => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::RESOURCE_NOT_FOUND,
-                                &format!("Resource not found: {}", err),
-                                Some(json!({
-                                    "uri": params.uri
-                                })),
-                            ))
-                            .await?;
-                    }
+    /// Handle a request for one of the methods the server implements itself
+    async fn handle_builtin(&self, request: IncomingRequest) -> HandlerResult {
+        let typed = ClientRequest::from_method(&request.method, &request.params).map_err(
+            |err| match err {
+                MessageParseError::MethodNotFound(method) => method_not_found(&method),
+                MessageParseError::InvalidParams { .. } => {
+                    JsonRpcErrorObject::new(error_codes::INVALID_PARAMS, &err.to_string())
                 }
-
-                Ok(())
+            },
+        )?;
+
+        match typed {
+            ClientRequest::Initialize(params) => self.handle_initialize(params).await,
+            ClientRequest::ListTools(params) => self.handle_tools_list(params).await,
+            ClientRequest::CallTool(params) => self.handle_tools_call(params).await,
+            ClientRequest::ListResources(params) => self.handle_resources_list(params).await,
+            ClientRequest::ListResourceTemplates(params) => {
+                self.handle_resources_templates_list(params).await
+            }
+            ClientRequest::ReadResource(params) => {
+                self.handle_resources_read(&request, params).await
             }
-            _ => Err(anyhow!("Expected request message for resources/read")),
+            ClientRequest::Subscribe(params) => {
+                self.handle_resources_subscribe(&request, params).await
+            }
+            ClientRequest::Unsubscribe(params) => {
+                self.handle_resources_unsubscribe(&request, params).await
+            }
+            ClientRequest::ListPrompts(params) => self.handle_prompts_list(params).await,
+            ClientRequest::GetPrompt(params) => self.handle_prompts_get(params).await,
+            ClientRequest::Complete(params) => self.handle_completion_complete(params).await,
         }
     }
 
-    /// Handle resources/subscribe request
-    async fn handle_resources_subscribe(&self, message: JsonRpcMessage) -> Result<()> {
-        match message {
-            JsonRpcMessage::Request { id, params, .. } => {
-                // Check if server is ready
-                if self.state.load(Ordering::SeqCst) != ServerState::Ready as u8 {
-                    // Send error response
-                    self.transport
-                        .send(JsonRpcMessage::error(
-                            id,
-                            error_codes::SERVER_NOT_INITIALIZED,
-                            "Server not initialized",
-                            None,
-                        ))
-                        .await?;
-                    return Ok(());
-                }
-
-                // Parse parameters
-                let params: ResourceSubscribeParams = match params {
-                    Some(params) => match serde_json::from_value(params) {
-                        Ok(params) => params,
-                        Err(err) => {
-                            // Send error response
-                            self.transport
-                                .send(JsonRpcMessage::error(
-                                    id,
-                                    error_codes::INVALID_PARAMS,
-                                    &format!("Invalid resource subscribe parameters: {}", err),
-                                    None,
-                                ))
-                                .await?;
-                            return Ok(());
-                        }
-                    },
-                    None => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::INVALID_PARAMS,
-                                "Missing resource subscribe parameters",
-                                None,
-                            ))
-                            .await?;
-                        return Ok(());
-                    }
-                };
-
-                // Subscribe to resource
-                let client_id = id.to_string(); // Use request ID as client ID for simplicity
-                match self
-                    .resource_manager
-                    .subscribe(&client_id, &params.uri)
-                    .await
-                {
-                    Ok(_) => {
-                        // Send success response
-                        self.transport
-                            .send(JsonRpcMessage::response(
-                                id,
-                                json!({
-                                    "success": true
-                                }),
-                            ))
-                            .await?;
-                    }
-                    Err(err) => {
-                        // Send error response
-                        self.transport
-                            .send(JsonRpcMessage::error(
-                                id,
-                                error_codes::RESOURCE_NOT_FOUND,
-                                &format!("Resource subscription error: {}", err),
-                                Some(json!({
-                                    "uri": params.uri
-                                })),
-                            ))
-                            .await?;
-                    }
-                }
+    /// Handle a request and send its response
+    async fn handle_request(&self, request: JsonRpcRequest) -> Result<()> {
+        let JsonRpcRequest {
+            id, method, params, ..
+        } = request;
+
+        // Track the request so the client can cancel it
+        let cancellation = CancellationToken::new();
+        self.in_flight
+            .lock()
+            .unwrap()
+            .insert(id.clone(), cancellation.clone());
+
+        let incoming = IncomingRequest {
+            id: id.clone(),
+            method,
+            meta: params.as_ref().and_then(Meta::from_value),
+            params,
+            session: self.session.read().unwrap().clone(),
+            cancellation: cancellation.clone(),
+        };
+        let result = self.router.dispatch(incoming, self).await;
+        self.in_flight.lock().unwrap().remove(&id);
 
-                Ok(())
-            }
-            _ => Err(anyhow!("Expected request message for resources/subscribe")),
+        // Cancelled requests get no response
+        if cancellation.is_cancelled() {
+            debug!("Request {} was cancelled", id);
+            return Ok(());
         }
+
+        let response = match result {
+            Ok(result) => JsonRpcMessage::response(id, result),
+            Err(error) => JsonRpcMessage::error(id, error.code, &error.message, error.data),
+        };
+        self.transport.send(response).await
     }
 
-    /// Handle incoming messages
+    /// Handle incoming notifications and responses
     async fn handle_message(&self, message: JsonRpcMessage) -> Result<()> {
-        match &message.clone() {
-            JsonRpcMessage::Request { method, .. } => {
-                match method.as_str() {
-                    methods::INITIALIZE => self.handle_initialize(message).await?,
-                    methods::TOOLS_LIST => self.handle_tools_list(message).await?,
-                    methods::TOOLS_CALL => self.handle_tools_call(message).await?,
-                    methods::RESOURCES_LIST => self.handle_resources_list(message).await?,
-                    methods::RESOURCES_READ => self.handle_resources_read(message).await?,
-                    methods::RESOURCES_SUBSCRIBE => {
-                        self.handle_resources_subscribe(message).await?
-                    }
-                    methods::RESOURCES_UNSUBSCRIBE => {
-                        self.handle_resources_unsubscribe(message).await?
-                    }
-                    methods::RESOURCES_TEMPLATES_LIST => {
-                        self.handle_resources_templates_list(message).await?
-                    }
-                    methods::PROMPTS_LIST => self.handle_prompts_list(message).await?,
-                    methods::PROMPTS_GET => self.handle_prompts_get(message).await?,
-                    methods::COMPLETION_COMPLETE => {
-                        self.handle_completion_complete(message).await?
-                    }
-                    _ => {
-                        if let JsonRpcMessage::Request { id, .. } = message {
-                            // Method not found
-                            self.transport
-                                .send(JsonRpcMessage::error(
-                                    id,
-                                    error_codes::METHOD_NOT_FOUND,
-                                    &format!("Method not found: {}", method),
-                                    None,
-                                ))
-                                .await?;
-                        }
+        match message {
+            JsonRpcMessage::Request(request) => self.handle_request(request).await?,
+            JsonRpcMessage::Notification(notification) => {
+                match ClientNotification::from_notification(&notification) {
+                    Ok(ClientNotification::Initialized(_)) => self.handle_initialized().await?,
+                    Ok(ClientNotification::Cancelled(params)) => self.handle_cancelled(params)?,
+                    Err(MessageParseError::MethodNotFound(method)) => {
+                        tracing::debug!("Unhandled notification: {}", method);
                     }
+                    Err(err) => return Err(err.into()),
                 }
             }
-            JsonRpcMessage::Notification { method, .. } => match method.as_str() {
-                methods::INITIALIZED => self.handle_initialized().await?,
-                _ => {
-                    tracing::debug!("Unhandled notification: {}", method);
-                }
-            },
             _ => {
                 // Not sure what to do with responses from the client
                 tracing::debug!("Unexpected message type from client");
@@ -818,11 +930,18 @@ impl Server {
         // Set up resource update listener
         let resource_update_rx = self.resource_manager.subscribe_to_updates();
         let resource_transport = self.transport.box_clone();
+        let resource_manager = self.resource_manager.clone();
+        let session_id = self.session.read().unwrap().id.clone();
 
         // Spawn a task to handle resource updates
         tokio::spawn(async move {
             let mut update_rx = resource_update_rx;
             while let Ok(uri) = update_rx.recv().await {
+                // Only resources the client subscribed to are announced
+                if !resource_manager.is_subscribed(&session_id, &uri) {
+                    continue;
+                }
+
                 // Send notification
                 let _ = resource_transport
                     .send(JsonRpcMessage::notification(
@@ -833,6 +952,72 @@ impl Server {
             }
         });
 
+        // Set up resource list change listener
+        let resource_list_rx = self.resource_manager.subscribe_to_list_changes();
+        let resource_list_transport = self.transport.box_clone();
+        let resource_list_state = self.state.clone();
+
+        // Spawn a task to handle resource list changes
+        tokio::spawn(async move {
+            let mut list_rx = resource_list_rx;
+            loop {
+                // Falling behind just means several changes happened; one notification covers them
+                match list_rx.recv().await {
+                    Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+
+                // Collapse a burst of changes, such as a directory scan, into one notification
+                while let Ok(()) | Err(broadcast::error::TryRecvError::Lagged(_)) = list_rx.try_recv() {}
+
+                // Only initialized clients are told about changes
+                if resource_list_state.load(Ordering::SeqCst) != ServerState::Ready as u8 {
+                    continue;
+                }
+
+                // Send notification
+                let _ = resource_list_transport
+                    .send(JsonRpcMessage::notification(
+                        methods::RESOURCES_LIST_CHANGED,
+                        None,
+                    ))
+                    .await;
+            }
+        });
+
+        // Set up tool update listener
+        let tool_update_rx = self.tool_manager.subscribe_to_updates();
+        let tool_transport = self.transport.box_clone();
+        let tool_state = self.state.clone();
+
+        // Spawn a task to handle tool updates
+        tokio::spawn(async move {
+            let mut update_rx = tool_update_rx;
+            loop {
+                // Falling behind just means several changes happened; one notification covers them
+                match update_rx.recv().await {
+                    Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+
+                // Collapse a burst of registrations into one notification
+                while let Ok(()) | Err(broadcast::error::TryRecvError::Lagged(_)) = update_rx.try_recv() {}
+
+                // Only initialized clients are told about changes
+                if tool_state.load(Ordering::SeqCst) != ServerState::Ready as u8 {
+                    continue;
+                }
+
+                // Send notification
+                let _ = tool_transport
+                    .send(JsonRpcMessage::notification(
+                        methods::TOOLS_LIST_CHANGED,
+                        None,
+                    ))
+                    .await;
+            }
+        });
+
         // Set up prompt update listener
         let prompt_update_rx = self.prompt_manager.subscribe_to_updates();
         let prompt_transport = self.transport.box_clone();
@@ -840,7 +1025,7 @@ impl Server {
         // Spawn a task to handle prompt updates
         tokio::spawn(async move {
             let mut update_rx = prompt_update_rx;
-            while let Ok(_) = update_rx.recv().await {
+            while update_rx.recv().await.is_ok() {
                 // Send notification
                 let _ = prompt_transport
                     .send(JsonRpcMessage::notification(
@@ -851,10 +1036,33 @@ impl Server {
             }
         });
 
-        // Process messages
-        while let Some(message) = rx.recv().await {
-            if let Err(err) = self.handle_message(message).await {
-                tracing::error!("Error handling message: {}", err);
+        // Process messages; requests are served concurrently so a slow one doesn't hold up the rest
+        let mut requests = FuturesUnordered::new();
+        loop {
+            tokio::select! {
+                message = rx.recv() => match message {
+                    Some(JsonRpcMessage::Request(request)) => {
+                        requests.push(self.handle_request(request));
+                    }
+                    Some(message) => {
+                        if let Err(err) = self.handle_message(message).await {
+                            tracing::error!("Error handling message: {}", err);
+                        }
+                    }
+                    None => break,
+                },
+                Some(result) = requests.next(), if !requests.is_empty() => {
+                    if let Err(err) = result {
+                        tracing::error!("Error handling request: {}", err);
+                    }
+                }
+            }
+        }
+
+        // Finish the requests already received
+        while let Some(result) = requests.next().await {
+            if let Err(err) = result {
+                tracing::error!("Error handling request: {}", err);
             }
         }
 
@@ -883,13 +1091,14 @@ impl Server {
         &self.prompt_manager
     }
 
-    /// Get a reference to the transport
-    pub(crate) fn transport(&self) -> &Box<dyn Transport> {
-        &self.transport
+    /// Get the number of items returned per page by the list methods
+    pub(crate) fn page_size(&self) -> usize {
+        self.page_size
     }
+}
 
-    /// Get the server state
-    pub(crate) fn state(&self) -> &Arc<AtomicU8> {
-        &self.state
+impl Endpoint for Server {
+    fn call(&self, request: IncomingRequest) -> BoxFuture<'_, HandlerResult> {
+        Box::pin(self.handle_builtin(request))
     }
 }
@@ -1,49 +1,310 @@
 // mcp-server/src/tools/mod.rs
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 use anyhow::Result;
-use tokio::sync::RwLock;
-use mcp_protocol::types::tool::{Tool, ToolCallResult};
+use futures::future::{BoxFuture, FutureExt};
+use tokio::sync::broadcast;
+use mcp_protocol::messages::Meta;
+use mcp_protocol::types::tool::{Tool, ToolAnnotations, ToolCallResult};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-/// Tool handler function type
-pub type ToolHandler = Arc<dyn Fn(serde_json::Value) -> Result<ToolCallResult> + Send + Sync>;
+use crate::pagination;
+
+pub mod typed;
+pub mod validation;
+
+pub use validation::{InvalidToolArguments, InvalidToolOutput, SchemaViolation};
+
+/// Tool handler function type, called with the arguments and the request's `_meta`
+pub type ToolHandler = Arc<dyn Fn(serde_json::Value, Option<Meta>) -> BoxFuture<'static, Result<ToolCallResult>> + Send + Sync>;
+
+/// A tool paired with its handler, ready to be registered with a [`ToolManager`]
+///
+/// This is what the `#[mcp_tool]` attribute generates.
+pub struct ToolDefinition {
+    pub tool: Tool,
+    pub handler: ToolHandler,
+}
+
+impl ToolDefinition {
+    /// Create a definition for an async tool whose arguments are deserialized into `Args`
+    pub fn typed<Args, F, Fut, E>(name: &str, description: Option<&str>, handler: F) -> Self
+    where
+        Args: DeserializeOwned + JsonSchema,
+        F: Fn(Args) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<ToolCallResult, E>> + Send + 'static,
+        E: Into<anyhow::Error> + 'static,
+    {
+        let tool = Tool {
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            input_schema: typed::input_schema::<Args>(),
+            output_schema: None,
+            annotations: None,
+            title: None,
+        };
+        
+        Self {
+            tool,
+            handler: typed::typed_async_handler(name, handler),
+        }
+    }
+    
+    /// Set the human-readable name shown in place of the tool's name
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.tool.title = Some(title.to_string());
+        self
+    }
+
+    /// Attach behavior hints to the tool
+    pub fn with_annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.tool.annotations = Some(annotations);
+        self
+    }
+}
 
 /// Tool manager for registering and executing tools
 pub struct ToolManager {
-    tools: Arc<RwLock<HashMap<String, (Tool, ToolHandler)>>>,
+    tools: Arc<RwLock<BTreeMap<String, (Tool, ToolHandler)>>>, // Ordered by name for stable paging
+    update_tx: broadcast::Sender<()>, // Channel for notifying tool list changes
 }
 
 impl ToolManager {
     /// Create a new tool manager
     pub fn new() -> Self {
+        let (update_tx, _) = broadcast::channel(100);
+        
         Self {
-            tools: Arc::new(RwLock::new(HashMap::new())),
+            tools: Arc::new(RwLock::new(BTreeMap::new())),
+            update_tx,
         }
     }
     
     /// Register a new tool
     pub fn register_tool(&self, tool: Tool, handler: impl Fn(serde_json::Value) -> Result<ToolCallResult> + Send + Sync + 'static) {
-        let tools = self.tools.clone();
-        let handler = Arc::new(handler);
+        self.register_async_tool(tool, move |arguments| futures::future::ready(handler(arguments)));
+    }
+    
+    /// Register a new tool with an async handler
+    pub fn register_async_tool<F, Fut>(&self, tool: Tool, handler: F)
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ToolCallResult>> + Send + 'static,
+    {
+        self.register_definition(ToolDefinition {
+            tool,
+            handler: Arc::new(move |arguments, _| handler(arguments).boxed()),
+        });
+    }
+    
+    /// Register a tool with an async handler that is also given the request's `_meta`
+    pub fn register_tool_with_meta<F, Fut>(&self, tool: Tool, handler: F)
+    where
+        F: Fn(serde_json::Value, Option<Meta>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ToolCallResult>> + Send + 'static,
+    {
+        self.register_definition(ToolDefinition {
+            tool,
+            handler: Arc::new(move |arguments, meta| handler(arguments, meta).boxed()),
+        });
+    }
+    
+    /// Register a tool definition, such as one generated by `#[mcp_tool]`
+    ///
+    /// The tool is visible to `list_tools` as soon as this returns; an existing tool with
+    /// the same name is replaced.
+    pub fn register_definition(&self, definition: ToolDefinition) {
+        let ToolDefinition { tool, handler } = definition;
         
-        tokio::spawn(async move {
-            let mut tools = tools.write().await;
+        {
+            let mut tools = self.tools.write().unwrap();
             tools.insert(tool.name.clone(), (tool, handler));
-        });
+        }
+        
+        // Notify of update
+        let _ = self.update_tx.send(());
+    }
+    
+    /// Add a tool at runtime, failing if a tool with the same name is already registered
+    pub fn add_tool(&self, definition: ToolDefinition) -> Result<()> {
+        let ToolDefinition { tool, handler } = definition;
+        
+        {
+            let mut tools = self.tools.write().unwrap();
+            if tools.contains_key(&tool.name) {
+                return Err(anyhow::anyhow!("Tool already registered: {}", tool.name));
+            }
+            tools.insert(tool.name.clone(), (tool, handler));
+        }
+        
+        // Notify of update
+        let _ = self.update_tx.send(());
+        Ok(())
+    }
+    
+    /// Replace a registered tool at runtime, returning the previous definition
+    pub fn replace_tool(&self, definition: ToolDefinition) -> Result<Tool> {
+        let ToolDefinition { tool, handler } = definition;
+        
+        let previous = {
+            let mut tools = self.tools.write().unwrap();
+            let entry = tools
+                .get_mut(&tool.name)
+                .ok_or_else(|| anyhow::anyhow!("Tool not found: {}", tool.name))?;
+            std::mem::replace(entry, (tool, handler)).0
+        };
+        
+        // Notify of update
+        let _ = self.update_tx.send(());
+        Ok(previous)
     }
     
-    /// Get all registered tools
+    /// Remove a tool at runtime, returning its definition
+    pub fn unregister_tool(&self, name: &str) -> Result<Tool> {
+        let (tool, _) = self
+            .tools
+            .write()
+            .unwrap()
+            .remove(name)
+            .ok_or_else(|| anyhow::anyhow!("Tool not found: {}", name))?;
+        
+        // Notify of update
+        let _ = self.update_tx.send(());
+        Ok(tool)
+    }
+    
+    /// Subscribe to tool list updates
+    pub fn subscribe_to_updates(&self) -> broadcast::Receiver<()> {
+        self.update_tx.subscribe()
+    }
+    
+    /// Register a tool whose arguments are deserialized into `Args` before the handler runs
+    ///
+    /// The tool's input schema is generated from `Args`.
+    pub fn register_typed_tool<Args, F>(&self, name: &str, description: Option<&str>, handler: F)
+    where
+        Args: DeserializeOwned + JsonSchema,
+        F: Fn(Args) -> Result<ToolCallResult> + Send + Sync + 'static,
+    {
+        let tool = Tool {
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            input_schema: typed::input_schema::<Args>(),
+            output_schema: None,
+            annotations: None,
+            title: None,
+        };
+        
+        self.register_tool(tool, typed::typed_handler(name, handler));
+    }
+    
+    /// Register a tool with typed arguments whose output is returned as structured content
+    ///
+    /// Both the input and output schemas are generated, and the structured output is
+    /// validated against the output schema on every call. `structuredContent` must be a
+    /// JSON object, so `Output` must serialize as one: registration fails if its schema
+    /// is not `"type": "object"`.
+    pub fn register_structured_tool<Args, Output, F>(&self, name: &str, description: Option<&str>, handler: F) -> Result<()>
+    where
+        Args: DeserializeOwned + JsonSchema,
+        Output: Serialize + JsonSchema,
+        F: Fn(Args) -> Result<Output> + Send + Sync + 'static,
+    {
+        let output_schema = typed::output_schema::<Output>();
+        if output_schema.get("type") != Some(&serde_json::Value::from("object")) {
+            return Err(anyhow::anyhow!(
+                "Output schema of tool {} must have type \"object\", got {}",
+                name,
+                output_schema
+            ));
+        }
+        
+        let tool = Tool {
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            input_schema: typed::input_schema::<Args>(),
+            output_schema: Some(output_schema),
+            annotations: None,
+            title: None,
+        };
+        
+        self.register_tool(tool, typed::structured_handler(name, handler));
+        Ok(())
+    }
+    
+    /// Get all registered tools, ordered by name
     pub async fn list_tools(&self) -> Vec<Tool> {
-        let tools = self.tools.read().await;
+        let tools = self.tools.read().unwrap();
         tools.values().map(|(tool, _)| tool.clone()).collect()
     }
     
+    /// Get registered tools with pagination
+    pub async fn list_tools_page(&self, cursor: Option<String>, page_size: usize) -> Result<(Vec<Tool>, Option<String>)> {
+        let tools = self.tools.read().unwrap();
+        let (page, next_cursor) = pagination::paginate(&tools, cursor.as_deref(), page_size)?;
+        
+        Ok((page.into_iter().map(|(tool, _)| tool.clone()).collect(), next_cursor))
+    }
+    
     /// Execute a tool
+    ///
+    /// Arguments are validated against the tool's input schema before the handler runs;
+    /// validation failures are returned as an [`InvalidToolArguments`] error. If the tool
+    /// declares an output schema, successful results must carry structured content matching
+    /// it, or an [`InvalidToolOutput`] error is returned instead.
     pub async fn execute_tool(&self, name: &str, arguments: serde_json::Value) -> Result<ToolCallResult> {
-        let tools = self.tools.read().await;
-        let (_, handler) = tools.get(name).ok_or_else(|| anyhow::anyhow!("Tool not found: {}", name))?;
+        self.execute_tool_with_meta(name, arguments, None).await
+    }
+    
+    /// Execute a tool, handing the request's `_meta` to its handler
+    pub async fn execute_tool_with_meta(
+        &self,
+        name: &str,
+        arguments: serde_json::Value,
+        meta: Option<Meta>,
+    ) -> Result<ToolCallResult> {
+        // Release the lock before running the handler
+        let (handler, output_schema) = {
+            let tools = self.tools.read().unwrap();
+            let (tool, handler) = tools.get(name).ok_or_else(|| anyhow::anyhow!("Tool not found: {}", name))?;
+            
+            let violations = validation::validate(&tool.input_schema, &arguments);
+            if !violations.is_empty() {
+                return Err(InvalidToolArguments {
+                    tool: name.to_string(),
+                    violations,
+                }
+                .into());
+            }
+            
+            (handler.clone(), tool.output_schema.clone())
+        };
+        
+        let result = handler(arguments, meta).await?;
+        
+        if let Some(output_schema) = output_schema {
+            if result.is_error != Some(true) {
+                let violations = match &result.structured_content {
+                    Some(content) => validation::validate(&output_schema, content),
+                    None => vec![SchemaViolation {
+                        instance_path: String::new(),
+                        message: "tool declares an output schema but returned no structured content".to_string(),
+                    }],
+                };
+                if !violations.is_empty() {
+                    return Err(InvalidToolOutput {
+                        tool: name.to_string(),
+                        violations,
+                    }
+                    .into());
+                }
+            }
+        }
         
-        handler(arguments)
+        Ok(result)
     }
 }
 
@@ -0,0 +1,123 @@
+// mcp-server/src/tools/typed.rs
+use anyhow::Result;
+use mcp_protocol::types::content::ContentBlock;
+use mcp_protocol::types::tool::ToolCallResult;
+use schemars::gen::SchemaSettings;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use futures::future::FutureExt;
+use std::future::Future;
+use std::sync::Arc;
+
+use super::validation::{InvalidToolArguments, SchemaViolation};
+use super::ToolHandler;
+
+/// Generate a tool input schema from a Rust type
+pub fn input_schema<T: JsonSchema>() -> serde_json::Value {
+    generate_schema::<T>()
+}
+
+/// Generate a tool output schema from a Rust type
+pub fn output_schema<T: JsonSchema>() -> serde_json::Value {
+    generate_schema::<T>()
+}
+
+/// Generate a JSON Schema for a Rust type
+///
+/// Subschemas are inlined so the result is self-contained and can be sent as a tool's
+/// `inputSchema` or `outputSchema` as-is. The root title (the Rust type name) is dropped.
+fn generate_schema<T: JsonSchema>() -> serde_json::Value {
+    let settings = SchemaSettings::draft07().with(|settings| {
+        settings.inline_subschemas = true;
+        settings.meta_schema = None;
+    });
+    let mut schema = settings.into_generator().into_root_schema_for::<T>();
+    schema.schema.metadata().title = None;
+
+    serde_json::to_value(schema).unwrap_or_else(|_| json!({ "type": "object" }))
+}
+
+/// Wrap a typed handler so it can be registered as a plain JSON tool handler
+///
+/// Arguments that cannot be deserialized into `Args` are reported as [`InvalidToolArguments`],
+/// which the server turns into an `INVALID_PARAMS` error.
+pub fn typed_handler<Args, F>(
+    tool_name: &str,
+    handler: F,
+) -> impl Fn(serde_json::Value) -> Result<ToolCallResult> + Send + Sync + 'static
+where
+    Args: DeserializeOwned,
+    F: Fn(Args) -> Result<ToolCallResult> + Send + Sync + 'static,
+{
+    let tool_name = tool_name.to_string();
+
+    move |arguments| {
+        let args = deserialize_arguments::<Args>(&tool_name, arguments)?;
+
+        handler(args)
+    }
+}
+
+/// Wrap a typed handler whose output is returned as structured content
+///
+/// See [`structured_result`] for how the output is encoded.
+pub fn structured_handler<Args, Output, F>(
+    tool_name: &str,
+    handler: F,
+) -> impl Fn(serde_json::Value) -> Result<ToolCallResult> + Send + Sync + 'static
+where
+    Args: DeserializeOwned,
+    Output: Serialize,
+    F: Fn(Args) -> Result<Output> + Send + Sync + 'static,
+{
+    typed_handler(tool_name, move |args: Args| structured_result(&handler(args)?))
+}
+
+/// Build a successful tool result carrying `output` as structured content
+///
+/// The serialized JSON is also returned as a text block for clients that do not
+/// understand `structuredContent`.
+pub fn structured_result<T: Serialize>(output: &T) -> Result<ToolCallResult> {
+    let value = serde_json::to_value(output)?;
+
+    Ok(ToolCallResult {
+        content: vec![ContentBlock::text(value.to_string())],
+        is_error: Some(false),
+        structured_content: Some(value),
+        meta: None,
+    })
+}
+
+/// Async counterpart of [`typed_handler`]
+///
+/// The handler may fail with any error type that converts into [`anyhow::Error`].
+pub fn typed_async_handler<Args, F, Fut, E>(tool_name: &str, handler: F) -> ToolHandler
+where
+    Args: DeserializeOwned,
+    F: Fn(Args) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = std::result::Result<ToolCallResult, E>> + Send + 'static,
+    E: Into<anyhow::Error> + 'static,
+{
+    let tool_name = tool_name.to_string();
+
+    Arc::new(move |arguments, _| match deserialize_arguments::<Args>(&tool_name, arguments) {
+        Ok(args) => handler(args).map(|result| result.map_err(Into::into)).boxed(),
+        Err(err) => futures::future::ready(Err(err.into())).boxed(),
+    })
+}
+
+/// Deserialize tool arguments, reporting failures as [`InvalidToolArguments`]
+fn deserialize_arguments<Args: DeserializeOwned>(
+    tool_name: &str,
+    arguments: serde_json::Value,
+) -> std::result::Result<Args, InvalidToolArguments> {
+    serde_json::from_value::<Args>(arguments).map_err(|err| InvalidToolArguments {
+        tool: tool_name.to_string(),
+        violations: vec![SchemaViolation {
+            instance_path: String::new(),
+            message: err.to_string(),
+        }],
+    })
+}
@@ -0,0 +1,372 @@
+// mcp-server/src/tools/validation.rs
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// A single violation found while validating a value against a JSON Schema
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaViolation {
+    /// JSON Pointer to the offending value (empty string for the root)
+    #[serde(rename = "instancePath")]
+    pub instance_path: String,
+
+    /// Human-readable description of the violation
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.instance_path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.instance_path, self.message)
+        }
+    }
+}
+
+/// Error returned when tool arguments do not match the tool's input schema
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Invalid arguments for tool {tool}: {}", join_violations(.violations))]
+pub struct InvalidToolArguments {
+    /// Name of the tool that was called
+    pub tool: String,
+
+    /// Every violation found in the arguments
+    pub violations: Vec<SchemaViolation>,
+}
+
+/// Error returned when a tool's structured output does not match its output schema
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Invalid structured output from tool {tool}: {}", join_violations(.violations))]
+pub struct InvalidToolOutput {
+    /// Name of the tool that was called
+    pub tool: String,
+
+    /// Every violation found in the output
+    pub violations: Vec<SchemaViolation>,
+}
+
+fn join_violations(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Validate a value against a JSON Schema, returning every violation found
+///
+/// Supports the subset of JSON Schema used to describe tool inputs: `type`, `enum`, `const`,
+/// numeric ranges, string lengths, `items`, `required`, `properties`, `additionalProperties`,
+/// `pattern`, the `allOf`/`anyOf`/`oneOf`/`not` combinators and local `$ref`s into
+/// `definitions`/`$defs`. Unknown keywords (including `format`) are ignored.
+pub fn validate(schema: &Value, instance: &Value) -> Vec<SchemaViolation> {
+    let mut validator = Validator {
+        root: schema,
+        violations: Vec::new(),
+    };
+    validator.check(schema, instance, "");
+    validator.violations
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    violations: Vec<SchemaViolation>,
+}
+
+impl<'a> Validator<'a> {
+    fn report(&mut self, path: &str, message: String) {
+        self.violations.push(SchemaViolation {
+            instance_path: path.to_string(),
+            message,
+        });
+    }
+
+    /// Run a nested validation and return its violations without recording them
+    fn probe(&self, schema: &'a Value, instance: &Value, path: &str) -> Vec<SchemaViolation> {
+        let mut nested = Validator {
+            root: self.root,
+            violations: Vec::new(),
+        };
+        nested.check(schema, instance, path);
+        nested.violations
+    }
+
+    fn check(&mut self, schema: &'a Value, instance: &Value, path: &str) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                self.report(path, "no value is allowed here".to_string());
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+            match resolve_ref(self.root, reference) {
+                Some(target) => self.check(target, instance, path),
+                None => self.report(path, format!("unresolvable schema reference {}", reference)),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let allowed: Vec<&str> = match expected {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+                _ => Vec::new(),
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(t, instance)) {
+                self.report(
+                    path,
+                    format!(
+                        "expected {}, found {}",
+                        allowed.join(" or "),
+                        type_name(instance)
+                    ),
+                );
+                // The remaining keywords only make sense for the declared type
+                return;
+            }
+        }
+
+        if let Some(Value::Array(options)) = schema.get("enum") {
+            if !options.contains(instance) {
+                self.report(
+                    path,
+                    format!("value must be one of {}", Value::Array(options.clone())),
+                );
+            }
+        }
+
+        if let Some(expected) = schema.get("const") {
+            if expected != instance {
+                self.report(path, format!("value must be {}", expected));
+            }
+        }
+
+        match instance {
+            Value::Number(n) => self.check_number(schema, n.as_f64().unwrap_or(f64::NAN), path),
+            Value::String(s) => self.check_string(schema, s, path),
+            Value::Array(items) => self.check_array(schema, items, path),
+            Value::Object(object) => self.check_object(schema, object, path),
+            _ => {}
+        }
+
+        if let Some(Value::Array(all)) = schema.get("allOf") {
+            for subschema in all {
+                self.check(subschema, instance, path);
+            }
+        }
+
+        if let Some(Value::Array(any)) = schema.get("anyOf") {
+            let matched = any
+                .iter()
+                .any(|subschema| self.probe(subschema, instance, path).is_empty());
+            if !matched {
+                self.report(path, "value does not match any of the allowed schemas".to_string());
+            }
+        }
+
+        if let Some(Value::Array(one)) = schema.get("oneOf") {
+            let matches = one
+                .iter()
+                .filter(|subschema| self.probe(subschema, instance, path).is_empty())
+                .count();
+            if matches != 1 {
+                self.report(
+                    path,
+                    format!("value must match exactly one schema, but matched {}", matches),
+                );
+            }
+        }
+
+        if let Some(not) = schema.get("not") {
+            if self.probe(not, instance, path).is_empty() {
+                self.report(path, "value matches a schema it must not match".to_string());
+            }
+        }
+    }
+
+    fn check_number(&mut self, schema: &serde_json::Map<String, Value>, value: f64, path: &str) {
+        if let Some(minimum) = schema.get("minimum").and_then(|m| m.as_f64()) {
+            if value < minimum {
+                self.report(path, format!("{} is less than the minimum of {}", value, minimum));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(|m| m.as_f64()) {
+            if value > maximum {
+                self.report(path, format!("{} is greater than the maximum of {}", value, maximum));
+            }
+        }
+        if let Some(minimum) = schema.get("exclusiveMinimum").and_then(|m| m.as_f64()) {
+            if value <= minimum {
+                self.report(path, format!("{} must be greater than {}", value, minimum));
+            }
+        }
+        if let Some(maximum) = schema.get("exclusiveMaximum").and_then(|m| m.as_f64()) {
+            if value >= maximum {
+                self.report(path, format!("{} must be less than {}", value, maximum));
+            }
+        }
+        if let Some(divisor) = schema.get("multipleOf").and_then(|m| m.as_f64()) {
+            if divisor > 0.0 && !is_multiple(value, divisor) {
+                self.report(path, format!("{} is not a multiple of {}", value, divisor));
+            }
+        }
+    }
+
+    fn check_string(&mut self, schema: &serde_json::Map<String, Value>, value: &str, path: &str) {
+        let length = value.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+            if length < min {
+                self.report(path, format!("string is shorter than {} characters", min));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+            if length > max {
+                self.report(path, format!("string is longer than {} characters", max));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(|p| p.as_str()) {
+            // A pattern the regex engine cannot compile is a schema bug, not the caller's
+            if let Ok(regex) = Regex::new(pattern) {
+                if !regex.is_match(value) {
+                    self.report(path, format!("string does not match the pattern {}", pattern));
+                }
+            }
+        }
+    }
+
+    fn check_array(&mut self, schema: &'a serde_json::Map<String, Value>, items: &[Value], path: &str) {
+        let count = items.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64()) {
+            if count < min {
+                self.report(path, format!("array has fewer than {} items", min));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(|m| m.as_u64()) {
+            if count > max {
+                self.report(path, format!("array has more than {} items", max));
+            }
+        }
+        if schema.get("uniqueItems").and_then(|u| u.as_bool()) == Some(true) {
+            let duplicated = items
+                .iter()
+                .enumerate()
+                .any(|(i, item)| items[..i].contains(item));
+            if duplicated {
+                self.report(path, "array items must be unique".to_string());
+            }
+        }
+
+        match schema.get("items") {
+            Some(Value::Array(tuple)) => {
+                for (index, (item, subschema)) in items.iter().zip(tuple).enumerate() {
+                    self.check(subschema, item, &child_path(path, &index.to_string()));
+                }
+            }
+            Some(subschema) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.check(subschema, item, &child_path(path, &index.to_string()));
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn check_object(
+        &mut self,
+        schema: &'a serde_json::Map<String, Value>,
+        object: &serde_json::Map<String, Value>,
+        path: &str,
+    ) {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(|r| r.as_str()) {
+                if !object.contains_key(name) {
+                    self.report(path, format!("missing required property \"{}\"", name));
+                }
+            }
+        }
+
+        let count = object.len() as u64;
+        if let Some(min) = schema.get("minProperties").and_then(|m| m.as_u64()) {
+            if count < min {
+                self.report(path, format!("object has fewer than {} properties", min));
+            }
+        }
+        if let Some(max) = schema.get("maxProperties").and_then(|m| m.as_u64()) {
+            if count > max {
+                self.report(path, format!("object has more than {} properties", max));
+            }
+        }
+
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        let additional = schema.get("additionalProperties");
+
+        for (name, value) in object {
+            let property_path = child_path(path, name);
+            match properties.and_then(|p| p.get(name)) {
+                Some(subschema) => self.check(subschema, value, &property_path),
+                None => match additional {
+                    Some(Value::Bool(false)) => {
+                        self.report(path, format!("unexpected property \"{}\"", name));
+                    }
+                    Some(subschema) => self.check(subschema, value, &property_path),
+                    None => {}
+                },
+            }
+        }
+    }
+}
+
+/// Whether `value` is a whole multiple of `divisor`, allowing for floating point rounding
+///
+/// `0.3 / 0.1` is `2.9999999999999996`, so the quotient is compared to the nearest integer
+/// with a tolerance relative to its size rather than checked for a zero fraction.
+fn is_multiple(value: f64, divisor: f64) -> bool {
+    let quotient = value / divisor;
+    (quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0)
+}
+
+/// Resolve a local `#/...` reference against the root schema
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+fn matches_type(expected: &str, instance: &Value) -> bool {
+    match expected {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => match instance {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().map(|f| f.fract() == 0.0).unwrap_or(false)
+            }
+            _ => false,
+        },
+        _ => true,
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Append a segment to a JSON Pointer, escaping `~` and `/`
+fn child_path(path: &str, segment: &str) -> String {
+    format!("{}/{}", path, segment.replace('~', "~0").replace('/', "~1"))
+}
@@ -3,9 +3,12 @@ use anyhow::Result;
 use async_trait::async_trait;
 use mcp_protocol::messages::JsonRpcMessage;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Mutex};
 use tracing::debug;
 
+/// Serializes writes to stdout so messages sent from concurrent tasks never interleave
+static STDOUT_LOCK: Mutex<()> = Mutex::const_new(());
+
 /// Transport implementation that uses stdio to communicate with the client
 #[derive(Clone)]
 pub struct StdioTransport;
@@ -17,6 +20,12 @@ impl StdioTransport {
     }
 }
 
+impl Default for StdioTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl super::Transport for StdioTransport {
     async fn start(&self, message_tx: mpsc::Sender<JsonRpcMessage>) -> Result<()> {
@@ -46,12 +55,13 @@ impl super::Transport for StdioTransport {
     }
 
     async fn send(&self, message: JsonRpcMessage) -> Result<()> {
-        let mut stdout = tokio::io::stdout();
-        let serialized = serde_json::to_string(&message)?;
-
+        let mut serialized = serde_json::to_string(&message)?;
         debug!("Sending message: {}", serialized);
+        serialized.push('\n');
+
+        let _guard = STDOUT_LOCK.lock().await;
+        let mut stdout = tokio::io::stdout();
         stdout.write_all(serialized.as_bytes()).await?;
-        stdout.write_all(b"\n").await?;
         stdout.flush().await?;
 
         Ok(())
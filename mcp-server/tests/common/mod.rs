@@ -0,0 +1,186 @@
+// mcp-server/tests/common/mod.rs
+//! Fixtures shared by the server integration tests
+#![allow(dead_code)] // Each test crate uses a different subset
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use modelcontextprotocol_server::mcp_protocol::messages::{
+    JsonRpcErrorObject, JsonRpcMessage, JsonRpcNotification,
+};
+use modelcontextprotocol_server::{ServerBuilder, Transport};
+
+/// Transport fed and drained through channels
+#[derive(Clone)]
+pub struct ChannelTransport {
+    incoming: Arc<Mutex<Option<mpsc::UnboundedReceiver<JsonRpcMessage>>>>,
+    outgoing: mpsc::UnboundedSender<JsonRpcMessage>,
+}
+
+#[async_trait]
+impl Transport for ChannelTransport {
+    async fn start(&self, message_tx: mpsc::Sender<JsonRpcMessage>) -> Result<()> {
+        let mut incoming = self.incoming.lock().unwrap().take().unwrap();
+        tokio::spawn(async move {
+            while let Some(message) = incoming.recv().await {
+                if message_tx.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn send(&self, message: JsonRpcMessage) -> Result<()> {
+        self.outgoing.send(message)?;
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Transport> {
+        Box::new(self.clone())
+    }
+}
+
+/// A running server and the client end of its transport
+pub struct Harness {
+    client_tx: mpsc::UnboundedSender<JsonRpcMessage>,
+    client_rx: mpsc::UnboundedReceiver<JsonRpcMessage>,
+    server: JoinHandle<Result<()>>,
+    next_id: i64,
+    /// Notifications received while waiting for replies, oldest first
+    pub notifications: Vec<JsonRpcNotification>,
+}
+
+impl Harness {
+    /// Build and run the server without initializing a session
+    pub fn connect(builder: ServerBuilder) -> Self {
+        let (client_tx, incoming) = mpsc::unbounded_channel();
+        let (outgoing, client_rx) = mpsc::unbounded_channel();
+        let transport = ChannelTransport {
+            incoming: Arc::new(Mutex::new(Some(incoming))),
+            outgoing,
+        };
+        let server = builder.with_transport(transport).build().unwrap();
+
+        Self {
+            client_tx,
+            client_rx,
+            server: tokio::spawn(async move { server.run().await }),
+            next_id: 0,
+            notifications: Vec::new(),
+        }
+    }
+
+    /// Build and run the server and complete the initialization handshake
+    pub async fn start(builder: ServerBuilder) -> Self {
+        let mut harness = Self::connect(builder);
+        harness.initialize().await;
+        harness
+    }
+
+    /// Run the initialization handshake, returning the initialize result
+    pub async fn initialize(&mut self) -> Value {
+        let result = self
+            .call(
+                "initialize",
+                json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {},
+                    "clientInfo": {"name": "test", "version": "1"}
+                }),
+            )
+            .await;
+        self.notify("notifications/initialized", None);
+        result
+    }
+
+    /// Send a notification
+    pub fn notify(&self, method: &str, params: Option<Value>) {
+        self.client_tx
+            .send(JsonRpcMessage::notification(method, params))
+            .unwrap();
+    }
+
+    /// Send a request without waiting for the reply, returning its id
+    pub fn send_request(&mut self, method: &str, params: Option<Value>) -> i64 {
+        self.next_id += 1;
+        self.client_tx
+            .send(JsonRpcMessage::request(self.next_id, method, params))
+            .unwrap();
+        self.next_id
+    }
+
+    /// Wait for the next response or error, keeping any notifications that arrive first
+    pub async fn reply(&mut self) -> JsonRpcMessage {
+        loop {
+            match self.client_rx.recv().await.unwrap() {
+                JsonRpcMessage::Notification(notification) => self.notifications.push(notification),
+                reply => return reply,
+            }
+        }
+    }
+
+    /// Whether the server replies to anything within `wait`
+    pub async fn replies_within(&mut self, wait: Duration) -> bool {
+        tokio::time::timeout(wait, self.reply()).await.is_ok()
+    }
+
+    /// Send a request and wait for its reply
+    pub async fn request(&mut self, method: &str, params: Option<Value>) -> JsonRpcMessage {
+        self.send_request(method, params);
+        self.reply().await
+    }
+
+    /// Send a request and return its result, failing if the server answers with an error
+    pub async fn call(&mut self, method: &str, params: Value) -> Value {
+        match self.request(method, Some(params)).await {
+            JsonRpcMessage::Response(response) => response.result,
+            other => panic!("expected a response to {}, got {:?}", method, other),
+        }
+    }
+
+    /// Send a request and return its error, failing if the server answers with a result
+    pub async fn call_err(&mut self, method: &str, params: Value) -> JsonRpcErrorObject {
+        match self.request(method, Some(params)).await {
+            JsonRpcMessage::Error(error) => error.error,
+            other => panic!("expected an error from {}, got {:?}", method, other),
+        }
+    }
+
+    /// Wait until `count` notifications have arrived or `wait` passes, then take them all
+    pub async fn take_notifications(
+        &mut self,
+        count: usize,
+        wait: Duration,
+    ) -> Vec<JsonRpcNotification> {
+        let _ = tokio::time::timeout(wait, async {
+            while self.notifications.len() < count {
+                match self.client_rx.recv().await {
+                    Some(JsonRpcMessage::Notification(notification)) => {
+                        self.notifications.push(notification)
+                    }
+                    Some(other) => panic!("unexpected message {:?}", other),
+                    None => break,
+                }
+            }
+        })
+        .await;
+        std::mem::take(&mut self.notifications)
+    }
+
+    /// Close the client end and wait for the server to shut down
+    pub async fn stop(self) {
+        drop(self.client_tx);
+        self.server.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+// mcp-server/tests/completion.rs
+use serde_json::{json, Value};
+
+use modelcontextprotocol_server::completion::{
+    fuzzy_matches, prefix_matches, MAX_COMPLETION_VALUES,
+};
+use modelcontextprotocol_server::{CompletionQuery, ServerBuilder};
+
+mod common;
+use common::Harness;
+
+/// Repositories per owner, for completing `name` once `owner` is chosen
+fn repos(owner: Option<&String>) -> Vec<String> {
+    let repos: &[&str] = match owner.map(String::as_str) {
+        Some("modelcontextprotocol") => &["rust-sdk", "specification", "servers"],
+        Some("rust-lang") => &["rust", "cargo"],
+        _ => &[],
+    };
+    repos.iter().map(|repo| repo.to_string()).collect()
+}
+
+/// Ask for completions of one argument
+async fn complete(
+    harness: &mut Harness,
+    r#ref: Value,
+    name: &str,
+    value: &str,
+    context: Value,
+) -> Value {
+    let result = harness
+        .call(
+            "completion/complete",
+            json!({
+                "ref": r#ref,
+                "argument": {"name": name, "value": value},
+                "context": context,
+            }),
+        )
+        .await;
+    result["completion"].clone()
+}
+
+#[tokio::test]
+async fn providers_see_previously_resolved_arguments() {
+    let builder = ServerBuilder::new("completion-test", "0.1.0")
+        .with_template_completion(
+            "repo://{owner}/{name}",
+            |query: CompletionQuery| async move {
+                Ok(query.prefix_matches(repos(query.arguments.get("owner"))))
+            },
+        )
+        .with_prompt_completion("review", "name", |query: CompletionQuery| async move {
+            Ok(repos(query.arguments.get("owner")))
+        });
+    let mut harness = Harness::connect(builder);
+    let result = harness.initialize().await;
+    assert_eq!(result["capabilities"]["completions"], json!({}));
+
+    let template = json!({"type": "ref/resource", "uri": "repo://{owner}/{name}"});
+    let context = json!({"arguments": {"owner": "modelcontextprotocol"}});
+
+    let completion = complete(&mut harness, template.clone(), "name", "s", context.clone()).await;
+    assert_eq!(completion["values"], json!(["specification", "servers"]));
+
+    // Without a context the provider has nothing to narrow by
+    let completion = complete(&mut harness, template, "name", "s", json!({})).await;
+    assert_eq!(completion["values"], json!([]));
+
+    let prompt = json!({"type": "ref/prompt", "name": "review"});
+    let completion = complete(&mut harness, prompt, "name", "", context).await;
+    assert_eq!(
+        completion["values"],
+        json!(["rust-sdk", "specification", "servers"])
+    );
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn responses_are_capped_with_an_accurate_total() {
+    let builder = ServerBuilder::new("completion-test", "0.1.0").with_prompt_completion(
+        "pick",
+        "number",
+        |query: CompletionQuery| async move {
+            Ok(query.prefix_matches((0..250).map(|n| n.to_string())))
+        },
+    );
+    let mut harness = Harness::start(builder).await;
+    let prompt = json!({"type": "ref/prompt", "name": "pick"});
+
+    let completion = complete(&mut harness, prompt.clone(), "number", "", json!({})).await;
+    assert_eq!(
+        completion["values"].as_array().unwrap().len(),
+        MAX_COMPLETION_VALUES
+    );
+    assert_eq!(completion["values"][0], "0");
+    assert_eq!(completion["total"], 250);
+    assert_eq!(completion["hasMore"], true);
+
+    let completion = complete(&mut harness, prompt, "number", "24", json!({})).await;
+    assert_eq!(
+        completion["values"],
+        json!(["24", "240", "241", "242", "243", "244", "245", "246", "247", "248", "249"])
+    );
+    assert_eq!(completion["total"], 11);
+    assert_eq!(completion["hasMore"], false);
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn parameters_are_found_in_any_template_expression() {
+    let template = "file:///{+root}/{name}{?version,lang}";
+    let builder = ServerBuilder::new("completion-test", "0.1.0")
+        .with_template_completion(template, |query: CompletionQuery| async move {
+            Ok(vec![query.argument])
+        });
+    let mut harness = Harness::start(builder).await;
+    let reference = json!({"type": "ref/resource", "uri": template});
+
+    for parameter in ["root", "name", "version", "lang"] {
+        let completion = complete(&mut harness, reference.clone(), parameter, "", json!({})).await;
+        assert_eq!(completion["values"], json!([parameter]), "{}", parameter);
+    }
+
+    let completion = complete(&mut harness, reference, "missing", "", json!({})).await;
+    assert_eq!(completion["values"], json!([]));
+
+    harness.stop().await;
+}
+
+#[test]
+fn prefix_matches_ignore_case_and_keep_order() {
+    let candidates = ["Rust", "ruby", "python", "rust-analyzer"];
+    assert_eq!(
+        prefix_matches(candidates, "ru"),
+        ["Rust", "ruby", "rust-analyzer"]
+    );
+    assert_eq!(prefix_matches(candidates, ""), candidates);
+}
+
+#[test]
+fn fuzzy_matches_rank_exact_then_prefix_then_substring_then_scattered() {
+    let candidates = [
+        "typescript",
+        "script",
+        "scripting",
+        "javascript",
+        "sc-ript",
+        "rust",
+    ];
+    assert_eq!(
+        fuzzy_matches(candidates, "script"),
+        ["script", "scripting", "typescript", "javascript", "sc-ript"]
+    );
+
+    // Tighter scattered matches rank first
+    assert_eq!(
+        fuzzy_matches(["main.rs", "m.rs", "m-a-i-n"], "mrs"),
+        ["m.rs", "main.rs"]
+    );
+
+    // The span is measured from the occurrence of the first character closest to the rest
+    assert_eq!(
+        fuzzy_matches(["mod.rs", "mxxxxm.rs", "main.rs"], "mrs"),
+        ["mxxxxm.rs", "mod.rs", "main.rs"]
+    );
+}
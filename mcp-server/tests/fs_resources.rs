@@ -0,0 +1,235 @@
+// mcp-server/tests/fs_resources.rs
+#![cfg(feature = "fs")]
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use modelcontextprotocol_server::context::RequestContext;
+use modelcontextprotocol_server::mcp_protocol::types::resource::ResourceContent;
+use modelcontextprotocol_server::resources::fs::FileSystemProvider;
+use modelcontextprotocol_server::resources::{ResourceManager, ResourceNotFound};
+
+/// A scratch directory removed when dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("mcp-fs-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    /// Write a file, creating its parent directories
+    fn write(&self, relative: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.0.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// The URI of a file under the provider's root; the test paths need no percent-encoding
+fn uri(provider: &FileSystemProvider, relative: &str) -> String {
+    format!("file://{}/{}", provider.root().display(), relative)
+}
+
+/// Register the provider's files, returning the names of every resource
+async fn register(provider: &FileSystemProvider) -> (Arc<ResourceManager>, Vec<String>) {
+    let manager = Arc::new(ResourceManager::new());
+    provider.register(&manager).unwrap();
+    let (resources, _) = manager.list_resources(None, 100).await.unwrap();
+    let mut names: Vec<_> = resources
+        .into_iter()
+        .map(|resource| resource.name)
+        .collect();
+    names.sort();
+    (manager, names)
+}
+
+async fn read(manager: &ResourceManager, uri: &str) -> anyhow::Result<ResourceContent> {
+    let mut contents = manager.read_resource(RequestContext::new(uri)).await?;
+    assert_eq!(contents.len(), 1);
+    Ok(contents.remove(0))
+}
+
+fn is_not_found(result: anyhow::Result<ResourceContent>) -> bool {
+    result.is_err_and(|err| err.is::<ResourceNotFound>())
+}
+
+#[tokio::test]
+async fn parent_directory_components_are_rejected() {
+    let dir = TempDir::new("parent");
+    dir.write("root/docs/guide.md", "# Guide");
+    dir.write("root/notes.txt", "notes");
+    dir.write("secret.txt", "secret");
+    let provider = FileSystemProvider::new(dir.0.join("root")).unwrap();
+    let (manager, _) = register(&provider).await;
+
+    let content = read(&manager, &uri(&provider, "notes.txt")).await.unwrap();
+    assert_eq!(content.text.as_deref(), Some("notes"));
+
+    // Not served, even when the path would land back inside the root
+    for relative in [
+        "docs/../notes.txt",
+        "../secret.txt",
+        "docs/%2E%2E/notes.txt",
+    ] {
+        let uri = uri(&provider, relative);
+        assert!(is_not_found(read(&manager, &uri).await), "{}", relative);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn symlinks_are_only_followed_inside_the_root() {
+    let dir = TempDir::new("symlink");
+    let guide = dir.write("root/docs/guide.md", "# Guide");
+    let secret = dir.write("outside/secret.txt", "secret");
+    std::os::unix::fs::symlink(&secret, dir.0.join("root/escape.txt")).unwrap();
+    std::os::unix::fs::symlink(dir.0.join("outside"), dir.0.join("root/outside")).unwrap();
+    std::os::unix::fs::symlink(&guide, dir.0.join("root/guide.md")).unwrap();
+    // Walking these would cover the whole filesystem, or the tree the root is in
+    std::os::unix::fs::symlink("/", dir.0.join("root/slash")).unwrap();
+    std::os::unix::fs::symlink(&dir.0, dir.0.join("root/parent")).unwrap();
+    let provider = FileSystemProvider::new(dir.0.join("root")).unwrap();
+    let (manager, names) = register(&provider).await;
+
+    assert_eq!(names, ["docs/guide.md", "guide.md"]);
+
+    let content = read(&manager, &uri(&provider, "guide.md")).await.unwrap();
+    assert_eq!(content.text.as_deref(), Some("# Guide"));
+
+    // Not registered, and not readable by guessing the URI either
+    for relative in ["escape.txt", "outside/secret.txt"] {
+        let uri = uri(&provider, relative);
+        assert!(is_not_found(read(&manager, &uri).await), "{}", relative);
+    }
+}
+
+#[tokio::test]
+async fn include_and_exclude_globs_filter_relative_paths() {
+    let dir = TempDir::new("globs");
+    dir.write("docs/guide.md", "# Guide");
+    dir.write("docs/drafts/plan.md", "# Plan");
+    dir.write("docs/logo.png", [0x89, b'P', b'N', b'G']);
+    dir.write("README.md", "# Readme");
+    let provider = FileSystemProvider::new(&dir.0)
+        .unwrap()
+        .with_include("**/*.md")
+        .with_exclude("**/drafts/**");
+    let (manager, names) = register(&provider).await;
+
+    assert_eq!(names, ["README.md", "docs/guide.md"]);
+    assert!(is_not_found(
+        read(&manager, &uri(&provider, "docs/drafts/plan.md")).await
+    ));
+    assert!(is_not_found(
+        read(&manager, &uri(&provider, "docs/logo.png")).await
+    ));
+
+    // With no include globs every file is served
+    let provider = FileSystemProvider::new(&dir.0).unwrap();
+    let (_, names) = register(&provider).await;
+    assert_eq!(
+        names,
+        [
+            "README.md",
+            "docs/drafts/plan.md",
+            "docs/guide.md",
+            "docs/logo.png"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn text_is_served_as_text_and_binary_as_base64() {
+    let png = [
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D,
+    ];
+    let dir = TempDir::new("content");
+    dir.write("notes.txt", "caf\u{e9}");
+    dir.write("Makefile", "all:\n\tcargo build\n");
+    dir.write("image", png);
+    dir.write("nul.txt", "a\0b");
+    dir.write("latin1", [b'c', b'a', b'f', 0xE9]);
+    let mut long = "a".repeat(8191).into_bytes();
+    long.extend("\u{e9}t\u{e9}".as_bytes());
+    dir.write("long", &long);
+    let provider = FileSystemProvider::new(&dir.0).unwrap();
+    let (manager, _) = register(&provider).await;
+    let read = |relative: &str| {
+        let uri = uri(&provider, relative);
+        let manager = manager.clone();
+        async move { read(&manager, &uri).await.unwrap() }
+    };
+
+    let content = read("notes.txt").await;
+    assert_eq!(content.mime_type, "text/plain");
+    assert_eq!(content.text.as_deref(), Some("caf\u{e9}"));
+    assert_eq!(content.blob, None);
+
+    // Without a known extension, text is recognized from the bytes
+    let content = read("Makefile").await;
+    assert_eq!(content.mime_type, "text/plain");
+    assert_eq!(content.text.as_deref(), Some("all:\n\tcargo build\n"));
+
+    let content = read("image").await;
+    assert_eq!(content.mime_type, "image/png");
+    assert_eq!(content.text, None);
+    assert_eq!(content.blob.as_deref(), Some("iVBORw0KGgoAAAAN"));
+
+    // Valid UTF-8 with a NUL byte is still binary
+    let content = read("nul.txt").await;
+    assert_eq!(content.text, None);
+    assert_eq!(content.blob.as_deref(), Some("YQBi"));
+
+    // A file ending part way through a UTF-8 sequence is not text
+    let content = read("latin1").await;
+    assert_eq!(content.mime_type, "application/octet-stream");
+    assert_eq!(content.text, None);
+    assert_eq!(content.blob.as_deref(), Some("Y2Fm6Q=="));
+
+    // One cut off by the sniffing window is
+    let content = read("long").await;
+    assert_eq!(content.mime_type, "text/plain");
+    assert!(content.text.unwrap().ends_with("\u{e9}t\u{e9}"));
+}
+
+#[tokio::test]
+async fn files_over_the_size_limit_are_not_read() {
+    let dir = TempDir::new("size");
+    dir.write("small.txt", "0123456789");
+    dir.write("large.txt", "0123456789a");
+    let provider = FileSystemProvider::new(&dir.0)
+        .unwrap()
+        .with_max_file_size(10);
+    let (manager, names) = register(&provider).await;
+
+    // Still listed, so clients can see what they are missing
+    assert_eq!(names, ["large.txt", "small.txt"]);
+
+    let content = read(&manager, &uri(&provider, "small.txt")).await.unwrap();
+    assert_eq!(content.text.as_deref(), Some("0123456789"));
+
+    let uri = uri(&provider, "large.txt");
+    let error = read(&manager, &uri).await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("{} is larger than the 10 byte limit", uri)
+    );
+}
+
+#[test]
+fn roots_must_be_existing_directories() {
+    let dir = TempDir::new("roots");
+    let file = dir.write("file.txt", "");
+    assert!(FileSystemProvider::new(&file).is_err());
+    assert!(FileSystemProvider::new(Path::new(&dir.0).join("missing")).is_err());
+}
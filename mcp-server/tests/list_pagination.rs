@@ -0,0 +1,112 @@
+// mcp-server/tests/list_pagination.rs
+use serde_json::{json, Value};
+
+use modelcontextprotocol_server::mcp_protocol::constants::error_codes;
+use modelcontextprotocol_server::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_server::mcp_protocol::types::tool::ToolCallResult;
+use modelcontextprotocol_server::ServerBuilder;
+
+mod common;
+use common::Harness;
+
+const COUNT: usize = 250;
+
+/// A server with `COUNT` tools and prompts, registered in a scrambled order
+fn builder(page_size: usize) -> ServerBuilder {
+    let mut builder = ServerBuilder::new("pagination-test", "0.1.0").with_page_size(page_size);
+    for i in 0..COUNT {
+        let name = format!("item-{:03}", (i * 7919) % COUNT);
+        builder = builder
+            .with_tool(&name, None, json!({"type": "object"}), |_| {
+                Ok(ToolCallResult {
+                    content: vec![ContentBlock::text("ok")],
+                    is_error: None,
+                    structured_content: None,
+                    meta: None,
+                })
+            })
+            .with_prompt(&name, None, None, |_| Ok(vec![]));
+    }
+    builder
+}
+
+/// Walk every page of a list method, returning the names in the order they were listed
+async fn list_all(harness: &mut Harness, method: &str, key: &str, page_size: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut params = json!({});
+
+    loop {
+        let result = harness.call(method, params).await;
+        let page = result[key].as_array().unwrap();
+        assert!(page.len() <= page_size);
+        names.extend(
+            page.iter()
+                .map(|item| item["name"].as_str().unwrap().to_string()),
+        );
+
+        match &result["nextCursor"] {
+            Value::String(cursor) => params = json!({"cursor": cursor}),
+            Value::Null => break,
+            other => panic!("unexpected cursor {}", other),
+        }
+    }
+
+    names
+}
+
+fn expected() -> Vec<String> {
+    (0..COUNT).map(|n| format!("item-{:03}", n)).collect()
+}
+
+#[tokio::test]
+async fn pages_through_tools_in_order() {
+    for page_size in [1, 7, 50, COUNT, COUNT + 1] {
+        let mut harness = Harness::start(builder(page_size)).await;
+        let names = list_all(&mut harness, "tools/list", "tools", page_size).await;
+        assert_eq!(names, expected(), "page size {}", page_size);
+        harness.stop().await;
+    }
+}
+
+#[tokio::test]
+async fn pages_through_prompts_in_order() {
+    for page_size in [1, 7, 50, COUNT, COUNT + 1] {
+        let mut harness = Harness::start(builder(page_size)).await;
+        let names = list_all(&mut harness, "prompts/list", "prompts", page_size).await;
+        assert_eq!(names, expected(), "page size {}", page_size);
+        harness.stop().await;
+    }
+}
+
+#[tokio::test]
+async fn last_page_has_no_cursor() {
+    let mut harness = Harness::start(builder(COUNT)).await;
+
+    for (method, key) in [("tools/list", "tools"), ("prompts/list", "prompts")] {
+        let result = harness.call(method, json!({})).await;
+        assert_eq!(result[key].as_array().unwrap().len(), COUNT);
+        assert!(result.get("nextCursor").is_none(), "{}", method);
+    }
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn cursors_the_server_did_not_issue_are_invalid_params() {
+    let mut harness = Harness::start(builder(10)).await;
+
+    for method in [
+        "tools/list",
+        "prompts/list",
+        "resources/list",
+        "resources/templates/list",
+    ] {
+        for cursor in ["not a cursor!", "//8"] {
+            let error = harness.call_err(method, json!({"cursor": cursor})).await;
+            assert_eq!(error.code, error_codes::INVALID_PARAMS, "{}", method);
+            assert_eq!(error.message, format!("Invalid cursor: {}", cursor));
+        }
+    }
+
+    harness.stop().await;
+}
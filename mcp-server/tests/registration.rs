@@ -0,0 +1,112 @@
+// mcp-server/tests/registration.rs
+//! Registration happens in place: no runtime is needed and the items are listed straight away
+use futures::executor::block_on;
+use serde_json::json;
+
+use modelcontextprotocol_server::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_server::mcp_protocol::types::prompt::Prompt;
+use modelcontextprotocol_server::mcp_protocol::types::resource::{
+    Resource, ResourceContent, ResourceTemplate,
+};
+use modelcontextprotocol_server::mcp_protocol::types::tool::{Tool, ToolCallResult};
+use modelcontextprotocol_server::prompts::PromptManager;
+use modelcontextprotocol_server::resources::ResourceManager;
+use modelcontextprotocol_server::tools::ToolManager;
+use modelcontextprotocol_server::CompletionQuery;
+
+#[test]
+fn tools_are_listed_as_soon_as_they_are_registered() {
+    let manager = ToolManager::new();
+    manager.register_tool(
+        Tool {
+            name: "echo".to_string(),
+            title: None,
+            description: None,
+            input_schema: json!({"type": "object"}),
+            output_schema: None,
+            annotations: None,
+        },
+        |_| {
+            Ok(ToolCallResult {
+                content: vec![ContentBlock::text("echo")],
+                is_error: None,
+                structured_content: None,
+                meta: None,
+            })
+        },
+    );
+
+    let tools = block_on(manager.list_tools());
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "echo");
+}
+
+#[test]
+fn resources_and_templates_are_listed_as_soon_as_they_are_registered() {
+    let manager = ResourceManager::new();
+    manager.register_resource(
+        Resource {
+            uri: "memo://today".to_string(),
+            name: "Today".to_string(),
+            description: None,
+            mime_type: None,
+            size: None,
+            annotations: None,
+            title: None,
+        },
+        || Ok(Vec::<ResourceContent>::new()),
+    );
+    manager.register_template(
+        ResourceTemplate {
+            uri_template: "memo://{day}".to_string(),
+            name: "Day".to_string(),
+            title: None,
+            description: None,
+            mime_type: None,
+            annotations: None,
+        },
+        |_| Ok(Vec::new()),
+    );
+
+    let (resources, _) = block_on(manager.list_resources(None, 10)).unwrap();
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, "memo://today");
+
+    let (templates, _) = block_on(manager.list_templates(None, 10)).unwrap();
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].uri_template, "memo://{day}");
+}
+
+#[test]
+fn completion_providers_answer_as_soon_as_they_are_registered() {
+    let resources = ResourceManager::new();
+    resources.register_completion_provider("memo://{day}", |query: CompletionQuery| async move {
+        Ok(query.prefix_matches(["monday", "tuesday"]))
+    });
+    let values =
+        block_on(resources.get_completions("memo://{day}", CompletionQuery::new("day", "m")))
+            .unwrap();
+    assert_eq!(values, ["monday"]);
+
+    let prompts = PromptManager::new();
+    prompts.register_prompt(
+        Prompt {
+            name: "greet".to_string(),
+            title: None,
+            description: None,
+            arguments: None,
+            annotations: None,
+        },
+        |_| Ok(Vec::new()),
+    );
+    prompts.register_completion_provider("greet", "name", |_: CompletionQuery| async move {
+        Ok(vec!["Ada".to_string()])
+    });
+
+    let (listed, _) = block_on(prompts.list_prompts(None, 10)).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].name, "greet");
+    let values =
+        block_on(prompts.get_completions("greet", CompletionQuery::new("name", ""))).unwrap();
+    assert_eq!(values, ["Ada"]);
+}
@@ -0,0 +1,216 @@
+// mcp-server/tests/resource_pagination.rs
+use std::collections::{BTreeMap, HashSet};
+
+use modelcontextprotocol_server::mcp_protocol::types::resource::{Resource, ResourceTemplate};
+use modelcontextprotocol_server::pagination;
+use modelcontextprotocol_server::resources::ResourceManager;
+
+const RESOURCE_COUNT: usize = 5000;
+
+fn resource(uri: &str) -> Resource {
+    Resource {
+        uri: uri.to_string(),
+        name: uri.to_string(),
+        description: None,
+        mime_type: Some("text/plain".to_string()),
+        size: None,
+        annotations: None,
+        title: None,
+    }
+}
+
+fn template(uri_template: &str) -> ResourceTemplate {
+    ResourceTemplate {
+        uri_template: uri_template.to_string(),
+        name: uri_template.to_string(),
+        description: None,
+        mime_type: None,
+        annotations: None,
+        title: None,
+    }
+}
+
+/// Register resources in a scrambled order so listing order cannot follow insertion order
+fn manager_with_resources(count: usize) -> ResourceManager {
+    let manager = ResourceManager::new();
+    for i in 0..count {
+        let n = (i * 7919) % count;
+        manager.register_resource(resource(&format!("file:///docs/{:05}.txt", n)), || Ok(vec![]));
+    }
+    manager
+}
+
+/// Walk every page, returning the URIs in the order they were listed
+async fn list_all(manager: &ResourceManager, page_size: usize) -> Vec<String> {
+    let mut uris = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let (page, next_cursor) = manager.list_resources(cursor, page_size).await.unwrap();
+        assert!(page.len() <= page_size);
+        uris.extend(page.into_iter().map(|r| r.uri));
+
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    uris
+}
+
+#[tokio::test]
+async fn pages_through_thousands_of_resources_in_order() {
+    let manager = manager_with_resources(RESOURCE_COUNT);
+
+    for page_size in [1, 7, 50, 128, RESOURCE_COUNT, RESOURCE_COUNT + 1] {
+        let uris = list_all(&manager, page_size).await;
+
+        let expected: Vec<String> = (0..RESOURCE_COUNT)
+            .map(|n| format!("file:///docs/{:05}.txt", n))
+            .collect();
+        assert_eq!(uris, expected, "page size {}", page_size);
+    }
+}
+
+#[tokio::test]
+async fn listing_is_stable_between_calls() {
+    let manager = manager_with_resources(RESOURCE_COUNT);
+
+    assert_eq!(list_all(&manager, 100).await, list_all(&manager, 100).await);
+}
+
+#[tokio::test]
+async fn last_page_has_no_cursor() {
+    let manager = manager_with_resources(100);
+
+    let (page, next_cursor) = manager.list_resources(None, 100).await.unwrap();
+    assert_eq!(page.len(), 100);
+    assert_eq!(next_cursor, None);
+
+    let (page, next_cursor) = manager.list_resources(None, 99).await.unwrap();
+    assert_eq!(page.len(), 99);
+    let (page, next_cursor) = manager.list_resources(next_cursor, 99).await.unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(next_cursor, None);
+}
+
+#[tokio::test]
+async fn cursors_survive_insertions() {
+    let manager = manager_with_resources(RESOURCE_COUNT);
+    let original = list_all(&manager, RESOURCE_COUNT).await;
+
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    let mut round = 0;
+
+    loop {
+        let (page, next_cursor) = manager.list_resources(cursor, 250).await.unwrap();
+        seen.extend(page.into_iter().map(|r| r.uri));
+
+        // Add resources before, inside and after the part of the listing already walked
+        for uri in [
+            format!("file:///aaa/{:05}.txt", round),
+            format!("file:///docs/{:05}.new", round * 250),
+            format!("file:///zzz/{:05}.txt", round),
+        ] {
+            manager.register_resource(resource(&uri), || Ok(vec![]));
+        }
+        round += 1;
+
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    // Nothing is listed twice and nothing that was there from the start is skipped
+    let unique: HashSet<&String> = seen.iter().collect();
+    assert_eq!(unique.len(), seen.len());
+    for uri in &original {
+        assert!(unique.contains(uri), "skipped {}", uri);
+    }
+
+    // Order is preserved across insertions
+    let mut sorted = seen.clone();
+    sorted.sort();
+    assert_eq!(seen, sorted);
+}
+
+#[test]
+fn cursors_survive_removals() {
+    let mut entries: BTreeMap<String, usize> = (0..RESOURCE_COUNT)
+        .map(|n| (format!("file:///docs/{:05}.txt", n), n))
+        .collect();
+
+    let mut seen = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let (page, next_cursor) = pagination::paginate(&entries, cursor.as_deref(), 100).unwrap();
+        let page: Vec<usize> = page.into_iter().copied().collect();
+        seen.extend(page.iter().copied());
+
+        // Remove the entry the cursor names, plus entries on both sides of it
+        if let Some(&last) = page.last() {
+            for n in [last, last.saturating_sub(50), last + 1, last + 3] {
+                entries.remove(&format!("file:///docs/{:05}.txt", n));
+            }
+        }
+
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    // Every entry not removed ahead of the walk is listed exactly once, in order
+    let expected: Vec<usize> = {
+        let mut removed_ahead = HashSet::new();
+        let mut expected = Vec::new();
+        let mut position = 0;
+        for n in 0..RESOURCE_COUNT {
+            if removed_ahead.contains(&n) {
+                continue;
+            }
+            expected.push(n);
+            position += 1;
+            if position % 100 == 0 {
+                removed_ahead.insert(n + 1);
+                removed_ahead.insert(n + 3);
+            }
+        }
+        expected
+    };
+    assert_eq!(seen, expected);
+}
+
+#[tokio::test]
+async fn rejects_cursors_the_server_did_not_issue() {
+    let manager = manager_with_resources(10);
+
+    assert!(manager.list_resources(Some("not a cursor!".to_string()), 5).await.is_err());
+    assert!(manager.list_resources(Some("//8".to_string()), 5).await.is_err());
+}
+
+#[tokio::test]
+async fn pages_through_templates() {
+    let manager = ResourceManager::new();
+    for i in (0..2500).rev() {
+        manager.register_template(template(&format!("db:///{:04}/{{table}}", i)), |_| Ok(vec![]));
+    }
+
+    let mut uri_templates = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (page, next_cursor) = manager.list_templates(cursor, 64).await.unwrap();
+        uri_templates.extend(page.into_iter().map(|t| t.uri_template));
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let expected: Vec<String> = (0..2500).map(|i| format!("db:///{:04}/{{table}}", i)).collect();
+    assert_eq!(uri_templates, expected);
+}
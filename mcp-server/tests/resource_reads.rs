@@ -0,0 +1,310 @@
+// mcp-server/tests/resource_reads.rs
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use futures::stream::{self, StreamExt};
+use serde_json::json;
+
+use modelcontextprotocol_server::context::RequestContext;
+use modelcontextprotocol_server::mcp_protocol::constants::error_codes;
+use modelcontextprotocol_server::mcp_protocol::messages::{JsonRpcMessage, RequestId};
+use modelcontextprotocol_server::mcp_protocol::types::resource::ResourceContent;
+use modelcontextprotocol_server::ServerBuilder;
+
+mod common;
+use common::Harness;
+
+fn text(uri: &str, text: impl Into<String>) -> ResourceContent {
+    ResourceContent {
+        uri: uri.to_string(),
+        mime_type: "text/plain".to_string(),
+        text: Some(text.into()),
+        blob: None,
+        meta: None,
+    }
+}
+
+/// Describe the extracted variables, sorted by name
+fn describe(variables: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<_, _> = variables.iter().collect();
+    serde_json::to_string(&sorted).unwrap()
+}
+
+#[tokio::test]
+async fn template_reads_receive_the_extracted_variables() {
+    let builder = ServerBuilder::new("read-test", "0.1.0")
+        .with_template(
+            "repo://{owner}/{name}{?ref}",
+            "Repository",
+            None,
+            None,
+            |variables| Ok(vec![text("repo://", describe(&variables))]),
+        )
+        .with_resource("repo://pinned/readme", "Pinned", None, None, None, || {
+            Ok(vec![text("repo://pinned/readme", "pinned")])
+        });
+    let mut harness = Harness::start(builder).await;
+
+    let result = harness
+        .call(
+            "resources/read",
+            json!({"uri": "repo://rust-lang/cargo?ref=release%2F1.0"}),
+        )
+        .await;
+    assert_eq!(
+        result["contents"][0]["text"],
+        r#"{"name":"cargo","owner":"rust-lang","ref":"release/1.0"}"#
+    );
+
+    let result = harness
+        .call("resources/read", json!({"uri": "repo://rust-lang/cargo"}))
+        .await;
+    assert_eq!(
+        result["contents"][0]["text"],
+        r#"{"name":"cargo","owner":"rust-lang"}"#
+    );
+
+    // A concrete resource wins over a template that also matches its URI
+    let result = harness
+        .call("resources/read", json!({"uri": "repo://pinned/readme"}))
+        .await;
+    assert_eq!(result["contents"][0]["text"], "pinned");
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn uris_nothing_serves_are_not_found() {
+    let builder = ServerBuilder::new("read-test", "0.1.0").with_template(
+        "repo://{owner}/{name}",
+        "Repository",
+        None,
+        None,
+        |_| Ok(vec![]),
+    );
+    let mut harness = Harness::start(builder).await;
+
+    for uri in ["repo://rust-lang", "other://rust-lang/cargo"] {
+        let error = harness
+            .call_err("resources/read", json!({"uri": uri}))
+            .await;
+        assert_eq!(error.code, error_codes::RESOURCE_NOT_FOUND);
+        assert_eq!(error.message, format!("Resource not found: {}", uri));
+        assert_eq!(error.data, Some(json!({"uri": uri})));
+    }
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn provider_failures_are_internal_errors() {
+    let builder = ServerBuilder::new("read-test", "0.1.0")
+        .with_template(
+            "repo://{owner}/{name}",
+            "Repository",
+            None,
+            None,
+            |variables| bail!("{} is archived", variables["name"]),
+        )
+        .with_resource("file:///broken", "Broken", None, None, None, || {
+            bail!("disk on fire")
+        });
+    let mut harness = Harness::start(builder).await;
+
+    let error = harness
+        .call_err("resources/read", json!({"uri": "repo://rust-lang/rls"}))
+        .await;
+    assert_eq!(error.code, error_codes::INTERNAL_ERROR);
+    assert_eq!(error.message, "Resource read error: rls is archived");
+    assert_eq!(error.data, Some(json!({"uri": "repo://rust-lang/rls"})));
+
+    let error = harness
+        .call_err("resources/read", json!({"uri": "file:///broken"}))
+        .await;
+    assert_eq!(error.code, error_codes::INTERNAL_ERROR);
+    assert_eq!(error.message, "Resource read error: disk on fire");
+
+    harness.stop().await;
+}
+
+/// Sets its flag when dropped, to observe a stream being abandoned
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn async_providers_receive_the_request_context() {
+    let builder = ServerBuilder::new("read-test", "0.1.0").with_async_resource(
+        "memo://today",
+        "Today",
+        None,
+        None,
+        None,
+        |context: RequestContext| async move {
+            tokio::task::yield_now().await;
+            let id = context.request_id.ok_or_else(|| anyhow!("no request id"))?;
+            Ok(vec![text(&context.uri, format!("request {}", id))])
+        },
+    );
+    let mut harness = Harness::start(builder).await;
+
+    let result = harness
+        .call("resources/read", json!({"uri": "memo://today"}))
+        .await;
+    assert_eq!(result["contents"][0]["uri"], "memo://today");
+    assert_eq!(result["contents"][0]["text"], "request 2");
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn streamed_contents_are_served_in_order() {
+    let builder = ServerBuilder::new("read-test", "0.1.0")
+        .with_streaming_resource(
+            "log://build",
+            "Build log",
+            None,
+            None,
+            None,
+            |context: RequestContext| async move {
+                let uri = context.uri;
+                Ok(stream::iter(1..=3)
+                    .then(move |line| {
+                        let uri = uri.clone();
+                        async move {
+                            tokio::task::yield_now().await;
+                            Ok(text(&uri, format!("line {}", line)))
+                        }
+                    })
+                    .boxed())
+            },
+        )
+        .with_streaming_resource(
+            "log://broken",
+            "Broken log",
+            None,
+            None,
+            None,
+            |context: RequestContext| async move {
+                Ok(stream::iter(vec![
+                    Ok(text(&context.uri, "line 1")),
+                    Err(anyhow!("log truncated")),
+                ])
+                .boxed())
+            },
+        );
+    let mut harness = Harness::start(builder).await;
+
+    let result = harness
+        .call("resources/read", json!({"uri": "log://build"}))
+        .await;
+    let lines: Vec<_> = result["contents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|content| content["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(lines, ["line 1", "line 2", "line 3"]);
+
+    // An error part way through fails the whole read
+    let error = harness
+        .call_err("resources/read", json!({"uri": "log://broken"}))
+        .await;
+    assert_eq!(error.code, error_codes::INTERNAL_ERROR);
+    assert_eq!(error.message, "Resource read error: log truncated");
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn blocking_providers_do_not_hold_up_other_requests() {
+    let builder = ServerBuilder::new("read-test", "0.1.0").with_resource(
+        "file:///slow",
+        "Slow",
+        None,
+        None,
+        None,
+        || {
+            std::thread::sleep(Duration::from_millis(300));
+            Ok(vec![text("file:///slow", "done")])
+        },
+    );
+    let mut harness = Harness::start(builder).await;
+
+    // On a single-threaded runtime, a provider run inline would block the listing until it finished
+    let read = harness.send_request("resources/read", Some(json!({"uri": "file:///slow"})));
+    let list = harness.send_request("resources/list", None);
+
+    let first = harness.reply().await;
+    assert_eq!(first.id(), Some(&RequestId::Number(list)));
+    let second = harness.reply().await;
+    assert_eq!(second.id(), Some(&RequestId::Number(read)));
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn cancelled_reads_get_no_response() {
+    let produced = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicBool::new(false));
+    let builder = ServerBuilder::new("read-test", "0.1.0").with_streaming_resource(
+        "log://follow",
+        "Followed log",
+        None,
+        None,
+        None,
+        {
+            let produced = produced.clone();
+            let dropped = dropped.clone();
+            move |context: RequestContext| {
+                let produced = produced.clone();
+                let guard = DropFlag(dropped.clone());
+                async move {
+                    // The second line never comes, as if waiting on a log that is not written to
+                    let lines = stream::iter([0, 1]).then(move |line| {
+                        let _guard = &guard;
+                        produced.fetch_add(1, Ordering::SeqCst);
+                        let uri = context.uri.clone();
+                        async move {
+                            if line > 0 {
+                                futures::future::pending::<()>().await;
+                            }
+                            Ok(text(&uri, format!("line {}", line)))
+                        }
+                    });
+                    Ok(lines.boxed())
+                }
+            }
+        },
+    );
+    let mut harness = Harness::start(builder).await;
+
+    let read = harness.send_request("resources/read", Some(json!({"uri": "log://follow"})));
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while produced.load(Ordering::SeqCst) < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    harness.notify(
+        "notifications/cancelled",
+        Some(json!({"requestId": read, "reason": "no longer needed"})),
+    );
+    assert!(!harness.replies_within(Duration::from_millis(200)).await);
+    assert!(dropped.load(Ordering::SeqCst));
+
+    // The server keeps serving other requests
+    let reply = harness.request("resources/list", None).await;
+    assert!(matches!(reply, JsonRpcMessage::Response(_)), "{:?}", reply);
+
+    harness.stop().await;
+}
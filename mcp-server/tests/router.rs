@@ -0,0 +1,177 @@
+// mcp-server/tests/router.rs
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use modelcontextprotocol_server::mcp_protocol::constants::error_codes;
+use modelcontextprotocol_server::mcp_protocol::messages::JsonRpcErrorObject;
+use modelcontextprotocol_server::{
+    HandlerResult, IncomingRequest, Middleware, Next, Router, ServerBuilder,
+};
+
+mod common;
+use common::Harness;
+
+/// Records the order requests pass through it
+struct Trace {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Middleware for Trace {
+    async fn handle(&self, request: IncomingRequest, next: Next<'_>) -> HandlerResult {
+        self.log.lock().unwrap().push(format!("{} in", self.name));
+        let result = next.run(request).await;
+        self.log.lock().unwrap().push(format!("{} out", self.name));
+        result
+    }
+}
+
+/// Rejects requests without the right token
+struct RequireToken;
+
+#[async_trait]
+impl Middleware for RequireToken {
+    async fn handle(&self, request: IncomingRequest, next: Next<'_>) -> HandlerResult {
+        let token = request
+            .params
+            .as_ref()
+            .and_then(|params| params.get("token"))
+            .and_then(Value::as_str);
+        if token != Some("secret") {
+            return Err(JsonRpcErrorObject::new(-32001, "Unauthorized"));
+        }
+        next.run(request).await
+    }
+}
+
+async fn echo(request: IncomingRequest) -> HandlerResult {
+    Ok(request.params.unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn routes_by_method() {
+    let router = Router::new().route("experimental/echo", echo);
+
+    let result = router
+        .handle(IncomingRequest::new(
+            1,
+            "experimental/echo",
+            Some(json!({"x": 1})),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result, json!({"x": 1}));
+
+    let err = router
+        .handle(IncomingRequest::new(2, "experimental/missing", None))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, error_codes::METHOD_NOT_FOUND);
+}
+
+#[tokio::test]
+async fn middleware_wraps_handlers_in_order() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let handler_log = log.clone();
+    let router = Router::new()
+        .route("experimental/echo", move |request: IncomingRequest| {
+            handler_log.lock().unwrap().push("handler".to_string());
+            echo(request)
+        })
+        .layer(Trace {
+            name: "outer",
+            log: log.clone(),
+        })
+        .layer(Trace {
+            name: "inner",
+            log: log.clone(),
+        });
+
+    router
+        .handle(IncomingRequest::new(1, "experimental/echo", None))
+        .await
+        .unwrap();
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["outer in", "inner in", "handler", "inner out", "outer out"]
+    );
+}
+
+#[tokio::test]
+async fn middleware_can_answer_without_the_handler() {
+    let router = Router::new()
+        .route("experimental/echo", echo)
+        .layer(RequireToken);
+
+    let err = router
+        .handle(IncomingRequest::new(1, "experimental/echo", None))
+        .await
+        .unwrap_err();
+    assert_eq!(err.message, "Unauthorized");
+
+    let params = json!({"token": "secret"});
+    let result = router
+        .handle(IncomingRequest::new(
+            2,
+            "experimental/echo",
+            Some(params.clone()),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result, params);
+}
+
+#[tokio::test]
+async fn handlers_see_the_request_meta() {
+    let router = Router::new().route("experimental/meta", |request: IncomingRequest| async move {
+        let meta = request.meta.unwrap_or_default();
+        Ok(json!({
+            "progressToken": meta.progress_token,
+            "traceId": meta.get("example.com/trace-id"),
+        }))
+    });
+
+    let result = router
+        .handle(IncomingRequest::new(
+            1,
+            "experimental/meta",
+            Some(json!({
+                "_meta": {"progressToken": 5, "example.com/trace-id": "t-1"}
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result, json!({"progressToken": 5, "traceId": "t-1"}));
+}
+
+#[tokio::test]
+async fn server_dispatches_custom_methods_through_middleware() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let builder = ServerBuilder::new("router-test", "0.1.0")
+        .with_route("experimental/echo", echo)
+        .with_middleware(Trace {
+            name: "trace",
+            log: log.clone(),
+        });
+    let mut harness = Harness::connect(builder);
+
+    // Custom methods need an initialized session like any other
+    let error = harness.call_err("experimental/echo", json!({})).await;
+    assert_eq!(error.code, error_codes::SERVER_NOT_INITIALIZED);
+
+    harness.initialize().await;
+
+    let result = harness.call("experimental/echo", json!({"x": 1})).await;
+    assert_eq!(result, json!({"x": 1}));
+
+    // Built-in methods pass through the same middleware
+    let result = harness.call("tools/list", json!({})).await;
+    assert_eq!(result["tools"], json!([]));
+
+    assert_eq!(log.lock().unwrap().len(), 8);
+
+    harness.stop().await;
+}
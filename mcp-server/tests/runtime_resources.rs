@@ -0,0 +1,196 @@
+// mcp-server/tests/runtime_resources.rs
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use modelcontextprotocol_server::mcp_protocol::constants::{error_codes, methods};
+use modelcontextprotocol_server::mcp_protocol::types::resource::{
+    Resource, ResourceContent, ResourceTemplate,
+};
+use modelcontextprotocol_server::pagination::DEFAULT_PAGE_SIZE;
+use modelcontextprotocol_server::resources::ResourceManager;
+use modelcontextprotocol_server::ServerBuilder;
+
+mod common;
+use common::Harness;
+
+const WAIT: Duration = Duration::from_secs(2);
+
+fn add_resource(manager: &ResourceManager, uri: &str) {
+    let resource = Resource {
+        uri: uri.to_string(),
+        name: uri.to_string(),
+        description: None,
+        mime_type: None,
+        size: None,
+        annotations: None,
+        title: None,
+    };
+    let reply = uri.to_string();
+    manager.register_resource(resource, move || {
+        Ok(vec![ResourceContent {
+            uri: reply.clone(),
+            mime_type: "text/plain".to_string(),
+            text: Some(reply.clone()),
+            blob: None,
+            meta: None,
+        }])
+    });
+}
+
+fn add_template(manager: &ResourceManager, uri_template: &str) {
+    let template = ResourceTemplate {
+        uri_template: uri_template.to_string(),
+        name: uri_template.to_string(),
+        description: None,
+        mime_type: None,
+        annotations: None,
+        title: None,
+    };
+    manager.register_template(template, |variables| {
+        Ok(vec![ResourceContent {
+            uri: format!("memo://t/{}", variables["id"]),
+            mime_type: "text/plain".to_string(),
+            text: Some("templated".to_string()),
+            blob: None,
+            meta: None,
+        }])
+    });
+}
+
+/// One field of every entry in a list result
+fn field(result: &Value, list: &str, field: &str) -> Vec<String> {
+    result[list]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry[field].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn resource_uris(harness: &mut Harness) -> Vec<String> {
+    let result = harness.call("resources/list", json!({})).await;
+    field(&result, "resources", "uri")
+}
+
+async fn template_uris(harness: &mut Harness) -> Vec<String> {
+    let result = harness.call("resources/templates/list", json!({})).await;
+    field(&result, "resourceTemplates", "uriTemplate")
+}
+
+/// Wait for notifications and check they are all `resources/list_changed`, returning how many came
+async fn list_changed(harness: &mut Harness, count: usize, wait: Duration) -> usize {
+    let notifications = harness.take_notifications(count, wait).await;
+    for notification in &notifications {
+        assert_eq!(notification.method, methods::RESOURCES_LIST_CHANGED);
+    }
+    notifications.len()
+}
+
+#[tokio::test]
+async fn runtime_changes_are_listed_and_notified() {
+    let manager = Arc::new(ResourceManager::new());
+    add_resource(&manager, "memo://first");
+    add_template(&manager, "memo://t/{id}");
+    let builder =
+        ServerBuilder::new("runtime-test", "0.1.0").with_resource_manager(manager.clone());
+    let mut harness = Harness::start(builder).await;
+    assert_eq!(resource_uris(&mut harness).await, ["memo://first"]);
+    assert_eq!(template_uris(&mut harness).await, ["memo://t/{id}"]);
+
+    add_resource(&manager, "memo://second");
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+    assert_eq!(
+        resource_uris(&mut harness).await,
+        ["memo://first", "memo://second"]
+    );
+
+    let removed = manager.remove_resource("memo://first").unwrap();
+    assert_eq!(removed.uri, "memo://first");
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+    assert_eq!(resource_uris(&mut harness).await, ["memo://second"]);
+    let error = harness
+        .call_err("resources/read", json!({"uri": "memo://first"}))
+        .await;
+    assert_eq!(error.code, error_codes::RESOURCE_NOT_FOUND);
+
+    let result = harness
+        .call("resources/read", json!({"uri": "memo://t/1"}))
+        .await;
+    assert_eq!(result["contents"][0]["text"], "templated");
+    let removed = manager.remove_template("memo://t/{id}").unwrap();
+    assert_eq!(removed.uri_template, "memo://t/{id}");
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+    assert!(template_uris(&mut harness).await.is_empty());
+    let error = harness
+        .call_err("resources/read", json!({"uri": "memo://t/1"}))
+        .await;
+    assert_eq!(error.code, error_codes::RESOURCE_NOT_FOUND);
+
+    // Removing what is not there fails and changes nothing
+    assert!(manager.remove_resource("memo://first").is_err());
+    assert!(manager.remove_template("memo://t/{id}").is_err());
+    assert_eq!(
+        list_changed(&mut harness, 1, Duration::from_millis(100)).await,
+        0
+    );
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn changes_are_only_notified_once_the_client_is_initialized() {
+    let manager = Arc::new(ResourceManager::new());
+    let builder =
+        ServerBuilder::new("runtime-test", "0.1.0").with_resource_manager(manager.clone());
+    let mut harness = Harness::connect(builder);
+    harness
+        .call(
+            "initialize",
+            json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "1"}
+            }),
+        )
+        .await;
+
+    // Answered with an error, but the round trip lets the server see the change
+    add_resource(&manager, "memo://early");
+    harness.request("resources/list", None).await;
+    assert_eq!(
+        list_changed(&mut harness, 1, Duration::from_millis(100)).await,
+        0
+    );
+
+    harness.notify("notifications/initialized", None);
+    assert_eq!(resource_uris(&mut harness).await, ["memo://early"]);
+    add_resource(&manager, "memo://late");
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn a_burst_of_changes_does_not_stop_notifications() {
+    let manager = Arc::new(ResourceManager::new());
+    let builder =
+        ServerBuilder::new("runtime-test", "0.1.0").with_resource_manager(manager.clone());
+    let mut harness = Harness::start(builder).await;
+    assert!(resource_uris(&mut harness).await.is_empty());
+
+    // More changes than the list change channel holds, so the listener falls behind
+    for n in 0..500 {
+        add_resource(&manager, &format!("memo://{:03}", n));
+    }
+    let burst = list_changed(&mut harness, usize::MAX, Duration::from_millis(500)).await;
+    assert!((1..500).contains(&burst), "{} notifications", burst);
+    assert_eq!(resource_uris(&mut harness).await.len(), DEFAULT_PAGE_SIZE);
+
+    // The listener is still running after lagging
+    manager.remove_resource("memo://000").unwrap();
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+
+    harness.stop().await;
+}
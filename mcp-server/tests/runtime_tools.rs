@@ -0,0 +1,116 @@
+// mcp-server/tests/runtime_tools.rs
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
+
+use modelcontextprotocol_server::mcp_protocol::constants::methods;
+use modelcontextprotocol_server::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_server::mcp_protocol::types::tool::{Tool, ToolCallResult};
+use modelcontextprotocol_server::pagination::DEFAULT_PAGE_SIZE;
+use modelcontextprotocol_server::tools::{ToolDefinition, ToolManager};
+use modelcontextprotocol_server::ServerBuilder;
+
+mod common;
+use common::Harness;
+
+const WAIT: Duration = Duration::from_secs(2);
+
+fn definition(name: &str) -> ToolDefinition {
+    let reply = name.to_string();
+    ToolDefinition {
+        tool: Tool {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            input_schema: json!({"type": "object"}),
+            output_schema: None,
+            annotations: None,
+        },
+        handler: Arc::new(move |_, _| {
+            let result = ToolCallResult {
+                content: vec![ContentBlock::text(reply.clone())],
+                is_error: None,
+                structured_content: None,
+                meta: None,
+            };
+            Box::pin(async move { Ok(result) })
+        }),
+    }
+}
+
+/// Names from a `tools/list` result
+async fn tool_names(harness: &mut Harness) -> Vec<String> {
+    let result = harness.call("tools/list", json!({})).await;
+    result["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// Wait for notifications and check they are all `tools/list_changed`, returning how many came
+async fn list_changed(harness: &mut Harness, count: usize, wait: Duration) -> usize {
+    let notifications = harness.take_notifications(count, wait).await;
+    for notification in &notifications {
+        assert_eq!(notification.method, methods::TOOLS_LIST_CHANGED);
+    }
+    notifications.len()
+}
+
+#[tokio::test]
+async fn runtime_changes_are_listed_and_notified() {
+    let manager = Arc::new(ToolManager::new());
+    manager.add_tool(definition("first")).unwrap();
+    let builder = ServerBuilder::new("runtime-test", "0.1.0").with_tool_manager(manager.clone());
+    let mut harness = Harness::start(builder).await;
+    assert_eq!(tool_names(&mut harness).await, ["first"]);
+
+    manager.add_tool(definition("second")).unwrap();
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+    assert_eq!(tool_names(&mut harness).await, ["first", "second"]);
+    let result = harness
+        .call("tools/call", json!({"name": "second", "arguments": {}}))
+        .await;
+    assert_eq!(result["content"][0]["text"], "second");
+
+    manager.unregister_tool("first").unwrap();
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+    assert_eq!(tool_names(&mut harness).await, ["second"]);
+    let error = harness
+        .call_err("tools/call", json!({"name": "first", "arguments": {}}))
+        .await;
+    assert!(
+        error.message.contains("Tool not found: first"),
+        "{}",
+        error.message
+    );
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn a_burst_of_changes_does_not_stop_notifications() {
+    let manager = Arc::new(ToolManager::new());
+    let builder = ServerBuilder::new("runtime-test", "0.1.0").with_tool_manager(manager.clone());
+    let mut harness = Harness::start(builder).await;
+    // Changes made before the server has seen `notifications/initialized` are not notified
+    assert!(tool_names(&mut harness).await.is_empty());
+
+    // More changes than the update channel holds, so the listener falls behind
+    for n in 0..500 {
+        manager
+            .add_tool(definition(&format!("tool-{:03}", n)))
+            .unwrap();
+    }
+    let burst = list_changed(&mut harness, usize::MAX, Duration::from_millis(500)).await;
+    assert!((1..500).contains(&burst), "{} notifications", burst);
+    assert_eq!(tool_names(&mut harness).await.len(), DEFAULT_PAGE_SIZE);
+
+    // The listener is still running after lagging
+    manager.unregister_tool("tool-000").unwrap();
+    assert_eq!(list_changed(&mut harness, 1, WAIT).await, 1);
+
+    harness.stop().await;
+}
@@ -0,0 +1,285 @@
+// mcp-server/tests/typed_tools.rs
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use modelcontextprotocol_server::mcp_protocol::constants::error_codes;
+use modelcontextprotocol_server::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_server::mcp_protocol::types::tool::{Tool, ToolCallResult};
+use modelcontextprotocol_server::tools::ToolManager;
+use modelcontextprotocol_server::ServerBuilder;
+
+mod common;
+use common::Harness;
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+enum Priority {
+    Low,
+    High,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct Order {
+    /// Where to ship the order
+    shipping: Address,
+    billing: Option<Address>,
+    previous: Vec<Address>,
+    priority: Priority,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct Ping {
+    /// The schema only says "string"; parsing the address is left to serde
+    host: IpAddr,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct WordCountArgs {
+    text: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct WordCount {
+    words: usize,
+}
+
+fn text(text: impl Into<String>) -> ToolCallResult {
+    ToolCallResult {
+        content: vec![ContentBlock::text(text)],
+        is_error: None,
+        structured_content: None,
+        meta: None,
+    }
+}
+
+/// Every key anywhere in a JSON value
+fn keys(value: &Value) -> Vec<String> {
+    match value {
+        Value::Object(object) => object
+            .iter()
+            .flat_map(|(key, value)| std::iter::once(key.clone()).chain(keys(value)))
+            .collect(),
+        Value::Array(items) => items.iter().flat_map(keys).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn generated_schemas_are_self_contained() {
+    let manager = ToolManager::new();
+    manager.register_typed_tool("order", Some("Place an order"), |_: Order| Ok(text("ok")));
+
+    let tools = manager.list_tools().await;
+    let schema = &tools[0].input_schema;
+
+    let keys = keys(schema);
+    for reference in ["$ref", "definitions", "$defs", "$schema"] {
+        assert!(
+            !keys.iter().any(|key| key == reference),
+            "{} in {}",
+            reference,
+            schema
+        );
+    }
+    assert!(schema.get("title").is_none());
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["shipping"]["type"], "object");
+    assert_eq!(
+        schema["properties"]["shipping"]["description"],
+        "Where to ship the order"
+    );
+    assert_eq!(schema["properties"]["previous"]["items"]["type"], "object");
+    assert_eq!(
+        schema["properties"]["priority"]["enum"],
+        json!(["Low", "High"])
+    );
+}
+
+#[tokio::test]
+async fn arguments_that_fail_to_deserialize_are_invalid_params() {
+    let builder =
+        ServerBuilder::new("typed-test", "0.1.0")
+            .with_typed_tool("ping", None, |args: Ping| Ok(text(args.host.to_string())));
+    let mut harness = Harness::start(builder).await;
+
+    // Passes schema validation, fails deserialization
+    let error = harness
+        .call_err(
+            "tools/call",
+            json!({"name": "ping", "arguments": {"host": "not-an-address"}}),
+        )
+        .await;
+    assert_eq!(error.code, error_codes::INVALID_PARAMS);
+    let data = error.data.unwrap();
+    assert_eq!(data["name"], "ping");
+    assert_eq!(data["errors"].as_array().unwrap().len(), 1);
+
+    // Fails schema validation before deserialization is attempted
+    let error = harness
+        .call_err("tools/call", json!({"name": "ping", "arguments": {}}))
+        .await;
+    assert_eq!(error.code, error_codes::INVALID_PARAMS);
+
+    let result = harness
+        .call(
+            "tools/call",
+            json!({"name": "ping", "arguments": {"host": "127.0.0.1"}}),
+        )
+        .await;
+    assert_eq!(result["content"][0]["text"], "127.0.0.1");
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn structured_tools_declare_an_output_schema() {
+    let manager = ToolManager::new();
+    manager
+        .register_structured_tool("count", None, |args: WordCountArgs| {
+            Ok(WordCount {
+                words: args.text.split_whitespace().count(),
+            })
+        })
+        .unwrap();
+
+    let tools = manager.list_tools().await;
+    let schema = tools[0].output_schema.as_ref().unwrap();
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["words"]["type"], "integer");
+    assert_eq!(schema["required"], json!(["words"]));
+}
+
+#[tokio::test]
+async fn structured_output_has_a_text_fallback() {
+    let builder = ServerBuilder::new("typed-test", "0.1.0").with_structured_tool(
+        "count",
+        None,
+        |args: WordCountArgs| {
+            Ok(WordCount {
+                words: args.text.split_whitespace().count(),
+            })
+        },
+    );
+    let mut harness = Harness::start(builder).await;
+
+    let result = harness
+        .call(
+            "tools/call",
+            json!({"name": "count", "arguments": {"text": "one two three"}}),
+        )
+        .await;
+    assert_eq!(result["structuredContent"], json!({"words": 3}));
+    let fallback: Value =
+        serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(fallback, result["structuredContent"]);
+    assert_eq!(result["isError"], false);
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn structured_output_must_be_an_object() {
+    let manager = ToolManager::new();
+    let error = manager
+        .register_structured_tool("count", None, |args: WordCountArgs| {
+            Ok(args.text.split_whitespace().count())
+        })
+        .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Output schema of tool count must have type \"object\""),
+        "{}",
+        error
+    );
+    assert!(manager.list_tools().await.is_empty());
+
+    // The builder reports the failure from `build`
+    let error = ServerBuilder::new("typed-test", "0.1.0")
+        .with_structured_tool("words", None, |args: WordCountArgs| {
+            Ok(args
+                .text
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>())
+        })
+        .build()
+        .err()
+        .unwrap();
+    assert!(
+        error.to_string().starts_with("Output schema of tool words"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn output_that_does_not_match_the_schema_is_an_internal_error() {
+    let manager = Arc::new(ToolManager::new());
+    let tool = |name: &str| Tool {
+        name: name.to_string(),
+        title: None,
+        description: None,
+        input_schema: json!({"type": "object"}),
+        output_schema: Some(json!({
+            "type": "object",
+            "properties": {"words": {"type": "integer"}},
+            "required": ["words"]
+        })),
+        annotations: None,
+    };
+    manager.register_tool(tool("wrong"), |_| {
+        Ok(ToolCallResult {
+            structured_content: Some(json!({"words": "three"})),
+            ..text("three")
+        })
+    });
+    manager.register_tool(tool("missing"), |_| Ok(text("3")));
+    manager.register_tool(tool("failed"), |_| {
+        Ok(ToolCallResult {
+            is_error: Some(true),
+            ..text("could not count")
+        })
+    });
+
+    let builder = ServerBuilder::new("typed-test", "0.1.0").with_tool_manager(manager);
+    let mut harness = Harness::start(builder).await;
+
+    let error = harness
+        .call_err("tools/call", json!({"name": "wrong", "arguments": {}}))
+        .await;
+    assert_eq!(error.code, error_codes::INTERNAL_ERROR);
+    assert_eq!(
+        error.data,
+        Some(json!({
+            "name": "wrong",
+            "errors": [{"instancePath": "/words", "message": "expected integer, found string"}]
+        }))
+    );
+
+    let error = harness
+        .call_err("tools/call", json!({"name": "missing", "arguments": {}}))
+        .await;
+    assert_eq!(error.code, error_codes::INTERNAL_ERROR);
+    assert_eq!(error.data.unwrap()["errors"][0]["instancePath"], "");
+
+    // Tool errors are reported as-is, without structured content
+    let result = harness
+        .call("tools/call", json!({"name": "failed", "arguments": {}}))
+        .await;
+    assert_eq!(result["isError"], true);
+
+    harness.stop().await;
+}
@@ -0,0 +1,220 @@
+// mcp-server/tests/validation.rs
+use serde_json::{json, Value};
+
+use modelcontextprotocol_server::mcp_protocol::constants::error_codes;
+use modelcontextprotocol_server::mcp_protocol::types::content::ContentBlock;
+use modelcontextprotocol_server::mcp_protocol::types::tool::ToolCallResult;
+use modelcontextprotocol_server::tools::validation::validate;
+use modelcontextprotocol_server::ServerBuilder;
+
+mod common;
+use common::Harness;
+
+/// The `(instancePath, message)` of every violation
+fn violations(schema: &Value, instance: &Value) -> Vec<(String, String)> {
+    validate(schema, instance)
+        .into_iter()
+        .map(|violation| (violation.instance_path, violation.message))
+        .collect()
+}
+
+/// The instance paths of every violation
+fn paths(schema: &Value, instance: &Value) -> Vec<String> {
+    validate(schema, instance)
+        .into_iter()
+        .map(|violation| violation.instance_path)
+        .collect()
+}
+
+#[test]
+fn reports_type_mismatches() {
+    let schema = json!({"type": "string"});
+    assert!(validate(&schema, &json!("hello")).is_empty());
+    assert_eq!(
+        violations(&schema, &json!(42)),
+        [("".to_string(), "expected string, found integer".to_string())]
+    );
+
+    let schema = json!({"type": ["integer", "null"]});
+    assert!(validate(&schema, &json!(3)).is_empty());
+    assert!(validate(&schema, &json!(3.0)).is_empty());
+    assert!(validate(&schema, &Value::Null).is_empty());
+    assert_eq!(paths(&schema, &json!(3.5)), [""]);
+}
+
+#[test]
+fn reports_missing_required_properties() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"city": {"type": "string"}, "units": {"type": "string"}},
+        "required": ["city", "units"]
+    });
+    assert!(validate(&schema, &json!({"city": "Oslo", "units": "metric"})).is_empty());
+    assert_eq!(
+        violations(&schema, &json!({"city": "Oslo"})),
+        [(
+            "".to_string(),
+            "missing required property \"units\"".to_string()
+        )]
+    );
+}
+
+#[test]
+fn reports_values_outside_an_enum() {
+    let schema = json!({"enum": ["metric", "imperial"]});
+    assert!(validate(&schema, &json!("metric")).is_empty());
+    assert_eq!(paths(&schema, &json!("kelvin")), [""]);
+}
+
+#[test]
+fn checks_numeric_ranges() {
+    let schema = json!({"type": "number", "minimum": 0, "maximum": 10});
+    assert!(validate(&schema, &json!(0)).is_empty());
+    assert!(validate(&schema, &json!(10)).is_empty());
+    assert_eq!(
+        violations(&schema, &json!(-1)),
+        [(
+            "".to_string(),
+            "-1 is less than the minimum of 0".to_string()
+        )]
+    );
+    assert_eq!(
+        violations(&schema, &json!(10.5)),
+        [(
+            "".to_string(),
+            "10.5 is greater than the maximum of 10".to_string()
+        )]
+    );
+
+    let schema = json!({"exclusiveMinimum": 0, "exclusiveMaximum": 1});
+    assert!(validate(&schema, &json!(0.5)).is_empty());
+    assert_eq!(paths(&schema, &json!(0)), [""]);
+    assert_eq!(paths(&schema, &json!(1)), [""]);
+}
+
+#[test]
+fn multiple_of_tolerates_floating_point_rounding() {
+    let schema = json!({"multipleOf": 0.1});
+    // 0.3 / 0.1 is 2.9999999999999996
+    assert!(validate(&schema, &json!(0.3)).is_empty());
+    assert!(validate(&schema, &json!(0.7)).is_empty());
+    assert!(validate(&schema, &json!(12.3)).is_empty());
+    assert_eq!(paths(&schema, &json!(0.35)), [""]);
+
+    let schema = json!({"multipleOf": 3});
+    assert!(validate(&schema, &json!(9)).is_empty());
+    assert_eq!(paths(&schema, &json!(10)), [""]);
+}
+
+#[test]
+fn checks_string_patterns() {
+    let schema = json!({"type": "string", "pattern": "^[a-z]+-[0-9]+$"});
+    assert!(validate(&schema, &json!("issue-42")).is_empty());
+    assert_eq!(
+        violations(&schema, &json!("Issue 42")),
+        [(
+            "".to_string(),
+            "string does not match the pattern ^[a-z]+-[0-9]+$".to_string()
+        )]
+    );
+
+    // Patterns are not anchored unless they say so
+    assert!(validate(&json!({"pattern": "[0-9]"}), &json!("abc1")).is_empty());
+}
+
+#[test]
+fn reports_nested_property_and_array_item_paths() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "address": {
+                "type": "object",
+                "properties": {"zip": {"type": "string"}},
+                "required": ["zip"]
+            },
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "a/b": {"type": "integer"}
+        }
+    });
+
+    let instance = json!({
+        "address": {"zip": 12345},
+        "tags": ["ok", 7, "fine", false],
+        "a/b": "x"
+    });
+    let mut found = paths(&schema, &instance);
+    found.sort();
+    assert_eq!(found, ["/address/zip", "/a~1b", "/tags/1", "/tags/3"]);
+
+    assert_eq!(paths(&schema, &json!({"address": {}})), ["/address"]);
+}
+
+#[test]
+fn rejects_unexpected_properties_when_closed() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"city": {"type": "string"}},
+        "additionalProperties": false
+    });
+    assert_eq!(
+        violations(&schema, &json!({"city": "Oslo", "country": "NO"})),
+        [(
+            "".to_string(),
+            "unexpected property \"country\"".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn invalid_arguments_are_reported_as_invalid_params() {
+    let builder = ServerBuilder::new("validation-test", "0.1.0").with_tool(
+        "forecast",
+        None,
+        json!({
+            "type": "object",
+            "properties": {
+                "city": {"type": "string"},
+                "days": {"type": "integer", "minimum": 1}
+            },
+            "required": ["city"]
+        }),
+        |_| {
+            Ok(ToolCallResult {
+                content: vec![ContentBlock::text("sunny")],
+                is_error: None,
+                structured_content: None,
+                meta: None,
+            })
+        },
+    );
+    let mut harness = Harness::start(builder).await;
+
+    let error = harness
+        .call_err(
+            "tools/call",
+            json!({"name": "forecast", "arguments": {"days": 0}}),
+        )
+        .await;
+    assert_eq!(error.code, error_codes::INVALID_PARAMS);
+    assert_eq!(
+        error.data,
+        Some(json!({
+            "name": "forecast",
+            "errors": [
+                {"instancePath": "", "message": "missing required property \"city\""},
+                {"instancePath": "/days", "message": "0 is less than the minimum of 1"}
+            ]
+        }))
+    );
+
+    // Valid arguments reach the handler
+    let result = harness
+        .call(
+            "tools/call",
+            json!({"name": "forecast", "arguments": {"city": "Oslo", "days": 3}}),
+        )
+        .await;
+    assert_eq!(result["content"][0]["text"], "sunny");
+
+    harness.stop().await;
+}